@@ -47,7 +47,7 @@ fn generate_build_rs_checksum() -> Result<String, Box<dyn std::error::Error>> {
 fn get_latest_semantic_git_tag() -> Result<String, Box<dyn std::error::Error>> {
     // Get all tags
     let output = Command::new("git")
-        .args(&["tag", "--list", "--sort=-version:refname"])
+        .args(["tag", "--list", "--sort=-version:refname"])
         .output()?;
 
     if !output.status.success() {
@@ -95,7 +95,7 @@ fn get_latest_semantic_git_tag() -> Result<String, Box<dyn std::error::Error>> {
 
 fn get_git_describe() -> Result<String, Box<dyn std::error::Error>> {
     let output = Command::new("git")
-        .args(&["describe", "--always", "--dirty"])
+        .args(["describe", "--always", "--dirty"])
         .output()?;
 
     if output.status.success() {
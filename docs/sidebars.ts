@@ -27,8 +27,16 @@ const sidebars: SidebarsConfig = {
           items: [
             'commands/gitignore/gitignore',
             'commands/gitignore/gitignore-add',
+            'commands/gitignore/gitignore-check',
+            'commands/gitignore/gitignore-detect',
+            'commands/gitignore/gitignore-diff',
+            'commands/gitignore/gitignore-explain',
+            'commands/gitignore/gitignore-export',
+            'commands/gitignore/gitignore-lint',
             'commands/gitignore/gitignore-list',
             'commands/gitignore/gitignore-preview',
+            'commands/gitignore/gitignore-remove',
+            'commands/gitignore/gitignore-suggest',
           ],
         },
         {
@@ -57,8 +65,22 @@ const sidebars: SidebarsConfig = {
           items: [
             'commands/license/license',
             'commands/license/license-add',
+            'commands/license/license-choose',
+            'commands/license/license-compat',
+            'commands/license/license-detect',
+            'commands/license/license-header-add',
+            'commands/license/license-header-check',
             'commands/license/license-list',
+            'commands/license/license-notice',
             'commands/license/license-preview',
+            'commands/license/license-reuse',
+          ],
+        },
+        {
+          type: 'category',
+          label: 'Cache',
+          items: [
+            'commands/cache/cache-info',
           ],
         },
         {
@@ -1,3 +1,6 @@
+use std::error::Error;
+use std::fmt;
+
 use clap::ValueEnum;
 
 #[derive(Clone, ValueEnum)]
@@ -11,3 +14,16 @@ pub enum TemplateCategory {
 pub trait Runnable {
     fn run(&self) -> anyhow::Result<()>;
 }
+
+/// A check that ran to completion and found problems; the process exits with
+/// this code instead of printing an error.
+#[derive(Debug)]
+pub struct CheckFailed(pub i32);
+
+impl fmt::Display for CheckFailed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Check failed with exit code {}", self.0)
+    }
+}
+
+impl Error for CheckFailed {}
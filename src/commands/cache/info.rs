@@ -0,0 +1,99 @@
+use colored::*;
+
+use crate::utils::cache::CacheManager;
+
+#[derive(clap::Args, Debug)]
+pub struct InfoArgs {
+    /// Cache names to look up (default: every cache found in any layer)
+    #[arg(value_name = "CACHE")]
+    pub caches: Vec<String>,
+}
+
+impl super::Runnable for InfoArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let cache_manager = CacheManager::new()?;
+
+        println!("{}", "✓ Cache layers (searched in order):".green());
+        for (i, layer) in cache_manager.layers().iter().enumerate() {
+            let mode = if layer.is_writable() {
+                "read-write"
+            } else {
+                "read-only"
+            };
+            let status = if layer.dir.is_dir() {
+                mode.to_string()
+            } else {
+                format!("{}, not present", mode)
+            };
+            println!(
+                "  {}. {:<7} {} ({})",
+                i + 1,
+                layer.kind.to_string(),
+                layer.dir.display(),
+                status
+            );
+        }
+
+        let names = if self.caches.is_empty() {
+            cache_manager.list_caches()?
+        } else {
+            self.caches.clone()
+        };
+
+        println!("\n{}", "✓ Cache lookups:".green());
+        if names.is_empty() {
+            println!("  No caches found in any layer.");
+            return Ok(());
+        }
+
+        for name in &names {
+            show_lookup(&cache_manager, name);
+        }
+
+        Ok(())
+    }
+}
+
+fn show_lookup(cache_manager: &CacheManager, name: &str) {
+    let Some((layer, cache_file)) = cache_manager.resolve_cache_file(name) else {
+        println!(
+            "  {} {:<28} {}",
+            "!".red(),
+            name,
+            "(not cached in any layer)".yellow()
+        );
+        return;
+    };
+
+    let size = cache_manager.get_cache_size(name).unwrap_or(0);
+    let entries = match cache_manager.load_cache::<serde_json::Value>(name) {
+        Ok(cache) => format!("{} entries", cache.len()),
+        Err(_) => "unreadable".to_string(),
+    };
+
+    println!(
+        "  {} {:<28} {:<7} {} ({} bytes, {})",
+        ">".green(),
+        name,
+        layer.kind.to_string(),
+        cache_file.display(),
+        size,
+        entries
+    );
+
+    // Entries missing from the layer that satisfied the lookup are read from lower layers
+    for fallback in cache_manager
+        .layers()
+        .iter()
+        .skip_while(|l| l.dir != layer.dir)
+        .skip(1)
+        .filter(|l| l.cache_file(name).is_file())
+    {
+        println!(
+            "      {} {} {}",
+            "falls back to".dimmed(),
+            fallback.kind,
+            fallback.cache_file(name).display()
+        );
+    }
+}
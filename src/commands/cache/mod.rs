@@ -0,0 +1,19 @@
+use clap::Subcommand;
+
+use crate::commands::base::Runnable;
+
+mod info;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show the cache layers and which layer satisfies each cache lookup
+    Info(info::InfoArgs),
+}
+
+impl Command {
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match self {
+            Command::Info(args) => args.run(),
+        }
+    }
+}
@@ -8,8 +8,13 @@ use crate::utils::file;
 use crate::utils::progress;
 use crate::utils::remote::Fetcher;
 
+use super::detect::{detect_stack, print_detections};
+use super::merge::{MergeOptions, Section, merge_section_bodies, merge_sections};
+use super::sections::{find_blocks, same_template, upsert_blocks, without_blocks};
+use super::target::Target;
+use super::workspace::{discover_members, plan, print_members, print_plan};
 use super::{
-    GITHUB_RAW_BASE, OUTPUT, OUTPUT_BASE_PATH, ensure_gitignore_cache, find_template_in_cache,
+    OUTPUT, OUTPUT_BASE_PATH, ensure_gitignore_cache, find_template_in_cache, template_url,
 };
 
 #[derive(clap::Args, Debug)]
@@ -52,6 +57,34 @@ pub struct AddArgs {
         requires = "templates"
     )]
     pub output: Vec<String>,
+
+    /// Strip comments and blank lines from the merged templates
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Add the templates suggested by the project's marker files (see `gitignore detect`)
+    #[arg(long, conflicts_with = "all")]
+    pub auto: bool,
+
+    /// Where to write: the repository's .gitignore, .git/info/exclude or the global excludes file
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Target::Repo,
+        conflicts_with_all = ["all", "use_remote_name", "output"]
+    )]
+    pub target: Target,
+
+    /// Write a .gitignore per workspace member and shared rules at the root
+    #[arg(
+        long,
+        conflicts_with_all = ["all", "auto", "target", "use_remote_name", "output"]
+    )]
+    pub workspace: bool,
+
+    /// Print the workspace placement plan without writing anything
+    #[arg(long = "dry-run", requires = "workspace")]
+    pub dry_run: bool,
 }
 
 impl super::Runnable for AddArgs {
@@ -65,41 +98,192 @@ impl super::Runnable for AddArgs {
             None => Some(file::find_repo_root()?),
         };
 
-        if self.all {
-            download_all_templates(dir.as_ref(), self.force, &cache, self.use_remote_name)?;
-        } else if self.templates.is_empty() {
+        if self.workspace {
+            let root = dir.expect("directory is always resolved");
+            return self.add_to_workspace(&root, &cache);
+        }
+
+        let mut templates = self.templates.clone();
+        if self.auto {
+            let root = dir.as_ref().expect("directory is always resolved");
+            let detections = detect_stack(root)?;
+            if detections.is_empty() && templates.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No known project markers found in {}. Pass template names instead.",
+                    root.display()
+                ));
+            }
+            print_detections(&detections, root, &cache);
+
+            for detection in detections {
+                let already_requested = templates
+                    .iter()
+                    .any(|t| same_template(t, &detection.template));
+                if !already_requested && find_template_in_cache(&detection.template, &cache).is_ok()
+                {
+                    templates.push(detection.template);
+                }
+            }
+        }
+
+        if !self.all && templates.is_empty() {
             return Err(anyhow::anyhow!(
                 "No gitignore template specified. Use `--all` or pass template names."
             ));
+        }
+
+        // Exclude files are shared with other content, so always merge into them
+        let target_file = self
+            .target
+            .resolve(dir.as_ref().expect("directory is always resolved"))?;
+        let (dest_dir, output) = match &target_file {
+            Some(path) => (
+                path.parent().map(Path::to_path_buf),
+                vec![
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                ],
+            ),
+            None => (dir.clone(), self.output.clone()),
+        };
+
+        let config = GitignoreDownloadConfig {
+            dir_path: dest_dir.as_ref(),
+            force: self.force,
+            append: self.append || target_file.is_some(),
+            use_remote_name: self.use_remote_name,
+            merge_options: MergeOptions {
+                compact: self.compact,
+            },
+        };
+
+        if self.all {
+            download_all_templates(&config, &cache)?;
         } else {
-            download_templates(
-                &self.templates,
-                dir.as_ref(),
-                &self.output,
-                self.force,
-                &cache,
-                self.append,
-                self.use_remote_name,
-            )?;
+            download_templates(&templates, &output, &config, &cache)?;
         }
 
         Ok(())
     }
 }
 
-fn download_all_templates(
-    dir_path: Option<&PathBuf>,
-    force: bool,
-    cache: &Cache<String>,
-    use_remote_name: bool,
-) -> Result<()> {
+impl AddArgs {
+    /// Places templates across the workspace members of `root` and writes each file.
+    fn add_to_workspace(&self, root: &Path, cache: &Cache<String>) -> Result<()> {
+        let members = discover_members(root)?;
+        if members.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No workspace members found in {}. Members are read from Cargo.toml [workspace], package.json workspaces, pnpm-workspace.yaml and pyproject.toml [tool.uv.workspace].",
+                root.display()
+            ));
+        }
+        print_members(&members);
+
+        let placements = plan(root, &members, &self.templates)?;
+        print_plan(&placements, cache);
+
+        if self.dry_run {
+            println!("\nRun without --dry-run to write these files.");
+            return Ok(());
+        }
+
+        for placement in placements.iter().filter(|p| !p.templates.is_empty()) {
+            let dir = root.join(&placement.dir);
+            let config = GitignoreDownloadConfig {
+                dir_path: Some(&dir),
+                force: self.force,
+                append: self.append,
+                use_remote_name: false,
+                merge_options: MergeOptions {
+                    compact: self.compact,
+                },
+            };
+            let templates: Vec<String> = placement
+                .templates
+                .iter()
+                .map(|p| p.template.clone())
+                .collect();
+            download_templates(&templates, &[".gitignore".to_string()], &config, cache)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Helper functions
+
+pub struct GitignoreDownloadConfig<'a> {
+    pub dir_path: Option<&'a PathBuf>,
+    pub force: bool,
+    pub append: bool,
+    pub use_remote_name: bool,
+    pub merge_options: MergeOptions,
+}
+
+/// Runs templates through the merge engine and prints what it changed.
+fn merge_templates(sections: &[Section], options: MergeOptions) -> String {
+    let (merged, report) = merge_sections(sections, options);
+    report.print();
+    merged
+}
+
+/// Writes templates as managed blocks into an existing file, replacing any
+/// blocks already present for them in place and appending the rest.
+pub fn write_blocks(sections: &[Section], dest_path: &Path, options: MergeOptions) -> Result<()> {
+    if !dest_path.exists() {
+        return file::save_file(&merge_templates(sections, options), dest_path, false);
+    }
+
+    let existing = std::fs::read_to_string(dest_path)?;
+    let replaced: Vec<_> = find_blocks(&existing)
+        .into_iter()
+        .filter(|block| sections.iter().any(|s| same_template(&s.name, &block.name)))
+        .collect();
+
+    // Deduplicate against everything that stays in the file
+    let (bodies, report) =
+        merge_section_bodies(&without_blocks(&existing, &replaced), sections, options);
+    report.print();
+
+    let blocks: Vec<(String, String)> = sections
+        .iter()
+        .map(|s| s.name.clone())
+        .zip(bodies)
+        .collect();
+    std::fs::write(dest_path, upsert_blocks(&existing, &blocks))?;
+
+    for section in sections {
+        let verb = if replaced
+            .iter()
+            .any(|b| same_template(&b.name, &section.name))
+        {
+            "Replaced"
+        } else {
+            "Added"
+        };
+        println!(
+            "{} {} {} in {}",
+            "✓".green(),
+            verb,
+            section.name,
+            dest_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn download_all_templates(config: &GitignoreDownloadConfig, cache: &Cache<String>) -> Result<()> {
     println!("Fetching all gitignore templates...");
     let fetcher = Fetcher::new();
+    let dir_path = config.dir_path;
+    let force = config.force;
 
-    if use_remote_name {
+    if config.use_remote_name {
         // Save each template as its remote filename (e.g., Python.gitignore)
         for (_key, rel_path_entry) in cache.entries.iter() {
-            let url = format!("{}/{}", GITHUB_RAW_BASE, rel_path_entry.data);
+            let url = template_url(&rel_path_entry.data)?;
 
             // Extract remote filename from rel_path_entry.data
             let remote_filename = Path::new(&rel_path_entry.data)
@@ -125,7 +309,10 @@ fn download_all_templates(
             pb.set_message("Download Complete");
             pb.finish_and_clear();
 
-            let section = format!("# ===== {} =====\n{}\n\n", remote_filename, content);
+            let section = merge_templates(
+                &[Section::new(remote_filename, content)],
+                config.merge_options,
+            );
             file::save_file(&section, &dest_path, force)?;
         }
     } else {
@@ -138,8 +325,12 @@ fn download_all_templates(
             std::fs::remove_file(&dest_path)?;
         }
 
-        for (key, rel_path_entry) in cache.entries.iter() {
-            let url = format!("{}/{}", GITHUB_RAW_BASE, rel_path_entry.data);
+        let mut keys: Vec<&String> = cache.entries.keys().collect();
+        keys.sort();
+
+        let mut sections = Vec::new();
+        for key in keys {
+            let url = template_url(&cache.entries[key].data)?;
 
             let msg = format!("Downloading gitignore template: {}", key);
             let pb = progress::spinner(&msg);
@@ -147,10 +338,11 @@ fn download_all_templates(
             pb.set_message("Download Complete");
             pb.finish_and_clear();
 
-            let section = format!("# ===== {}.gitignore =====\n{}\n\n", key, content);
-            file::append_file(&section, &dest_path, None)?;
+            sections.push(Section::new(format!("{}.gitignore", key), content));
         }
 
+        write_blocks(&sections, &dest_path, config.merge_options)?;
+
         println!(
             "{} Downloaded and merged all gitignore templates to {}",
             "✓".green(),
@@ -163,20 +355,20 @@ fn download_all_templates(
 
 fn download_templates(
     templates: &[String],
-    dir_path: Option<&PathBuf>,
     output: &[String],
-    force: bool,
+    config: &GitignoreDownloadConfig,
     cache: &Cache<String>,
-    append: bool,
-    use_remote_name: bool,
 ) -> Result<()> {
     let fetcher = Fetcher::new(); // Create once, reuse
+    let dir_path = config.dir_path;
+    let force = config.force;
+    let append = config.append;
 
-    if use_remote_name {
+    if config.use_remote_name {
         // Each template is saved using its remote filename (e.g., Python.gitignore)
         for template_name in templates {
             let template_path = find_template_in_cache(template_name, cache)?;
-            let url = format!("{}/{}", GITHUB_RAW_BASE, template_path);
+            let url = template_url(template_path)?;
 
             // Extract filename from template_path (e.g., "Python.gitignore")
             let remote_filename = Path::new(&template_path)
@@ -190,7 +382,7 @@ fn download_templates(
             pb.set_message("Download Complete");
             pb.finish_and_clear();
 
-            let section = format!("# ===== {} =====\n{}\n\n", remote_filename, content);
+            let section = Section::new(remote_filename, content);
 
             let dest_path = dir_path
                 .map(|p| p.join(remote_filename))
@@ -201,18 +393,21 @@ fn download_templates(
                 });
 
             if append {
-                file::append_file(&section, &dest_path, None)?;
+                write_blocks(&[section], &dest_path, config.merge_options)?;
             } else {
-                file::save_file(&section, &dest_path, force)?;
+                let merged = merge_templates(&[section], config.merge_options);
+                file::save_file(&merged, &dest_path, force)?;
             }
         }
     } else if output.len() == templates.len() {
         // Save each template to its own file as specified in output
         for (template_name, output_file) in templates.iter().zip(output.iter()) {
             // check if the template_name has  a .gitignore ext rm it to normalize it
-            let template_name = template_name.strip_suffix(".gitignore").unwrap_or(template_name);
+            let template_name = template_name
+                .strip_suffix(".gitignore")
+                .unwrap_or(template_name);
             let template_path = find_template_in_cache(template_name, cache)?;
-            let url = format!("{}/{}", GITHUB_RAW_BASE, template_path);
+            let url = template_url(template_path)?;
 
             let msg = format!("Downloading gitignore template: {}", template_name);
             let pb = progress::spinner(&msg);
@@ -220,16 +415,17 @@ fn download_templates(
             pb.set_message("Download Complete");
             pb.finish_and_clear();
 
-            let section = format!("# ===== {}.gitignore =====\n{}\n\n", template_name, content);
+            let section = Section::new(format!("{}.gitignore", template_name), content);
 
             let dest_path = dir_path
                 .map(|p| p.join(output_file))
                 .unwrap_or_else(|| Path::new(OUTPUT_BASE_PATH).join(OUTPUT).join(output_file));
 
             if append {
-                file::append_file(&section, &dest_path, None)?;
+                write_blocks(&[section], &dest_path, config.merge_options)?;
             } else {
-                file::save_file(&section, &dest_path, force)?;
+                let merged = merge_templates(&[section], config.merge_options);
+                file::save_file(&merged, &dest_path, force)?;
             }
 
             println!(
@@ -241,13 +437,16 @@ fn download_templates(
         }
     } else if output.len() == 1 {
         // Merge all templates into one file, but skip invalid templates and collect errors
-        let mut merged_content = String::new();
+        let mut sections = Vec::new();
         let mut errors = Vec::new();
 
         for template_name in templates {
+            let template_name = template_name
+                .strip_suffix(".gitignore")
+                .unwrap_or(template_name);
             match find_template_in_cache(template_name, cache) {
                 Ok(template_path) => {
-                    let url = format!("{}/{}", GITHUB_RAW_BASE, template_path);
+                    let url = template_url(template_path)?;
 
                     let msg = format!("Downloading gitignore template: {}", template_name);
                     let pb = progress::spinner(&msg);
@@ -255,9 +454,9 @@ fn download_templates(
                         Ok(content) => {
                             pb.set_message("Download Complete");
                             pb.finish_and_clear();
-                            merged_content.push_str(&format!(
-                                "# ===== {}.gitignore =====\n{}\n\n",
-                                template_name, content
+                            sections.push(Section::new(
+                                format!("{}.gitignore", template_name),
+                                content,
                             ));
                         }
                         Err(e) => {
@@ -279,10 +478,11 @@ fn download_templates(
             .map(|p| p.join(&output[0]))
             .unwrap_or_else(|| Path::new(OUTPUT_BASE_PATH).join(OUTPUT).join(&output[0]));
 
-        if !merged_content.is_empty() {
+        if !sections.is_empty() {
             if append {
-                file::append_file(&merged_content, &dest_path, None)?;
+                write_blocks(&sections, &dest_path, config.merge_options)?;
             } else {
+                let merged_content = merge_templates(&sections, config.merge_options);
                 file::save_file(&merged_content, &dest_path, force)?;
             }
         }
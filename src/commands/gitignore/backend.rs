@@ -0,0 +1,199 @@
+use std::env;
+
+use crate::utils::cache::Cache;
+use crate::utils::remote::Fetcher;
+
+use super::{GITHUB_API_BASE, GITHUB_RAW_BASE, GITIGNORE_CACHE_NAME};
+
+/// Selects the gitignore backend: `github` (default) or `toptal`.
+const BACKEND_ENV: &str = "GITFORGE_GITIGNORE_BACKEND";
+
+/// Overrides the base URL of the `toptal` backend, e.g. with a self-hosted mirror.
+const BACKEND_URL_ENV: &str = "GITFORGE_GITIGNORE_URL";
+
+const TOPTAL_BASE: &str = "https://www.toptal.com/developers/gitignore";
+
+/// Names that differ between github/gitignore and gitignore.io, as (github, gitignore.io).
+///
+/// Editor and OS templates live under `Global/` on GitHub and at the top level
+/// on gitignore.io. Several gitignore.io names can map to one GitHub template.
+pub const ALIASES: &[(&str, &str)] = &[
+    ("global/macos", "macos"),
+    ("global/macos", "osx"),
+    ("global/windows", "windows"),
+    ("global/linux", "linux"),
+    ("global/visualstudiocode", "visualstudiocode"),
+    ("global/vim", "vim"),
+    ("global/emacs", "emacs"),
+    ("global/xcode", "xcode"),
+    ("global/sublimetext", "sublimetext"),
+    ("global/eclipse", "eclipse"),
+    ("global/netbeans", "netbeans"),
+    ("global/jetbrains", "jetbrains"),
+    ("global/jetbrains", "jetbrains+all"),
+    ("global/jetbrains", "jetbrains+iml"),
+    ("global/jetbrains", "intellij"),
+    ("global/jetbrains", "pycharm"),
+    ("global/jetbrains", "webstorm"),
+    ("global/jetbrains", "clion"),
+    ("global/jetbrains", "goland"),
+    ("global/jetbrains", "rider"),
+    ("global/virtualenv", "virtualenv"),
+    ("global/virtualenv", "venv"),
+    ("global/vagrant", "vagrant"),
+    ("global/dropbox", "dropbox"),
+    ("global/backup", "backup"),
+    ("global/diff", "diff"),
+    ("global/patch", "patch"),
+    ("global/tags", "tags"),
+    ("global/microsoftoffice", "microsoftoffice"),
+    ("global/libreoffice", "libreoffice"),
+    ("dotnet", "dotnetcore"),
+    ("android", "androidstudio"),
+];
+
+/// Where gitignore templates are listed and downloaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backend {
+    /// github/gitignore, listed through the GitHub API
+    Github,
+    /// A gitignore.io compatible server: toptal's, or a mirror at another base URL
+    Toptal { base_url: String },
+}
+
+impl Backend {
+    /// Reads the backend from `GITFORGE_GITIGNORE_BACKEND` and `GITFORGE_GITIGNORE_URL`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let kind = env::var(BACKEND_ENV).unwrap_or_default();
+        match kind.trim().to_lowercase().as_str() {
+            "" | "github" => Ok(Backend::Github),
+            "toptal" | "gitignore.io" => {
+                let base_url = env::var(BACKEND_URL_ENV)
+                    .ok()
+                    .filter(|url| !url.trim().is_empty())
+                    .unwrap_or_else(|| TOPTAL_BASE.to_string());
+                Ok(Backend::Toptal {
+                    base_url: base_url.trim().trim_end_matches('/').to_string(),
+                })
+            }
+            other => Err(anyhow::anyhow!(
+                "Unknown gitignore backend '{}' in {}. Use `github` or `toptal`.",
+                other,
+                BACKEND_ENV
+            )),
+        }
+    }
+
+    /// Each backend keeps its own catalogue cache, since the names differ.
+    pub fn cache_name(&self) -> &'static str {
+        match self {
+            Backend::Github => GITIGNORE_CACHE_NAME,
+            Backend::Toptal { .. } => "gitignore_templates_toptal",
+        }
+    }
+
+    /// The URL of a template, given its path as stored in the cache.
+    pub fn template_url(&self, template_path: &str) -> String {
+        match self {
+            Backend::Github => format!("{}/{}", GITHUB_RAW_BASE, template_path),
+            Backend::Toptal { base_url } => format!("{}/api/{}", base_url, template_path),
+        }
+    }
+
+    /// Downloads the list of templates, keyed by lowercase name.
+    pub fn fetch_catalogue(&self, fetcher: &Fetcher) -> anyhow::Result<Cache<String>> {
+        match self {
+            Backend::Github => fetch_github_catalogue(fetcher),
+            Backend::Toptal { base_url } => fetch_toptal_catalogue(fetcher, base_url),
+        }
+    }
+}
+
+fn fetch_github_catalogue(fetcher: &Fetcher) -> anyhow::Result<Cache<String>> {
+    let folders = vec![
+        ("", ""), // root
+        ("Global", "Global/"),
+        ("community", "community/"),
+    ];
+
+    let mut cache = Cache::new();
+
+    for (folder, prefix) in folders {
+        let url = if folder.is_empty() {
+            format!("{}/contents", GITHUB_API_BASE)
+        } else {
+            format!("{}/contents/{}", GITHUB_API_BASE, folder)
+        };
+
+        let entries = fetcher.fetch_json(&url)?;
+        if let Some(array) = entries.as_array() {
+            for entry in array {
+                if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                    if let Some(template_name) = name.strip_suffix(".gitignore") {
+                        // Create the full path for fetching
+                        let full_path = if prefix.is_empty() {
+                            name.to_string()
+                        } else {
+                            format!("{}{}", prefix, name)
+                        };
+
+                        // Store with a single, consistent key format
+                        let cache_key = if folder.is_empty() {
+                            template_name.to_lowercase()
+                        } else {
+                            format!("{}-{}", folder.to_lowercase(), template_name.to_lowercase())
+                        };
+
+                        cache.insert(cache_key, full_path);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(cache)
+}
+
+/// `GET <base>/api/list` answers with the template names, comma separated over several lines.
+fn fetch_toptal_catalogue(fetcher: &Fetcher, base_url: &str) -> anyhow::Result<Cache<String>> {
+    let list = fetcher.fetch_content(&format!("{}/api/list", base_url))?;
+
+    let mut cache = Cache::new();
+    for name in list.split([',', '\n']).map(str::trim) {
+        if !name.is_empty() {
+            // The name is the path too: templates are fetched from `<base>/api/<name>`
+            cache.insert(name.to_lowercase(), name.to_string());
+        }
+    }
+
+    if cache.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{}/api/list returned no templates. Is it a gitignore.io compatible server?",
+            base_url
+        ));
+    }
+
+    Ok(cache)
+}
+
+/// The names a template goes by in the other catalogue, per [`ALIASES`].
+pub fn aliases(name: &str) -> Vec<&'static str> {
+    let name = name.to_lowercase();
+    let name = match name.strip_prefix("global-") {
+        Some(rest) => format!("global/{}", rest),
+        None => name,
+    };
+    let bare = name.strip_prefix("global/").unwrap_or(&name);
+
+    let mut found = Vec::new();
+    for (github, toptal) in ALIASES {
+        let github_bare = github.strip_prefix("global/").unwrap_or(github);
+        if (name == *github || bare == github_bare) && !found.contains(toptal) {
+            found.push(*toptal);
+        }
+        if name == *toptal && !found.contains(github) {
+            found.push(*github);
+        }
+    }
+    found
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use colored::*;
+
+use crate::utils::cache::CacheManager;
+use crate::utils::file;
+use crate::utils::remote::Fetcher;
+
+use super::matcher::{Matcher, Pattern, tracked_files};
+use super::{ensure_gitignore_cache, fetch_template};
+
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    /// Templates to check as if they were appended to the root .gitignore (e.g., rust, python)
+    #[arg(value_name = "TEMPLATE")]
+    pub templates: Vec<String>,
+
+    /// A gitignore file to check as if it were appended to the root .gitignore
+    #[arg(long, value_name = "FILE")]
+    pub candidate: Option<PathBuf>,
+
+    /// Directory to check (default: repository root)
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Update the gitignore cache
+    #[arg(long = "update-cache")]
+    pub update_cache: bool,
+}
+
+impl super::Runnable for CheckArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let root = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => file::find_repo_root()?,
+        };
+
+        let current = Matcher::load(&root)?;
+        let candidates = self.candidate_sections()?;
+        let tracked = tracked_files(&root);
+        let tracked_set: HashSet<&str> = tracked.iter().map(String::as_str).collect();
+
+        if candidates.is_empty() {
+            let ignored = current.ignored_paths()?;
+            let ignored: Vec<_> = ignored
+                .into_iter()
+                .filter(|(path, _)| !tracked_set.contains(path.as_str()))
+                .collect();
+            let matching = tracked_matches(&current, &tracked);
+
+            print_paths("Files ignored by the current rules", &ignored);
+            print_tracked("Tracked files matching ignore patterns", &matching);
+            return Ok(());
+        }
+
+        let mut proposed = current.clone();
+        for (name, content) in &candidates {
+            proposed.add_rules("", name, content);
+        }
+
+        // Only report what the candidate changes
+        let already: HashSet<String> = current
+            .ignored_paths()?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        let ignored: Vec<_> = proposed
+            .ignored_paths()?
+            .into_iter()
+            .filter(|(path, _)| !already.contains(path) && !tracked_set.contains(path.as_str()))
+            .collect();
+
+        let already: HashSet<&str> = tracked_matches(&current, &tracked)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        let matching: Vec<_> = tracked_matches(&proposed, &tracked)
+            .into_iter()
+            .filter(|(path, _)| !already.contains(path))
+            .collect();
+
+        print_paths("Files that would become ignored", &ignored);
+        print_tracked("Tracked files that would match ignore patterns", &matching);
+
+        Ok(())
+    }
+}
+
+impl CheckArgs {
+    /// Reads the candidate file and downloads the candidate templates, as (name, content) pairs.
+    fn candidate_sections(&self) -> anyhow::Result<Vec<(String, String)>> {
+        let mut sections = Vec::new();
+
+        if let Some(path) = &self.candidate {
+            let content = fs::read_to_string(path).map_err(|e| {
+                anyhow::anyhow!("Failed to read candidate '{}': {}", path.display(), e)
+            })?;
+            sections.push((path.display().to_string(), content));
+        }
+
+        if self.templates.is_empty() {
+            return Ok(sections);
+        }
+
+        let mut cache_manager = CacheManager::new()?;
+        let cache = ensure_gitignore_cache(&mut cache_manager, self.update_cache)?;
+        let fetcher = Fetcher::new();
+
+        for template in &self.templates {
+            sections.push(fetch_template(template, &cache, &fetcher)?);
+        }
+
+        Ok(sections)
+    }
+}
+
+/// Pairs every tracked file that the matcher ignores with the deciding pattern.
+fn tracked_matches<'a, 'm>(
+    matcher: &'m Matcher,
+    tracked: &'a [String],
+) -> Vec<(&'a str, &'m Pattern)> {
+    tracked
+        .iter()
+        .filter_map(|path| {
+            matcher
+                .ignored_by(path, false)
+                .map(|pattern| (path.as_str(), pattern))
+        })
+        .collect()
+}
+
+fn print_paths(title: &str, paths: &[(String, &Pattern)]) {
+    if paths.is_empty() {
+        println!("{} {}: none", "✓".green(), title);
+        return;
+    }
+
+    println!("{} {} ({}):", "✓".green(), title, paths.len());
+    for (path, pattern) in paths {
+        println!(
+            "  {} {:<40} {} {}",
+            ">".green(),
+            path,
+            pattern.location().dimmed(),
+            pattern.text
+        );
+    }
+}
+
+fn print_tracked(title: &str, paths: &[(&str, &Pattern)]) {
+    if paths.is_empty() {
+        println!("{} {}: none", "✓".green(), title);
+        return;
+    }
+
+    println!("{} {} ({}):", "⚠".yellow(), title, paths.len());
+    for (path, pattern) in paths {
+        println!(
+            "  {} {:<40} {} {}",
+            "!".red(),
+            path,
+            pattern.location().dimmed(),
+            pattern.text
+        );
+    }
+
+    println!("\nStop tracking them (the files stay on disk) with:");
+    let quoted: Vec<String> = paths.iter().map(|(path, _)| shell_quote(path)).collect();
+    println!("  git rm --cached -- {}", quoted.join(" "));
+}
+
+fn shell_quote(path: &str) -> String {
+    if path
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./+@%".contains(c))
+    {
+        path.to_string()
+    } else {
+        format!("'{}'", path.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::*;
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+
+use super::{ensure_gitignore_cache, find_template_in_cache};
+
+/// How deep below the repository root marker files are looked for.
+const MAX_DEPTH: usize = 2;
+
+/// Directories that never hold project markers worth reporting.
+pub const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    ".venv",
+    "venv",
+];
+
+/// Marker file (or directory, with a trailing `/`) to the template it suggests.
+const MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("pyproject.toml", "python"),
+    ("requirements.txt", "python"),
+    ("setup.py", "python"),
+    ("Pipfile", "python"),
+    ("package.json", "node"),
+    ("go.mod", "go"),
+    ("*.csproj", "visualstudio"),
+    ("*.sln", "visualstudio"),
+    (".idea/", "global/jetbrains"),
+    (".vscode/", "global/visualstudiocode"),
+];
+
+#[derive(clap::Args, Debug)]
+pub struct DetectArgs {
+    /// Directory to scan (default: repository root)
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Update the gitignore cache
+    #[arg(long = "update-cache")]
+    pub update_cache: bool,
+}
+
+impl super::Runnable for DetectArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let root = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => file::find_repo_root()?,
+        };
+
+        let mut cache_manager = CacheManager::new()?;
+        let cache = ensure_gitignore_cache(&mut cache_manager, self.update_cache)?;
+
+        let detections = detect_stack(&root)?;
+        if detections.is_empty() {
+            println!("No known project markers found in {}", root.display());
+            return Ok(());
+        }
+
+        print_detections(&detections, &root, &cache);
+        println!("\nRun `gh-templates gitignore add --auto` to add the suggested templates.");
+
+        Ok(())
+    }
+}
+
+/// A template suggested by a marker file found in the project.
+#[derive(Debug, Clone)]
+pub struct Detection {
+    pub template: String,
+    pub trigger: PathBuf,
+}
+
+/// Scans a project for marker files, returning one suggestion per template.
+///
+/// Each template is attributed to the shallowest marker that triggered it.
+pub fn detect_stack(root: &Path) -> anyhow::Result<Vec<Detection>> {
+    detect_stack_to_depth(root, MAX_DEPTH)
+}
+
+/// Like [`detect_stack`], but only looks `max_depth` directories below `root`.
+pub fn detect_stack_to_depth(root: &Path, max_depth: usize) -> anyhow::Result<Vec<Detection>> {
+    let mut detections: Vec<Detection> = Vec::new();
+    // Breadth-first so that root markers win over nested ones
+    let mut dirs = VecDeque::from([(root.to_path_buf(), 0)]);
+
+    while let Some((dir, depth)) = dirs.pop_front() {
+        // An unreadable subdirectory hides its markers, it does not stop the detection
+        let read_dir = match fs::read_dir(&dir) {
+            Ok(read_dir) => read_dir,
+            Err(_) if depth > 0 => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries: Vec<_> = read_dir.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = path.is_dir();
+
+            for (marker, template) in MARKERS {
+                if marker_matches(marker, &name, is_dir)
+                    && !detections.iter().any(|d| d.template == *template)
+                {
+                    detections.push(Detection {
+                        template: template.to_string(),
+                        trigger: path.strip_prefix(root).unwrap_or(&path).to_path_buf(),
+                    });
+                }
+            }
+
+            if is_dir && depth < max_depth && !SKIPPED_DIRS.contains(&name.as_str()) {
+                dirs.push_back((path, depth + 1));
+            }
+        }
+    }
+
+    Ok(detections)
+}
+
+fn marker_matches(marker: &str, name: &str, is_dir: bool) -> bool {
+    if let Some(dir_name) = marker.strip_suffix('/') {
+        return is_dir && name == dir_name;
+    }
+    if is_dir {
+        return false;
+    }
+    match marker.strip_prefix('*') {
+        Some(suffix) => name.ends_with(suffix),
+        None => name == marker,
+    }
+}
+
+/// Prints each suggestion with the template it resolves to and the file that triggered it.
+pub fn print_detections(detections: &[Detection], root: &Path, cache: &Cache<String>) {
+    println!("{} Detected stacks in {}:", "✓".green(), root.display());
+    for detection in detections {
+        match find_template_in_cache(&detection.template, cache) {
+            Ok(path) => println!(
+                "  {} {:<24} {:<36} (found {})",
+                ">".green(),
+                detection.template,
+                path,
+                detection.trigger.display()
+            ),
+            Err(_) => println!(
+                "  {} {:<24} {:<36} (found {})",
+                "!".red(),
+                detection.template,
+                "not in cache".yellow(),
+                detection.trigger.display()
+            ),
+        }
+    }
+}
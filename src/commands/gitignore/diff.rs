@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use colored::*;
+
+use crate::utils::cache::CacheManager;
+use crate::utils::file;
+use crate::utils::remote::Fetcher;
+
+use super::rules::{self, Line, Rule};
+use super::sections::{Block, find_blocks, same_template, without_blocks};
+use super::{ensure_gitignore_cache, fetch_template};
+
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    /// Templates to compare (default: every template section in the file)
+    #[arg(value_name = "TEMPLATE")]
+    pub templates: Vec<String>,
+
+    /// Directory containing the .gitignore file
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Gitignore file to compare (default: .gitignore)
+    #[arg(
+        long = "output",
+        short = 'o',
+        value_name = "FILENAME",
+        default_value = ".gitignore"
+    )]
+    pub output: String,
+
+    /// Update the gitignore cache
+    #[arg(long = "update-cache")]
+    pub update_cache: bool,
+}
+
+impl super::Runnable for DiffArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => file::find_repo_root()?,
+        };
+        let path = dir.join(&self.output);
+
+        if !path.exists() {
+            return Err(anyhow::anyhow!("File '{}' does not exist.", path.display()));
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let blocks = find_blocks(&content);
+
+        // Each template to compare, with its section in the file if there is one
+        let targets: Vec<(String, Option<&Block>)> = if self.templates.is_empty() {
+            blocks.iter().map(|b| (b.name.clone(), Some(b))).collect()
+        } else {
+            self.templates
+                .iter()
+                .map(|t| {
+                    let block = blocks.iter().find(|b| same_template(&b.name, t));
+                    (t.clone(), block)
+                })
+                .collect()
+        };
+
+        if targets.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No template sections found in '{}'. Pass the templates to compare against.",
+                path.display()
+            ));
+        }
+
+        let mut cache_manager = CacheManager::new()?;
+        let cache = ensure_gitignore_cache(&mut cache_manager, self.update_cache)?;
+        let fetcher = Fetcher::new();
+
+        let lines: Vec<&str> = content.lines().collect();
+        // Merging drops patterns another section already has, so look in the whole file
+        let in_file: HashSet<Rule> = patterns(&content).into_iter().map(|(r, _)| r).collect();
+        let mut upstream_rules: HashSet<Rule> = HashSet::new();
+
+        for (name, block) in &targets {
+            let (template_path, upstream) = fetch_template(name, &cache, &fetcher)?;
+            let upstream = patterns(&upstream);
+            let upstream_set: HashSet<&Rule> = upstream.iter().map(|(r, _)| r).collect();
+
+            let added: Vec<&str> = upstream
+                .iter()
+                .filter(|(rule, _)| !in_file.contains(rule))
+                .map(|(_, raw)| raw.as_str())
+                .collect();
+
+            let removed: Vec<String> = match block {
+                Some(block) => patterns(&lines[block.start..block.end].join("\n"))
+                    .into_iter()
+                    .filter(|(rule, _)| !upstream_set.contains(rule))
+                    .map(|(_, raw)| raw)
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            let location = match block {
+                Some(block) => format!("section {}", block.name),
+                None => "whole file, no section".to_string(),
+            };
+
+            if added.is_empty() && removed.is_empty() {
+                println!(
+                    "{} {} matches upstream {} ({})",
+                    "✓".green(),
+                    name,
+                    template_path,
+                    location
+                );
+            } else {
+                println!(
+                    "{} {} differs from upstream {} ({}): {} added upstream, {} removed upstream",
+                    "⚠".yellow(),
+                    name,
+                    template_path,
+                    location,
+                    added.len(),
+                    removed.len()
+                );
+                for raw in &added {
+                    println!("  {} {}", "+".green(), raw);
+                }
+                for raw in &removed {
+                    println!("  {} {}", "-".red(), raw);
+                }
+            }
+
+            upstream_rules.extend(upstream.into_iter().map(|(r, _)| r));
+        }
+
+        // Hand-written lines outside every section that no compared template provides
+        let local_only: Vec<String> = patterns(&without_blocks(&content, &blocks))
+            .into_iter()
+            .filter(|(rule, _)| !upstream_rules.contains(rule))
+            .map(|(_, raw)| raw)
+            .collect();
+
+        if local_only.is_empty() {
+            println!("{} No local-only patterns", "✓".green());
+        } else {
+            println!(
+                "{} Local-only patterns ({}):",
+                "✓".green(),
+                local_only.len()
+            );
+            for raw in &local_only {
+                println!("  {} {}", ">".green(), raw);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The distinct patterns of a gitignore body in file order, with their raw text.
+fn patterns(content: &str) -> Vec<(Rule, String)> {
+    let mut seen = HashSet::new();
+    content
+        .lines()
+        .filter_map(|raw| match rules::parse_line(raw) {
+            Line::Rule(rule) if seen.insert(rule.clone()) => Some((rule, raw.trim().to_string())),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use colored::*;
+
+use crate::utils::file;
+
+use super::matcher::{Matcher, Pattern, tracked_files};
+use super::sections::block_at;
+
+#[derive(clap::Args, Debug)]
+pub struct ExplainArgs {
+    /// Paths to explain, relative to the current directory
+    #[arg(value_name = "PATH", required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+impl super::Runnable for ExplainArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let root = file::find_repo_root()?;
+        let matcher = Matcher::load(&root)?;
+        let tracked = tracked_files(&root);
+
+        for (i, path) in self.paths.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+
+            let rel = relative_to_root(&root, path)?;
+            let is_dir = root.join(&rel).is_dir() || path.to_string_lossy().ends_with('/');
+            let shown = if is_dir {
+                format!("{}/", rel)
+            } else {
+                rel.clone()
+            };
+            let own = matcher.decide(&rel, is_dir);
+
+            match matcher.excluded_parent(&rel) {
+                Some((parent, pattern)) => {
+                    println!("{} {} is ignored", "✓".green(), shown.bold());
+                    print_pattern(&root, pattern);
+                    println!("  {:<8} parent directory {}/", "via", parent);
+                    if let Some(negation) = own.filter(|p| p.rule.negated) {
+                        println!(
+                            "  {:<8} `{}` ({}) cannot re-include it because a parent directory is excluded",
+                            "note",
+                            negation.text,
+                            negation.location()
+                        );
+                    }
+                }
+                None => match own {
+                    Some(pattern) if !pattern.rule.negated => {
+                        println!("{} {} is ignored", "✓".green(), shown.bold());
+                        print_pattern(&root, pattern);
+                    }
+                    Some(pattern) => {
+                        println!(
+                            "{} {} is not ignored, it is re-included",
+                            "✓".green(),
+                            shown.bold()
+                        );
+                        print_pattern(&root, pattern);
+                    }
+                    None => println!(
+                        "{} {} is not ignored (no pattern matches)",
+                        "✓".green(),
+                        shown.bold()
+                    ),
+                },
+            }
+
+            if tracked.contains(&rel) && matcher.ignored_by(&rel, is_dir).is_some() {
+                println!(
+                    "{} {} is tracked, so git keeps it until it is removed with `git rm --cached`",
+                    "⚠".yellow(),
+                    rel
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the pattern, where it was read from and the template section it belongs to.
+fn print_pattern(root: &Path, pattern: &Pattern) {
+    println!("  {:<8} {}", "pattern", pattern.text);
+    println!("  {:<8} {}", "source", pattern.location());
+
+    let section = fs::read_to_string(root.join(&pattern.source))
+        .ok()
+        .and_then(|content| block_at(&content, pattern.line - 1));
+    match section {
+        Some(block) => println!("  {:<8} {}", "section", block.name),
+        None => println!("  {:<8} {}", "section", "none (hand-written)".dimmed()),
+    }
+}
+
+/// Resolves a path given on the command line to a `/`-separated path relative to the root.
+fn relative_to_root(root: &Path, path: &Path) -> anyhow::Result<String> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    let rel = normalized
+        .strip_prefix(root)
+        .map_err(|_| anyhow::anyhow!("'{}' is outside the repository", path.display()))?;
+    let rel: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    if rel.is_empty() {
+        return Err(anyhow::anyhow!(
+            "'{}' is the repository root, which is never ignored",
+            path.display()
+        ));
+    }
+
+    Ok(rel.join("/"))
+}
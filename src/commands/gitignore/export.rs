@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use colored::*;
+
+use crate::utils::file;
+
+use super::rules::{self, Line, Rule};
+
+/// Files that `npm publish` always includes, whatever .npmignore says.
+const NPM_ALWAYS_INCLUDED: &[&str] = &["package.json", "README.md", "LICENSE", "CHANGELOG.md"];
+
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs {
+    /// Ignore file format to export to
+    #[arg(long = "to", value_enum, value_name = "FORMAT")]
+    pub to: ExportFormat,
+
+    /// Gitignore file to export (default: .gitignore at the repository root)
+    #[arg(long, value_name = "FILE")]
+    pub from: Option<PathBuf>,
+
+    /// Directory to save the exported file (default: repository root)
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Output file name (default: the format's usual name, e.g. .dockerignore)
+    #[arg(long, short = 'o', value_name = "FILENAME")]
+    pub output: Option<String>,
+
+    /// Force overwrite an existing file
+    #[arg(long)]
+    pub force: bool,
+
+    /// Print the exported file instead of writing it
+    #[arg(long)]
+    pub stdout: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Dockerignore,
+    Npmignore,
+    Helmignore,
+    Eslintignore,
+    Prettierignore,
+}
+
+impl ExportFormat {
+    fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Dockerignore => ".dockerignore",
+            ExportFormat::Npmignore => ".npmignore",
+            ExportFormat::Helmignore => ".helmignore",
+            ExportFormat::Eslintignore => ".eslintignore",
+            ExportFormat::Prettierignore => ".prettierignore",
+        }
+    }
+}
+
+impl super::Runnable for ExportArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let source = match &self.from {
+            Some(path) => path.clone(),
+            None => file::find_repo_root()?.join(".gitignore"),
+        };
+
+        let content = fs::read_to_string(&source)
+            .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", source.display(), e))?;
+        let (exported, warnings) = export(&content, self.to);
+
+        let output = self
+            .output
+            .clone()
+            .unwrap_or_else(|| self.to.file_name().to_string());
+        let header = format!(
+            "# Generated from {} by `gh-templates gitignore export --to {}`\n\n",
+            source.file_name().unwrap_or_default().to_string_lossy(),
+            self.to
+                .to_possible_value()
+                .expect("no skipped variants")
+                .get_name()
+        );
+
+        if self.stdout {
+            print!("{}{}", header, exported);
+        } else {
+            let root = match &self.dir {
+                Some(dir) => dir.clone(),
+                None => file::find_repo_root()?,
+            };
+            file::save_file(
+                &format!("{}{}", header, exported),
+                &root.join(&output),
+                self.force,
+            )?;
+        }
+
+        for (message, lines) in &warnings {
+            let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+            eprintln!(
+                "{} {} (line{} {})",
+                "⚠".yellow(),
+                message,
+                if lines.len() == 1 { "" } else { "s" },
+                lines.join(", ")
+            );
+        }
+
+        if self.to == ExportFormat::Eslintignore {
+            eprintln!(
+                "{} ESLint 9 flat config no longer reads .eslintignore; copy the patterns into `ignores` in eslint.config.js if you use it.",
+                "⚠".yellow()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Translates a gitignore file, returning the new content and the warnings
+/// grouped by message, each with the 1-based lines it applies to.
+pub fn export(content: &str, format: ExportFormat) -> (String, BTreeMap<String, Vec<usize>>) {
+    let mut output = String::new();
+    let mut warnings: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+    for (i, raw) in content.lines().enumerate() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        let rule = match rules::parse_line(raw) {
+            Line::Rule(rule) => rule,
+            Line::Blank => {
+                output.push('\n');
+                continue;
+            }
+            Line::Comment(_) => {
+                output.push_str(raw);
+                output.push('\n');
+                continue;
+            }
+        };
+
+        let (line, warning) = match format {
+            ExportFormat::Dockerignore => to_dockerignore(&rule),
+            ExportFormat::Helmignore => to_helmignore(&rule),
+            ExportFormat::Npmignore => (Some(raw.trim_end().to_string()), npm_warning(&rule)),
+            ExportFormat::Eslintignore | ExportFormat::Prettierignore => {
+                (Some(raw.trim_end().to_string()), None)
+            }
+        };
+
+        match line {
+            Some(line) => output.push_str(&line),
+            None => output.push_str(&format!("# untranslatable: {}", raw.trim_end())),
+        }
+        output.push('\n');
+
+        if let Some(warning) = warning {
+            warnings.entry(warning).or_default().push(i + 1);
+        }
+    }
+
+    (output, warnings)
+}
+
+/// Docker matches every pattern against the full path from the context root,
+/// with Go's `filepath.Match` syntax plus `**`.
+fn to_dockerignore(rule: &Rule) -> (Option<String>, Option<String>) {
+    let mut pattern = bracket_negation_to_caret(&inline_double_stars(&rule.pattern));
+    if !rule.anchored {
+        pattern = format!("**/{}", pattern);
+    }
+
+    let warning = rule.dir_only.then(|| {
+        "Directory-only patterns also match files of the same name in .dockerignore".to_string()
+    });
+
+    let prefix = if rule.negated { "!" } else { "" };
+    (Some(format!("{}{}", prefix, pattern)), warning)
+}
+
+/// Helm follows gitignore's anchoring and directory rules but has no `**`.
+fn to_helmignore(rule: &Rule) -> (Option<String>, Option<String>) {
+    let mut rule = rule.clone();
+    rule.pattern = bracket_negation_to_caret(&inline_double_stars(&rule.pattern));
+
+    // `dir/**` ignores everything inside, which is what `dir/` does
+    if let Some(dir) = rule.pattern.strip_suffix("/**") {
+        if !dir.contains("**") {
+            rule.pattern = dir.to_string();
+            rule.dir_only = true;
+        }
+    }
+
+    if rule.pattern.contains("**") {
+        return (
+            None,
+            Some("`**` is not supported by .helmignore, the pattern was commented out".to_string()),
+        );
+    }
+
+    (Some(rule.to_string()), None)
+}
+
+fn npm_warning(rule: &Rule) -> Option<String> {
+    (!rule.negated && NPM_ALWAYS_INCLUDED.iter().any(|f| rule.matches(f, false))).then(|| {
+        "npm always publishes package.json, README, LICENSE and CHANGELOG, the pattern has no effect on them".to_string()
+    })
+}
+
+/// Replaces `**` that is not a whole path segment with `*`, which is what git
+/// makes of it; other tools would let it cross directories.
+fn inline_double_stars(pattern: &str) -> String {
+    pattern
+        .split('/')
+        .map(|segment| {
+            if segment == "**" {
+                return segment.to_string();
+            }
+            let mut collapsed = segment.to_string();
+            while collapsed.contains("**") {
+                collapsed = collapsed.replace("**", "*");
+            }
+            collapsed
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Go's `filepath.Match` negates bracket expressions with `^` only.
+fn bracket_negation_to_caret(pattern: &str) -> String {
+    pattern.replace("[!", "[^")
+}
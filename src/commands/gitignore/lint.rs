@@ -0,0 +1,547 @@
+use std::fs;
+use std::path::PathBuf;
+
+use colored::*;
+use serde::Serialize;
+
+use crate::commands::base::CheckFailed;
+use crate::utils::file;
+
+use super::rules::{self, Line, Rule};
+
+/// Exit code when issues are found and not fixed.
+const EXIT_ISSUES: i32 = 4;
+
+#[derive(clap::Args, Debug)]
+pub struct LintArgs {
+    /// Gitignore file to lint (default: .gitignore at the repository root)
+    #[arg(value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Print the issues as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Rewrite the file with every issue fixed
+    #[arg(long)]
+    pub fix: bool,
+}
+
+impl super::Runnable for LintArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let path = match &self.file {
+            Some(path) => path.clone(),
+            None => file::find_repo_root()?.join(".gitignore"),
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path.display(), e))?;
+        let issues = lint(&content);
+
+        if self.json {
+            let report = serde_json::json!({
+                "file": path.display().to_string(),
+                "fixed": self.fix && !issues.is_empty(),
+                "issues": issues,
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_issues(&path.display().to_string(), &issues, self.fix);
+        }
+
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        if self.fix {
+            fs::write(&path, apply_fixes(&content, &issues))?;
+            if !self.json {
+                println!(
+                    "{} Fixed {} issue(s) in {}",
+                    "✓".green(),
+                    issues.len(),
+                    path.display()
+                );
+            }
+            return Ok(());
+        }
+
+        Err(CheckFailed(EXIT_ISSUES).into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueKind {
+    /// The line can never match anything
+    Meaningless,
+    /// Trailing whitespace that is either dropped by git or silently part of the pattern
+    TrailingWhitespace,
+    /// The same pattern appears earlier with nothing in between that changes its effect
+    Duplicate,
+    /// An earlier, broader pattern already ignores everything this one matches
+    Shadowed,
+    /// A negation inside a directory that is excluded, which git never re-includes
+    DeadNegation,
+}
+
+impl IssueKind {
+    fn label(&self) -> &'static str {
+        match self {
+            IssueKind::Meaningless => "meaningless",
+            IssueKind::TrailingWhitespace => "trailing-whitespace",
+            IssueKind::Duplicate => "duplicate",
+            IssueKind::Shadowed => "shadowed",
+            IssueKind::DeadNegation => "dead-negation",
+        }
+    }
+}
+
+/// A problem found on one line of a gitignore file.
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    /// 1-based line number
+    pub line: usize,
+    pub kind: IssueKind,
+    /// The line as written
+    pub pattern: String,
+    pub message: String,
+}
+
+/// Checks a gitignore file's content and returns its issues in line order.
+pub fn lint(content: &str) -> Vec<Issue> {
+    let raw: Vec<&str> = content
+        .lines()
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect();
+    let mut issues = Vec::new();
+
+    // (line index, rule) for every pattern line
+    let mut parsed: Vec<(usize, Rule)> = Vec::new();
+    for (i, line) in raw.iter().enumerate() {
+        let issue = |kind, message: &str| Issue {
+            line: i + 1,
+            kind,
+            pattern: line.to_string(),
+            message: message.to_string(),
+        };
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(message) = whitespace_problem(line) {
+            issues.push(issue(IssueKind::TrailingWhitespace, message));
+        }
+
+        match rules::parse_line(line) {
+            Line::Blank => {}
+            Line::Comment(_) => issues.push(issue(
+                IssueKind::Meaningless,
+                "pattern is empty once `!` and `/` are removed, so it matches nothing",
+            )),
+            Line::Rule(rule) => match meaningless_reason(&rule) {
+                Some(message) => issues.push(issue(IssueKind::Meaningless, message)),
+                None => parsed.push((i, rule)),
+            },
+        }
+    }
+
+    for (j, (line, rule)) in parsed.iter().enumerate() {
+        let issue = |kind, message: String| Issue {
+            line: line + 1,
+            kind,
+            pattern: raw[*line].to_string(),
+            message,
+        };
+
+        if let Some(earlier) = redundant_with(&parsed, j, |a, b| a == b) {
+            issues.push(issue(
+                IssueKind::Duplicate,
+                format!("duplicates line {}", parsed[earlier].0 + 1),
+            ));
+        } else if let Some(earlier) = (!rule.negated)
+            .then(|| redundant_with(&parsed, j, |a, b| covers(a, b) || excludes_parent(a, b)))
+            .flatten()
+        {
+            issues.push(issue(
+                IssueKind::Shadowed,
+                format!(
+                    "already ignored by `{}` on line {}",
+                    raw[parsed[earlier].0],
+                    parsed[earlier].0 + 1
+                ),
+            ));
+        } else if let Some(excluding) = dead_negation(&parsed, j) {
+            issues.push(issue(
+                IssueKind::DeadNegation,
+                format!(
+                    "a parent directory is excluded by `{}` on line {}, so this can never re-include anything",
+                    raw[parsed[excluding].0],
+                    parsed[excluding].0 + 1
+                ),
+            ));
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Rewrites the content with the issues fixed.
+///
+/// Lines with a semantic issue are removed; lines that only have trailing
+/// whitespace problems are trimmed.
+pub fn apply_fixes(content: &str, issues: &[Issue]) -> String {
+    let mut output = String::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line_issues: Vec<&Issue> = issues.iter().filter(|x| x.line == i + 1).collect();
+        if line_issues
+            .iter()
+            .any(|x| x.kind != IssueKind::TrailingWhitespace)
+        {
+            continue;
+        }
+        if line_issues.is_empty() {
+            output.push_str(line);
+        } else {
+            output.push_str(&trim_trailing_whitespace(line));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn print_issues(path: &str, issues: &[Issue], fixing: bool) {
+    if issues.is_empty() {
+        println!("{} No issues found in {}", "✓".green(), path);
+        return;
+    }
+
+    println!(
+        "{} {} issue(s) found in {}:",
+        "⚠".yellow(),
+        issues.len(),
+        path
+    );
+    for issue in issues {
+        println!(
+            "  {} {:<6} {:<20} {:<30} {}",
+            "!".red(),
+            format!("{}:", issue.line),
+            issue.kind.label(),
+            issue.pattern.trim_end(),
+            issue.message.dimmed()
+        );
+    }
+    if !fixing {
+        println!("\nRun with --fix to rewrite the file.");
+    }
+}
+
+fn whitespace_problem(line: &str) -> Option<&'static str> {
+    if trim_trailing_whitespace(line) == line {
+        return None;
+    }
+    let trimmed = line.trim_end_matches(' ');
+    if trimmed.ends_with(char::is_whitespace) {
+        Some("trailing tab or other whitespace is part of the pattern, so it will not match")
+    } else {
+        Some("trailing spaces are ignored by git; escape them with `\\ ` if they are intended")
+    }
+}
+
+/// Strips trailing whitespace, keeping a space escaped with a backslash.
+fn trim_trailing_whitespace(line: &str) -> String {
+    let trimmed = line.trim_end();
+    let backslashes = trimmed.len() - trimmed.trim_end_matches('\\').len();
+    if backslashes % 2 == 1 && line[trimmed.len()..].starts_with(' ') {
+        format!("{} ", trimmed)
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn meaningless_reason(rule: &Rule) -> Option<&'static str> {
+    let pattern = &rule.pattern;
+    let backslashes = pattern.len() - pattern.trim_end_matches('\\').len();
+    if backslashes % 2 == 1 {
+        return Some("a trailing backslash is invalid, so the pattern matches nothing");
+    }
+    if pattern
+        .split('/')
+        .any(|segment| segment == "." || segment == "..")
+    {
+        return Some("git never sees `.` or `..` path segments, so the pattern matches nothing");
+    }
+    None
+}
+
+/// Finds an earlier pattern, of the same polarity, that makes pattern `j` redundant.
+///
+/// `same_effect(earlier, later)` decides whether the earlier rule already does
+/// what the later one does. Any pattern of the opposite polarity in between
+/// that may touch the same paths keeps the later one meaningful.
+fn redundant_with(
+    parsed: &[(usize, Rule)],
+    j: usize,
+    same_effect: impl Fn(&Rule, &Rule) -> bool,
+) -> Option<usize> {
+    let later = &parsed[j].1;
+    (0..j).rev().find(|&i| {
+        let earlier = &parsed[i].1;
+        earlier.negated == later.negated
+            && same_effect(earlier, later)
+            && !parsed[i + 1..j]
+                .iter()
+                .any(|(_, between)| between.negated != later.negated && may_overlap(between, later))
+    })
+}
+
+/// Finds the pattern excluding a parent directory of negation `j`, if nothing
+/// in the file can re-include that directory.
+fn dead_negation(parsed: &[(usize, Rule)], j: usize) -> Option<usize> {
+    let negation = &parsed[j].1;
+    if !negation.negated || !negation.anchored {
+        return None;
+    }
+
+    (0..j).rev().find(|&i| {
+        let excluding = &parsed[i].1;
+        !excluding.negated
+            && excludes_parent(excluding, negation)
+            && !parsed[i + 1..].iter().enumerate().any(|(k, (_, other))| {
+                i + 1 + k != j && other.negated && may_reinclude_parent(other, negation)
+            })
+    })
+}
+
+/// A token of a gitignore glob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Lit(char),
+    /// `?`
+    Any,
+    /// `*`, never crossing a `/`
+    Star,
+    /// `**` as a whole path segment
+    DoubleStar,
+    /// A bracket expression, as written
+    Class(String),
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    tokenize_with_offsets(pattern)
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect()
+}
+
+/// Splits a glob into tokens, each with the byte offset it starts at.
+fn tokenize_with_offsets(pattern: &str) -> Vec<(Token, usize)> {
+    let indexed: Vec<(usize, char)> = pattern.char_indices().collect();
+    let chars: Vec<char> = indexed.iter().map(|&(_, c)| c).collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let offset = indexed[i].0;
+        let (token, next) = match chars[i] {
+            '\\' if i + 1 < chars.len() => (Token::Lit(chars[i + 1]), i + 2),
+            '?' => (Token::Any, i + 1),
+            '*' => {
+                let mut end = i;
+                while chars.get(end) == Some(&'*') {
+                    end += 1;
+                }
+                let whole_segment = end - i == 2
+                    && (i == 0 || chars[i - 1] == '/')
+                    && matches!(chars.get(end), None | Some('/'));
+                let token = if whole_segment {
+                    Token::DoubleStar
+                } else {
+                    Token::Star
+                };
+                (token, end)
+            }
+            '[' => match class_end(&chars, i) {
+                Some(end) => (Token::Class(chars[i..end].iter().collect()), end),
+                None => (Token::Lit('['), i + 1),
+            },
+            c => (Token::Lit(c), i + 1),
+        };
+        tokens.push((token, offset));
+        i = next;
+    }
+
+    tokens
+}
+
+/// Index one past the `]` closing the bracket expression at `start`.
+fn class_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    if matches!(chars.get(i), Some('!') | Some('^')) {
+        i += 1;
+    }
+    // A `]` right after the opening bracket is literal
+    if chars.get(i) == Some(&']') {
+        i += 1;
+    }
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            ']' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Checks whether glob `a` matches every string glob `b` can match.
+///
+/// Wildcards in `b` must be absorbed by wildcards in `a` that are at least as
+/// broad, so the check may miss some coverage but never reports a false one.
+fn covers_tokens(a: &[Token], b: &[Token]) -> bool {
+    let Some(first) = a.first() else {
+        return b.is_empty();
+    };
+
+    match first {
+        Token::Star => (0..=b.len())
+            .take_while(|&k| k == 0 || !matches!(b[k - 1], Token::Lit('/') | Token::DoubleStar))
+            .any(|k| covers_tokens(&a[1..], &b[k..])),
+        Token::DoubleStar => {
+            (0..=b.len()).any(|k| covers_tokens(&a[1..], &b[k..]))
+                // `a/**/b` also matches `a/b`
+                || (a.get(1) == Some(&Token::Lit('/')) && covers_tokens(&a[2..], b))
+        }
+        Token::Any => {
+            (matches!(b.first(), Some(Token::Lit(c)) if *c != '/')
+                || matches!(b.first(), Some(Token::Any | Token::Class(_))))
+                && covers_tokens(&a[1..], &b[1..])
+        }
+        Token::Class(class) => {
+            let matched = match b.first() {
+                Some(Token::Class(other)) => other == class,
+                Some(Token::Lit(c)) => class_matches(class, *c),
+                _ => false,
+            };
+            matched && covers_tokens(&a[1..], &b[1..])
+        }
+        Token::Lit(c) => b.first() == Some(&Token::Lit(*c)) && covers_tokens(&a[1..], &b[1..]),
+    }
+}
+
+fn class_matches(class: &str, c: char) -> bool {
+    let rule = Rule {
+        negated: false,
+        anchored: true,
+        dir_only: false,
+        pattern: class.to_string(),
+    };
+    rule.matches(&c.to_string(), false)
+}
+
+/// The tokens of the last path segment.
+fn last_segment(tokens: &[Token]) -> &[Token] {
+    match tokens.iter().rposition(|t| *t == Token::Lit('/')) {
+        Some(slash) => &tokens[slash + 1..],
+        None => tokens,
+    }
+}
+
+/// Checks whether rule `a` matches every path rule `b` matches, ignoring polarity.
+fn covers(a: &Rule, b: &Rule) -> bool {
+    if a.dir_only && !b.dir_only {
+        return false;
+    }
+
+    let a_tokens = tokenize(&a.pattern);
+    let b_tokens = tokenize(&b.pattern);
+    match (a.anchored, b.anchored) {
+        (true, true) => covers_tokens(&a_tokens, &b_tokens),
+        // Unanchored rules look at the file name only
+        (false, _) => covers_tokens(&a_tokens, last_segment(&b_tokens)),
+        (true, false) => false,
+    }
+}
+
+/// The directories above everything an anchored rule matches, shallowest first.
+fn parent_dirs(rule: &Rule) -> Vec<Rule> {
+    if !rule.anchored {
+        return Vec::new();
+    }
+    tokenize_with_offsets(&rule.pattern)
+        .into_iter()
+        .filter(|(token, _)| *token == Token::Lit('/'))
+        .map(|(_, offset)| Rule {
+            negated: false,
+            anchored: true,
+            dir_only: true,
+            pattern: rule.pattern[..offset].to_string(),
+        })
+        .collect()
+}
+
+/// Checks whether rule `a` excludes a directory above everything `b` matches.
+fn excludes_parent(a: &Rule, b: &Rule) -> bool {
+    parent_dirs(b).iter().any(|dir| covers(a, dir))
+}
+
+/// Checks whether a negation may re-include one of the directories above `b`.
+fn may_reinclude_parent(negation: &Rule, b: &Rule) -> bool {
+    parent_dirs(b).iter().any(|dir| may_overlap(negation, dir))
+}
+
+/// Conservatively checks whether two rules may match a common path.
+///
+/// Two rules are only considered disjoint when the literal text at the start
+/// or the end of their last segments rules out any common file name.
+fn may_overlap(a: &Rule, b: &Rule) -> bool {
+    let a_tokens = tokenize(&a.pattern);
+    let b_tokens = tokenize(&b.pattern);
+
+    // Compare against every directory name too, a rule on a parent touches `b`
+    let b_names: Vec<&[Token]> = b_tokens.split(|t| *t == Token::Lit('/')).collect();
+    let a_name = last_segment(&a_tokens);
+
+    b_names
+        .iter()
+        .any(|b_name| names_may_overlap(a_name, b_name))
+}
+
+fn names_may_overlap(a: &[Token], b: &[Token]) -> bool {
+    let literal_prefix = |tokens: &[Token]| -> String {
+        tokens
+            .iter()
+            .map_while(|t| match t {
+                Token::Lit(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    };
+    let literal_suffix = |tokens: &[Token]| -> String {
+        let mut chars: Vec<char> = tokens
+            .iter()
+            .rev()
+            .map_while(|t| match t {
+                Token::Lit(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        chars.reverse();
+        chars.into_iter().collect()
+    };
+
+    let exact = |tokens: &[Token]| tokens.iter().all(|t| matches!(t, Token::Lit(_)));
+    if exact(a) && exact(b) {
+        return a == b;
+    }
+
+    let (a_prefix, b_prefix) = (literal_prefix(a), literal_prefix(b));
+    let (a_suffix, b_suffix) = (literal_suffix(a), literal_suffix(b));
+    (a_prefix.starts_with(&b_prefix) || b_prefix.starts_with(&a_prefix))
+        && (a_suffix.ends_with(&b_suffix) || b_suffix.ends_with(&a_suffix))
+}
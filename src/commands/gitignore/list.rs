@@ -3,6 +3,7 @@ use std::collections::HashMap;
 
 use crate::utils::cache::{Cache, CacheManager};
 
+use super::backend::ALIASES;
 use super::ensure_gitignore_cache;
 
 #[derive(clap::Args)]
@@ -22,10 +23,19 @@ pub struct ListArgs {
     /// Update the gitignore cache
     #[arg(long = "update-cache", default_value = "false")]
     pub update_cache: bool,
+
+    /// Show the names that differ between github/gitignore and gitignore.io
+    #[arg(long, conflicts_with_all = ["popular", "global", "community", "update_cache"])]
+    pub aliases: bool,
 }
 
 impl super::Runnable for ListArgs {
     fn run(&self) -> anyhow::Result<()> {
+        if self.aliases {
+            display_aliases();
+            return Ok(());
+        }
+
         let mut cache_manager = CacheManager::new()?;
 
         let cache: Cache<String> = ensure_gitignore_cache(&mut cache_manager, self.update_cache)?;
@@ -51,9 +61,10 @@ fn filter_templates(cache: &Cache<String>, args: &ListArgs) -> Vec<GitIgnoreTemp
     let mut templates = Vec::new();
 
     // Determine which categories to include
-    let show_popular = args.popular || (!args.popular && !args.global && !args.community);
-    let show_global = args.global || (!args.popular && !args.global && !args.community);
-    let show_community = args.community || (!args.popular && !args.global && !args.community);
+    let show_all = !args.popular && !args.global && !args.community;
+    let show_popular = args.popular || show_all;
+    let show_global = args.global || show_all;
+    let show_community = args.community || show_all;
 
     for (key, entry) in &cache.entries {
         let path = &entry.data;
@@ -121,3 +132,15 @@ fn display_templates(templates: Vec<GitIgnoreTemplate>) {
         }
     }
 }
+
+fn display_aliases() {
+    println!(
+        "{}",
+        "✓ Template names in github/gitignore and gitignore.io:".green()
+    );
+    println!("\n    {:<24} {}", "GITHUB".bold(), "GITIGNORE.IO".bold());
+    for (github, toptal) in ALIASES {
+        println!("  {} {:<24} {}", ">".green(), github, toptal);
+    }
+    println!("\nEither name works with every gitignore command, whichever backend is in use.");
+}
@@ -0,0 +1,277 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::rules::{self, Line, Rule};
+
+/// A gitignore pattern together with the place it was read from.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub rule: Rule,
+    /// File the pattern comes from, relative to the repository root
+    pub source: String,
+    /// 1-based line number in `source`
+    pub line: usize,
+    /// The line as written
+    pub text: String,
+}
+
+impl Pattern {
+    /// Location of the pattern as `file:line`.
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.source, self.line)
+    }
+}
+
+/// The patterns of one gitignore file, which apply below `base`.
+#[derive(Debug, Clone)]
+struct RuleSet {
+    /// Directory holding the file, relative to the root, without trailing `/`
+    base: String,
+    patterns: Vec<Pattern>,
+}
+
+impl RuleSet {
+    fn depth(&self) -> usize {
+        if self.base.is_empty() {
+            0
+        } else {
+            self.base.split('/').count()
+        }
+    }
+
+    /// Returns `path` relative to this set's directory, if it lies below it.
+    fn relative<'a>(&self, path: &'a str) -> Option<&'a str> {
+        if self.base.is_empty() {
+            return Some(path);
+        }
+        path.strip_prefix(self.base.as_str())?.strip_prefix('/')
+    }
+}
+
+/// Decides which paths of a working tree git ignores.
+///
+/// Sets are kept in increasing precedence: `.git/info/exclude`, then the root
+/// `.gitignore`, then nested `.gitignore` files from shallow to deep. Within
+/// that order the last matching pattern decides, as in git.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    root: PathBuf,
+    sets: Vec<RuleSet>,
+}
+
+impl Matcher {
+    /// Creates a matcher without any patterns.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            sets: Vec::new(),
+        }
+    }
+
+    /// Loads `.git/info/exclude` and every `.gitignore` in the working tree.
+    ///
+    /// Directories that are already ignored are not entered, since git never
+    /// reads the gitignore files inside them either.
+    pub fn load(root: &Path) -> anyhow::Result<Self> {
+        let mut matcher = Self::new(root);
+
+        let exclude = root.join(".git").join("info").join("exclude");
+        if let Ok(content) = fs::read_to_string(&exclude) {
+            matcher.add_rules("", ".git/info/exclude", &content);
+        }
+
+        let mut dirs = vec![String::new()];
+        while let Some(dir) = dirs.pop() {
+            let abs = matcher.absolute(&dir);
+            let gitignore = abs.join(".gitignore");
+            if let Ok(content) = fs::read_to_string(&gitignore) {
+                matcher.add_rules(&dir, &join(&dir, ".gitignore"), &content);
+            }
+
+            for (path, is_dir) in read_dir_sorted(&abs)? {
+                if is_dir
+                    && matcher
+                        .decide(&join(&dir, &path), true)
+                        .is_none_or(|p| p.rule.negated)
+                {
+                    dirs.push(join(&dir, &path));
+                }
+            }
+        }
+
+        Ok(matcher)
+    }
+
+    /// Adds the patterns of a gitignore file located in directory `base`.
+    ///
+    /// The file takes precedence over files in shallower or equally deep
+    /// directories that were added before it.
+    pub fn add_rules(&mut self, base: &str, source: &str, content: &str) {
+        let patterns = content
+            .lines()
+            .enumerate()
+            .filter_map(|(i, raw)| match rules::parse_line(raw) {
+                Line::Rule(rule) => Some(Pattern {
+                    rule,
+                    source: source.to_string(),
+                    line: i + 1,
+                    text: raw.strip_suffix('\r').unwrap_or(raw).to_string(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let set = RuleSet {
+            base: base.trim_matches('/').to_string(),
+            patterns,
+        };
+        let at = self
+            .sets
+            .iter()
+            .position(|s| s.depth() > set.depth())
+            .unwrap_or(self.sets.len());
+        self.sets.insert(at, set);
+    }
+
+    /// Returns the last pattern matching `path` itself, ignoring its parents.
+    ///
+    /// The result may be a negation, meaning the path is explicitly re-included.
+    pub fn decide(&self, path: &str, is_dir: bool) -> Option<&Pattern> {
+        self.sets
+            .iter()
+            .filter_map(|set| set.relative(path).map(|rel| (set, rel)))
+            .flat_map(|(set, rel)| {
+                set.patterns
+                    .iter()
+                    .filter(move |p| p.rule.matches(rel, is_dir))
+            })
+            .last()
+    }
+
+    /// Returns the pattern that makes git ignore `path`, if any.
+    ///
+    /// A path inside an ignored directory is ignored by that directory's
+    /// pattern, and no negation can re-include it.
+    pub fn ignored_by(&self, path: &str, is_dir: bool) -> Option<&Pattern> {
+        match self.excluded_parent(path) {
+            Some((_, pattern)) => Some(pattern),
+            None => self.decide(path, is_dir).filter(|p| !p.rule.negated),
+        }
+    }
+
+    /// Returns the shallowest ignored directory above `path` and its pattern.
+    pub fn excluded_parent(&self, path: &str) -> Option<(String, &Pattern)> {
+        let components: Vec<&str> = path.split('/').collect();
+        (1..components.len()).find_map(|i| {
+            let parent = components[..i].join("/");
+            self.decide(&parent, true)
+                .filter(|p| !p.rule.negated)
+                .map(|pattern| (parent, pattern))
+        })
+    }
+
+    /// Walks the working tree and returns every ignored path with its pattern.
+    ///
+    /// An ignored directory is reported once, with a trailing `/`, instead of
+    /// listing its contents.
+    pub fn ignored_paths(&self) -> anyhow::Result<Vec<(String, &Pattern)>> {
+        let mut ignored = Vec::new();
+        let mut dirs = vec![String::new()];
+
+        while let Some(dir) = dirs.pop() {
+            for (name, is_dir) in read_dir_sorted(&self.absolute(&dir))? {
+                let path = join(&dir, &name);
+                match self.decide(&path, is_dir) {
+                    Some(pattern) if !pattern.rule.negated => {
+                        let shown = if is_dir { format!("{}/", path) } else { path };
+                        ignored.push((shown, pattern));
+                    }
+                    _ if is_dir => dirs.push(path),
+                    _ => {}
+                }
+            }
+        }
+
+        ignored.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(ignored)
+    }
+
+    fn absolute(&self, path: &str) -> PathBuf {
+        if path.is_empty() {
+            self.root.clone()
+        } else {
+            self.root.join(path)
+        }
+    }
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Lists a directory's entries as (name, is_dir), skipping `.git`.
+fn read_dir_sorted(dir: &Path) -> anyhow::Result<Vec<(String, bool)>> {
+    let mut entries: Vec<(String, bool)> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| {
+            let is_dir = e.file_type().is_ok_and(|t| t.is_dir());
+            (e.file_name().to_string_lossy().to_string(), is_dir)
+        })
+        .filter(|(name, _)| name != ".git")
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// Lists the files tracked by git below `root`, relative to it.
+///
+/// Returns an empty list when `root` is not inside a git repository.
+pub fn tracked_files(root: &Path) -> Vec<String> {
+    let output = std::process::Command::new("git")
+        .args(["ls-files", "-z", "--cached"])
+        .current_dir(root)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Lists the untracked, not ignored paths below `root`, relative to it.
+///
+/// Directories holding only untracked files are listed once, with a trailing `/`.
+pub fn untracked_paths(root: &Path) -> anyhow::Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--others",
+            "--exclude-standard",
+            "--directory",
+            "--no-empty-directory",
+        ])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to list untracked files: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect())
+}
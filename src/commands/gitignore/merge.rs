@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use colored::*;
+
+use super::rules::{self, Line, Rule};
+use super::sections;
+
+/// A template's content together with the name used in its section header.
+pub struct Section {
+    pub name: String,
+    pub content: String,
+}
+
+impl Section {
+    pub fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MergeOptions {
+    /// Strip comments and blank lines, keeping only section headers and patterns
+    pub compact: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// Number of pattern lines dropped because an equivalent line was kept elsewhere
+    pub duplicates_removed: usize,
+    /// Negations that a later section ignores again, as (negation, overriding section)
+    pub overridden_negations: Vec<(String, String)>,
+}
+
+impl MergeReport {
+    /// Prints the merge summary and any negation warnings.
+    pub fn print(&self) {
+        if self.duplicates_removed > 0 {
+            println!(
+                "{} Removed {} duplicate pattern(s) while merging.",
+                "✓".green(),
+                self.duplicates_removed
+            );
+        }
+
+        for (negation, section) in &self.overridden_negations {
+            println!(
+                "{} Negation '{}' is overridden by a later pattern in {}",
+                "⚠".yellow(),
+                negation,
+                section
+            );
+        }
+    }
+}
+
+/// Merges templates into a single gitignore body with one managed block per template.
+pub fn merge_sections(sections: &[Section], options: MergeOptions) -> (String, MergeReport) {
+    let (bodies, report) = merge_section_bodies("", sections, options);
+
+    let blocks: Vec<String> = sections
+        .iter()
+        .zip(bodies)
+        .map(|(section, body)| sections::render_block(&section.name, &body))
+        .collect();
+
+    (blocks.join("\n"), report)
+}
+
+/// Deduplicates each template's patterns and returns one body per section.
+///
+/// Patterns in `existing` (lines already in the destination file) take part in
+/// deduplication but are never rewritten.
+///
+/// Negations are kept at their last occurrence, which is always safe because git
+/// lets the last matching pattern decide. Positive patterns are kept at their
+/// first occurrence, and again at their first occurrence after any kept
+/// negation, so a pattern that re-ignores what an earlier template negated
+/// still wins.
+pub fn merge_section_bodies(
+    existing: &str,
+    sections: &[Section],
+    options: MergeOptions,
+) -> (Vec<String>, MergeReport) {
+    let existing = Section::new("the existing file", existing);
+    let all: Vec<&Section> = std::iter::once(&existing).chain(sections).collect();
+
+    let parsed: Vec<Vec<(&str, Line)>> = all
+        .iter()
+        .map(|s| {
+            s.content
+                .lines()
+                .map(|raw| {
+                    (
+                        raw.strip_suffix('\r').unwrap_or(raw),
+                        rules::parse_line(raw),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    let rule_lines = || {
+        parsed.iter().enumerate().flat_map(|(s, lines)| {
+            lines
+                .iter()
+                .enumerate()
+                .filter_map(move |(l, (_, line))| match line {
+                    Line::Rule(rule) => Some((rule, (s, l))),
+                    _ => None,
+                })
+        })
+    };
+
+    // Position of the last occurrence of every negation
+    let mut negated_at: HashMap<&Rule, (usize, usize)> = HashMap::new();
+    for (rule, at) in rule_lines().filter(|(rule, _)| rule.negated) {
+        negated_at.insert(rule, at);
+    }
+
+    // Positions of the occurrences to keep
+    let mut keep: HashSet<(usize, usize)> = negated_at.values().copied().collect();
+    let mut negations: Vec<(usize, usize)> = keep.iter().copied().collect();
+    negations.sort();
+    // A negation of another pattern, like `!important.log` after `*.log`, may
+    // still be undone by the repeat, so any negation in between counts
+    let mut last_kept: HashMap<&Rule, (usize, usize)> = HashMap::new();
+    for (rule, at) in rule_lines().filter(|(rule, _)| !rule.negated) {
+        let repeat_needed = last_kept.get(rule).is_none_or(|&kept| {
+            let after = negations.partition_point(|&n| n <= kept);
+            negations.get(after).is_some_and(|&n| n < at)
+        });
+        if repeat_needed {
+            last_kept.insert(rule, at);
+            keep.insert(at);
+        }
+    }
+
+    let mut report = MergeReport::default();
+    // Section each kept negation was written in
+    let mut seen_negations: HashMap<Rule, usize> = HashMap::new();
+    let mut bodies = Vec::new();
+
+    for (s, (section, lines)) in all.iter().zip(&parsed).enumerate() {
+        let mut body: Vec<String> = Vec::new();
+
+        for (l, (raw, line)) in lines.iter().enumerate() {
+            match line {
+                // Existing lines are only tracked, a duplicate there is not ours to drop
+                Line::Rule(rule) if s == 0 && rule.negated => {
+                    seen_negations.insert(rule.clone(), s);
+                }
+                Line::Rule(_) if s == 0 => {}
+                Line::Rule(rule) => {
+                    if !keep.contains(&(s, l)) {
+                        report.duplicates_removed += 1;
+                        continue;
+                    }
+                    if rule.negated {
+                        seen_negations.insert(rule.clone(), s);
+                    } else if seen_negations
+                        .get(&rule.inverted())
+                        .is_some_and(|&negated_in| negated_in != s)
+                    {
+                        report
+                            .overridden_negations
+                            .push((rule.inverted().to_string(), section.name.clone()));
+                    }
+                    // Keep the author's spelling rather than the canonical form
+                    body.push(raw.to_string());
+                }
+                Line::Comment(text) if !options.compact => body.push(text.clone()),
+                // Dropped duplicates can leave runs of blank lines behind, collapse them
+                Line::Blank if !options.compact && body.last().is_some_and(|l| !l.is_empty()) => {
+                    body.push(String::new())
+                }
+                _ => {}
+            }
+        }
+
+        while body.last().is_some_and(|last| last.is_empty()) {
+            body.pop();
+        }
+
+        if s > 0 {
+            bodies.push(body.join("\n"));
+        }
+    }
+
+    (bodies, report)
+}
@@ -6,9 +6,26 @@ use crate::utils::remote::Fetcher;
 
 use clap::Subcommand;
 
+use backend::Backend;
+
 mod add;
+mod backend;
+mod check;
+mod detect;
+mod diff;
+mod explain;
+mod export;
+mod lint;
 mod list;
+pub(crate) mod matcher;
+mod merge;
 mod preview;
+mod remove;
+pub(crate) mod rules;
+mod sections;
+mod suggest;
+mod target;
+mod workspace;
 
 // Global constants - these can stay in the main module file
 const GITHUB_API_BASE: &str = "https://api.github.com/repos/github/gitignore";
@@ -16,21 +33,46 @@ const GITHUB_RAW_BASE: &str = "https://raw.githubusercontent.com/github/gitignor
 const OUTPUT_BASE_PATH: &str = ".";
 const OUTPUT: &str = "gitignore_templates";
 const GITIGNORE_CACHE_NAME: &str = "gitignore_templates";
+const GITIGNORE_BODIES_CACHE_NAME: &str = "gitignore_template_bodies";
 const CACHE_MAX_AGE_SECONDS: u64 = 60 * 60 * 24 * 30; // 30 days
 
 #[derive(Subcommand)]
 pub enum Command {
     Add(add::AddArgs),
+    /// Show which files the gitignore rules ignore, and tracked files they match
+    Check(check::CheckArgs),
+    /// Detect the project's stacks and suggest gitignore templates
+    Detect(detect::DetectArgs),
+    /// Compare template sections with the current upstream templates
+    Diff(diff::DiffArgs),
+    /// Show which pattern decides whether a path is ignored, and the template it came from
+    Explain(explain::ExplainArgs),
+    /// Translate a .gitignore file into another tool's ignore file format
+    Export(export::ExportArgs),
+    /// Report redundant, dead or malformed patterns in a .gitignore file
+    Lint(lint::LintArgs),
     List(list::ListArgs),
     Preview(preview::PreviewArgs),
+    /// Remove the managed sections of one or more templates from a .gitignore file
+    Remove(remove::RemoveArgs),
+    /// Suggest templates and patterns for the untracked files in the repository
+    Suggest(suggest::SuggestArgs),
 }
 
 impl Command {
     pub fn execute(&self) -> anyhow::Result<()> {
         match self {
             Command::Add(args) => args.run(),
+            Command::Check(args) => args.run(),
+            Command::Detect(args) => args.run(),
+            Command::Diff(args) => args.run(),
+            Command::Explain(args) => args.run(),
+            Command::Export(args) => args.run(),
+            Command::Lint(args) => args.run(),
             Command::List(args) => args.run(),
             Command::Preview(args) => args.run(),
+            Command::Remove(args) => args.run(),
+            Command::Suggest(args) => args.run(),
         }
     }
 }
@@ -54,8 +96,17 @@ fn find_template_in_cache<'a>(
         }
     }
 
+    // Names from the other catalogue, e.g. gitignore.io's `osx` for GitHub's `Global/macOS`
+    for alias in backend::aliases(&normalized_template) {
+        for key in [alias.to_string(), alias.replace('/', "-")] {
+            if let Some(entry) = cache.entries.get(&key) {
+                return Ok(&entry.data);
+            }
+        }
+    }
+
     // Fallback: if the template is "global/windows", try "windows"
-    if let Some(last_part) = normalized_template.split('/').last() {
+    if let Some(last_part) = normalized_template.split('/').next_back() {
         if let Some(entry) = cache.entries.get(last_part) {
             return Ok(&entry.data);
         }
@@ -90,73 +141,58 @@ fn find_template_in_cache<'a>(
     ))
 }
 
+/// Downloads a template, returning its upstream path and content.
+fn fetch_template(
+    template_name: &str,
+    cache: &Cache<String>,
+    fetcher: &Fetcher,
+) -> Result<(String, String), anyhow::Error> {
+    let template_name = template_name
+        .strip_suffix(".gitignore")
+        .unwrap_or(template_name);
+    let template_path = find_template_in_cache(template_name, cache)?;
+    let url = template_url(template_path)?;
+
+    let pb = progress::spinner(&format!("Fetching gitignore template: {}", template_name));
+    let content = fetcher.fetch_content(&url);
+    pb.finish_and_clear();
+
+    Ok((template_path.to_string(), content?))
+}
+
+/// The download URL of a template path from the cache, for the configured backend.
+fn template_url(template_path: &str) -> Result<String, anyhow::Error> {
+    Ok(Backend::from_env()?.template_url(template_path))
+}
+
 /// Ensures the gitignore cache exists and is up-to-date
 fn ensure_gitignore_cache(
     cache_manager: &mut CacheManager,
     update_cache: bool,
 ) -> Result<Cache<String>, anyhow::Error> {
+    let backend = Backend::from_env()?;
+
     // Only print if we are updating the cache
     let should_update =
-        cache_manager.should_update_cache::<String>(GITIGNORE_CACHE_NAME, CACHE_MAX_AGE_SECONDS)?;
+        cache_manager.should_update_cache::<String>(backend.cache_name(), CACHE_MAX_AGE_SECONDS)?;
 
     if !should_update && !update_cache {
-        let cache = cache_manager.load_cache(GITIGNORE_CACHE_NAME)?;
+        let cache = cache_manager.load_cache(backend.cache_name())?;
         // Only print if running in verbose/debug mode (not implemented here)
         // e.g., println!("Loaded gitignore template cache ({} templates)", cache.entries.len());
         return Ok(cache);
     }
 
     let pb = progress::spinner("Updating gitignore template cache...");
-
-    let fetcher = Fetcher::new();
-    let folders = vec![
-        ("", ""), // root
-        ("Global", "Global/"),
-        ("community", "community/"),
-    ];
-
-    let mut cache = Cache::new();
-
-    for (folder, prefix) in folders {
-        let url = if folder.is_empty() {
-            format!("{}/contents", GITHUB_API_BASE)
-        } else {
-            format!("{}/contents/{}", GITHUB_API_BASE, folder)
-        };
-
-        let entries = fetcher.fetch_json(&url)?;
-        if let Some(array) = entries.as_array() {
-            for entry in array {
-                if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
-                    if name.ends_with(".gitignore") {
-                        let template_name = &name[..name.len() - ".gitignore".len()];
-
-                        // Create the full path for fetching
-                        let full_path = if prefix.is_empty() {
-                            name.to_string()
-                        } else {
-                            format!("{}{}", prefix, name)
-                        };
-
-                        // Store with a single, consistent key format
-                        let cache_key = if folder.is_empty() {
-                            template_name.to_lowercase()
-                        } else {
-                            format!("{}-{}", folder.to_lowercase(), template_name.to_lowercase())
-                        };
-
-                        cache.insert(cache_key, full_path);
-                    }
-                }
-            }
-        }
-    }
+    let cache = backend.fetch_catalogue(&Fetcher::new());
     pb.finish_and_clear();
+    let cache = cache?;
+
     println!(
         "Gitignore template cache updated ({} templates available).",
         cache.entries.len()
     );
 
-    cache_manager.save_cache(GITIGNORE_CACHE_NAME, &cache)?;
+    cache_manager.save_cache(backend.cache_name(), &cache)?;
     Ok(cache)
 }
@@ -3,7 +3,7 @@ use crate::utils::pretty_print;
 use crate::utils::progress;
 use crate::utils::remote::Fetcher;
 
-use super::{GITHUB_RAW_BASE, ensure_gitignore_cache, find_template_in_cache};
+use super::{ensure_gitignore_cache, find_template_in_cache, template_url};
 
 #[derive(clap::Args)]
 pub struct PreviewArgs {
@@ -38,12 +38,12 @@ impl super::Runnable for PreviewArgs {
 fn preview_single_template(template: &str, cache: &super::Cache<String>) -> anyhow::Result<()> {
     // normalize template if it has the .gitignore ext
     let template = template.strip_suffix(".gitignore").unwrap_or(template);
-    
+
     // Find the template path in cache
     let template_path = find_template_in_cache(template, cache)?;
 
     let fetcher = Fetcher::new();
-    let url = format!("{}/{}", GITHUB_RAW_BASE, template_path);
+    let url = template_url(template_path)?;
 
     let pb = progress::spinner(&format!("Fetching gitignore template: {}", template));
     let content = fetcher.fetch_content(&url)?;
@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::PathBuf;
+
+use colored::*;
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+use crate::utils::progress;
+use crate::utils::remote::Fetcher;
+
+use super::merge::{MergeOptions, Section, merge_section_bodies};
+use super::rules::{self, Line};
+use super::sections::{Block, find_blocks, remove_blocks, upsert_blocks, without_blocks};
+use super::{
+    CACHE_MAX_AGE_SECONDS, GITIGNORE_BODIES_CACHE_NAME, ensure_gitignore_cache,
+    find_template_in_cache, template_url,
+};
+
+#[derive(clap::Args, Debug)]
+pub struct RemoveArgs {
+    /// Template names to remove (e.g., rust, python, global/windows)
+    #[arg(value_name = "TEMPLATE", required = true)]
+    pub templates: Vec<String>,
+
+    /// Directory containing the .gitignore file
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Gitignore file to remove the templates from (default: .gitignore)
+    #[arg(
+        long = "output",
+        short = 'o',
+        value_name = "FILENAME",
+        default_value = ".gitignore"
+    )]
+    pub output: String,
+}
+
+impl super::Runnable for RemoveArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => file::find_repo_root()?,
+        };
+        let path = dir.join(&self.output);
+
+        if !path.exists() {
+            return Err(anyhow::anyhow!("File '{}' does not exist.", path.display()));
+        }
+
+        let original = fs::read_to_string(&path)?;
+        let mut content = original.clone();
+        let mut errors = Vec::new();
+        let mut removed_patterns = false;
+
+        for template in &self.templates {
+            let template = template.strip_suffix(".gitignore").unwrap_or(template);
+            match remove_blocks(&content, template) {
+                Some(updated) => {
+                    removed_patterns |= pattern_count(&content) > pattern_count(&updated);
+                    content = updated;
+                    println!(
+                        "{} Removed gitignore template: {} from {}",
+                        "✓".green(),
+                        template,
+                        path.display()
+                    );
+                }
+                None => errors.push(format!(
+                    "Template '{}' has no managed section in {}",
+                    template,
+                    path.display()
+                )),
+            }
+        }
+
+        // Merging dropped patterns from the remaining blocks that the removed ones
+        // already had, merge their templates again to bring those back
+        if removed_patterns {
+            match remerge_blocks(&content) {
+                Ok(Some(remerged)) => {
+                    content = remerged;
+                    println!(
+                        "{} Restored patterns the remaining templates shared with the removed ones",
+                        "✓".green()
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "{} Could not merge the remaining templates again: {}\n  Patterns they shared with the removed templates may be missing. Add them again with `gh-templates gitignore add <TEMPLATE> --append`.",
+                    "⚠".yellow(),
+                    e
+                ),
+            }
+        }
+
+        if content != original {
+            fs::write(&path, &content)?;
+        }
+
+        for error in &errors {
+            eprintln!("{}", error.red());
+        }
+
+        if errors.len() == self.templates.len() {
+            return Err(anyhow::anyhow!("No templates were removed."));
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of pattern lines in the file.
+fn pattern_count(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|line| matches!(rules::parse_line(line), Line::Rule(_)))
+        .count()
+}
+
+/// Merges the templates of the remaining managed blocks again, from the cached
+/// template bodies or else a fresh download. Returns `None` when nothing changes.
+fn remerge_blocks(content: &str) -> anyhow::Result<Option<String>> {
+    let blocks: Vec<Block> = find_blocks(content)
+        .into_iter()
+        .filter(|b| !b.legacy)
+        .collect();
+    if blocks.is_empty() {
+        return Ok(None);
+    }
+
+    let mut cache_manager = CacheManager::new()?;
+    let cache = ensure_gitignore_cache(&mut cache_manager, false)?;
+    let mut bodies: Cache<String> = cache_manager.load_cache(GITIGNORE_BODIES_CACHE_NAME)?;
+    let fetcher = Fetcher::new();
+    let mut downloaded = false;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut sections = Vec::new();
+    // Blocks written with --compact hold nothing but patterns between their markers
+    let mut compact = true;
+    for block in &blocks {
+        compact &= lines[block.start + 1..block.end - 1]
+            .iter()
+            .all(|line| matches!(rules::parse_line(line), Line::Rule(_)));
+
+        let name = block.name.strip_suffix(".gitignore").unwrap_or(&block.name);
+        let path = find_template_in_cache(name, &cache)?;
+        let body = match bodies.get(path) {
+            Some(body) if !bodies.is_entry_stale(path, CACHE_MAX_AGE_SECONDS) => body.clone(),
+            _ => {
+                let pb = progress::spinner(&format!("Fetching gitignore template: {}", name));
+                let fetched = fetcher.fetch_content(&template_url(path)?);
+                pb.finish_and_clear();
+                let body = fetched?;
+                bodies.insert(path.to_string(), body.clone());
+                downloaded = true;
+                body
+            }
+        };
+        sections.push(Section::new(block.name.clone(), body));
+    }
+
+    if downloaded {
+        cache_manager.save_cache(GITIGNORE_BODIES_CACHE_NAME, &bodies)?;
+    }
+
+    let (merged, _) = merge_section_bodies(
+        &without_blocks(content, &blocks),
+        &sections,
+        MergeOptions { compact },
+    );
+    let blocks: Vec<(String, String)> = sections.into_iter().map(|s| s.name).zip(merged).collect();
+    let remerged = upsert_blocks(content, &blocks);
+
+    Ok((remerged != content).then_some(remerged))
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+/// A single line of a gitignore file, classified according to gitignore(5).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Blank,
+    Comment(String),
+    Rule(Rule),
+}
+
+/// A parsed gitignore pattern in canonical form.
+///
+/// Two rules that ignore exactly the same paths compare equal, e.g. `foo`,
+/// `**/foo` and `foo   ` all parse to the same rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// The pattern starts with `!` and re-includes matching paths
+    pub negated: bool,
+    /// The pattern contains a `/` before its last character and is relative to the .gitignore
+    pub anchored: bool,
+    /// The pattern ends with `/` and only matches directories
+    pub dir_only: bool,
+    /// The glob itself, without the `!`, leading `/` or trailing `/`
+    pub pattern: String,
+}
+
+impl Rule {
+    /// Returns the same rule with the opposite polarity.
+    pub fn inverted(&self) -> Rule {
+        Rule {
+            negated: !self.negated,
+            ..self.clone()
+        }
+    }
+
+    /// Checks whether the rule matches a path, ignoring its polarity.
+    ///
+    /// `path` is relative to the directory holding the gitignore file and uses
+    /// `/` as separator. Parent directories are not considered here: a path
+    /// inside an ignored directory only matches if the rule matches it too.
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        // Unanchored patterns match the file name at any depth
+        let text = if self.anchored {
+            path
+        } else {
+            path.rsplit('/').next().unwrap_or(path)
+        };
+
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        wildmatch(&pattern, 0, &text)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negated {
+            write!(f, "!")?;
+        }
+        // A leading slash is only needed when nothing else in the pattern anchors it
+        if self.anchored && !self.pattern.contains('/') {
+            write!(f, "/")?;
+        }
+        write!(f, "{}", self.pattern)?;
+        if self.dir_only {
+            write!(f, "/")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses one line of a gitignore file.
+pub fn parse_line(line: &str) -> Line {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+
+    if line.starts_with('#') {
+        return Line::Comment(line.to_string());
+    }
+
+    let line = trim_unescaped_trailing_spaces(line);
+    if line.is_empty() {
+        return Line::Blank;
+    }
+
+    let (negated, rest) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (dir_only, rest) = match rest.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+
+    let anchored = rest.contains('/');
+    let mut pattern = rest.trim_start_matches('/').to_string();
+
+    // Consecutive `**/` segments are equivalent to a single one
+    while pattern.contains("**/**/") {
+        pattern = pattern.replace("**/**/", "**/");
+    }
+
+    // `**/foo` matches `foo` at any depth, which is what an unanchored `foo` does already
+    let (anchored, pattern) = match pattern.strip_prefix("**/") {
+        Some(rest) if !rest.is_empty() && !rest.contains('/') => (false, rest.to_string()),
+        _ => (anchored, pattern),
+    };
+
+    if pattern.is_empty() {
+        // `/` or `!/` never match anything, treat them like comments so they are kept verbatim
+        return Line::Comment(line.to_string());
+    }
+
+    Line::Rule(Rule {
+        negated,
+        anchored,
+        dir_only,
+        pattern,
+    })
+}
+
+/// Removes trailing spaces unless they are escaped with a backslash.
+fn trim_unescaped_trailing_spaces(line: &str) -> &str {
+    let trimmed = line.trim_end_matches(' ');
+    if trimmed.len() < line.len() && trimmed.ends_with('\\') {
+        // Keep exactly one escaped space
+        let backslashes = trimmed.len() - trimmed.trim_end_matches('\\').len();
+        if backslashes % 2 == 1 {
+            return &line[..trimmed.len() + 1];
+        }
+    }
+    trimmed
+}
+
+/// Matches `text` against `pattern[pi..]`, following git's wildmatch rules.
+///
+/// `*`, `?` and bracket expressions never match `/`. A `**` that forms a whole
+/// path segment matches any number of directories, otherwise it acts like `*`.
+fn wildmatch(pattern: &[char], pi: usize, text: &[char]) -> bool {
+    let Some(&first) = pattern.get(pi) else {
+        return text.is_empty();
+    };
+
+    match first {
+        '\\' if pi + 1 < pattern.len() => {
+            text.first() == Some(&pattern[pi + 1]) && wildmatch(pattern, pi + 2, &text[1..])
+        }
+        '?' => text.first().is_some_and(|&c| c != '/') && wildmatch(pattern, pi + 1, &text[1..]),
+        '[' => match match_class(&pattern[pi..], text.first().copied()) {
+            Some((true, len)) => wildmatch(pattern, pi + len, &text[1..]),
+            Some((false, _)) => false,
+            // An unterminated bracket is a literal `[`
+            None => text.first() == Some(&'[') && wildmatch(pattern, pi + 1, &text[1..]),
+        },
+        '*' if pattern.get(pi + 1) == Some(&'*')
+            && (pi == 0 || pattern[pi - 1] == '/')
+            && matches!(pattern.get(pi + 2), None | Some('/')) =>
+        {
+            if pi + 2 == pattern.len() {
+                return true;
+            }
+            // Zero directories, or resume after each `/` in turn
+            let rest = pi + 3;
+            wildmatch(pattern, rest, text)
+                || text
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &c)| c == '/')
+                    .any(|(i, _)| wildmatch(pattern, rest, &text[i + 1..]))
+        }
+        '*' => {
+            // Adjacent stars outside a whole segment behave like one
+            let mut rest = pi + 1;
+            while pattern.get(rest) == Some(&'*') {
+                rest += 1;
+            }
+            for i in 0..=text.len() {
+                if wildmatch(pattern, rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        c => text.first() == Some(&c) && wildmatch(pattern, pi + 1, &text[1..]),
+    }
+}
+
+/// Evaluates a bracket expression at the start of `pattern` against `c`.
+///
+/// Returns whether it matched and how many pattern characters it spans, or
+/// `None` if the bracket is never closed.
+fn match_class(pattern: &[char], c: Option<char>) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negated = matches!(pattern.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let &p = pattern.get(i)?;
+        if p == ']' && !first {
+            break;
+        }
+        first = false;
+
+        let (low, next) = if p == '\\' {
+            (*pattern.get(i + 1)?, i + 2)
+        } else {
+            (p, i + 1)
+        };
+
+        if pattern.get(next) == Some(&'-') && pattern.get(next + 1).is_some_and(|&h| h != ']') {
+            let high = pattern[next + 1];
+            matched |= c.is_some_and(|c| low <= c && c <= high);
+            i = next + 2;
+        } else {
+            matched |= c == Some(low);
+            i = next;
+        }
+    }
+
+    let matched = c.is_some_and(|c| c != '/' && matched != negated);
+    Some((matched, i + 1))
+}
@@ -0,0 +1,245 @@
+/// A template's block inside a gitignore file, as a range of line indices.
+///
+/// Blocks are written between `# ===== BEGIN <name> =====` and
+/// `# ===== END <name> =====` markers. Files written by older versions only
+/// carry a `# ===== <name> =====` header, followed by the template and two
+/// blank lines. Such a legacy block ends after those two blank lines, or at
+/// its first blank line when they are missing, so hand-written lines after it
+/// are never taken for part of the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub name: String,
+    /// Index of the BEGIN (or legacy header) line
+    pub start: usize,
+    /// Index one past the END line, or past the blank lines ending a legacy block
+    pub end: usize,
+    pub legacy: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Marker {
+    Begin(String),
+    End(String),
+    Legacy(String),
+}
+
+fn parse_marker(line: &str) -> Option<Marker> {
+    let inner = line
+        .trim()
+        .strip_prefix("# =====")?
+        .strip_suffix("=====")?
+        .trim();
+
+    if inner.is_empty() {
+        return None;
+    }
+
+    if let Some(name) = inner.strip_prefix("BEGIN ") {
+        Some(Marker::Begin(name.trim().to_string()))
+    } else if let Some(name) = inner.strip_prefix("END ") {
+        Some(Marker::End(name.trim().to_string()))
+    } else {
+        Some(Marker::Legacy(inner.to_string()))
+    }
+}
+
+/// Finds every managed block in a gitignore file, in file order.
+pub fn find_blocks(content: &str) -> Vec<Block> {
+    let lines: Vec<&str> = content.lines().collect();
+    let markers: Vec<Option<Marker>> = lines.iter().map(|l| parse_marker(l)).collect();
+    let next_header = |from: usize| {
+        (from..lines.len())
+            .find(|&i| matches!(markers[i], Some(Marker::Begin(_)) | Some(Marker::Legacy(_))))
+            .unwrap_or(lines.len())
+    };
+    let blank = |i: usize| lines[i].trim().is_empty();
+    let legacy_end = |from: usize| {
+        let limit = next_header(from);
+        (from..limit.saturating_sub(1))
+            .find(|&i| blank(i) && blank(i + 1))
+            .map(|i| i + 2)
+            .or_else(|| (from..limit).find(|&i| blank(i)).map(|i| i + 1))
+            .unwrap_or(limit)
+    };
+
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let (name, legacy) = match &markers[i] {
+            Some(Marker::Begin(name)) => (name.clone(), false),
+            Some(Marker::Legacy(name)) => (name.clone(), true),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let end_marker = Some(Marker::End(name.clone()));
+        let closing = if legacy {
+            None
+        } else {
+            (i + 1..lines.len()).find(|&j| markers[j] == end_marker)
+        };
+
+        // An unterminated BEGIN block behaves like a legacy one
+        let (end, legacy) = match closing {
+            Some(j) => (j + 1, false),
+            None => (legacy_end(i + 1), true),
+        };
+
+        blocks.push(Block {
+            name,
+            start: i,
+            end,
+            legacy,
+        });
+        i = end;
+    }
+
+    blocks
+}
+
+/// Returns the block containing the given line index, if any.
+pub fn block_at(content: &str, line: usize) -> Option<Block> {
+    find_blocks(content)
+        .into_iter()
+        .find(|b| (b.start..b.end).contains(&line))
+}
+
+/// Renders a template's patterns as a managed block.
+pub fn render_block(name: &str, body: &str) -> String {
+    let mut block = format!("# ===== BEGIN {} =====\n", name);
+    for line in body.lines() {
+        block.push_str(line);
+        block.push('\n');
+    }
+    block.push_str(&format!("# ===== END {} =====\n", name));
+    block
+}
+
+/// Checks whether two block or template names refer to the same template,
+/// e.g. `global/macos`, `global-macos.gitignore` and `macOS.gitignore`.
+pub fn same_template(a: &str, b: &str) -> bool {
+    let normalize = |name: &str| {
+        let name = name.trim().to_lowercase();
+        let name = name
+            .strip_suffix(".gitignore")
+            .unwrap_or(&name)
+            .replace('/', "-");
+        // Remote file names drop the folder the template lives in
+        ["global-", "community-"]
+            .iter()
+            .find_map(|folder| name.strip_prefix(folder).map(str::to_string))
+            .unwrap_or(name)
+    };
+
+    normalize(a) == normalize(b)
+}
+
+/// Replaces the blocks of the given templates in place and appends the others.
+///
+/// `blocks` holds (name, body) pairs. Any further blocks for the same template
+/// are removed so each template appears once.
+pub fn upsert_blocks(content: &str, blocks: &[(String, String)]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let existing = find_blocks(content);
+
+    let mut written = vec![false; blocks.len()];
+    let mut output = String::new();
+    let mut i = 0;
+
+    for block in &existing {
+        for line in &lines[i..block.start] {
+            output.push_str(line);
+            output.push('\n');
+        }
+
+        match blocks
+            .iter()
+            .position(|(name, _)| same_template(name, &block.name))
+        {
+            Some(idx) if !written[idx] => {
+                output.push_str(&render_block(&blocks[idx].0, &blocks[idx].1));
+                written[idx] = true;
+                // Legacy blocks swallow the separator before the next header, keep it
+                if block.legacy && lines[block.end - 1].trim().is_empty() {
+                    output.push('\n');
+                }
+            }
+            Some(_) => {}
+            None => {
+                for line in &lines[block.start..block.end] {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+        }
+        i = block.end;
+    }
+
+    for line in &lines[i..] {
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    for (idx, (name, body)) in blocks.iter().enumerate() {
+        if written[idx] {
+            continue;
+        }
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(&render_block(name, body));
+    }
+
+    output
+}
+
+/// Removes every block belonging to `template`, leaving all other lines alone.
+///
+/// Returns `None` when the file has no block for the template.
+pub fn remove_blocks(content: &str, template: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let matching: Vec<Block> = find_blocks(content)
+        .into_iter()
+        .filter(|b| same_template(&b.name, template))
+        .collect();
+
+    if matching.is_empty() {
+        return None;
+    }
+
+    let mut output = String::new();
+    let mut i = 0;
+    for block in &matching {
+        for line in &lines[i..block.start] {
+            output.push_str(line);
+            output.push('\n');
+        }
+        i = block.end;
+        // Drop the blank separator that followed the block
+        if lines.get(i).is_some_and(|l| l.trim().is_empty()) {
+            i += 1;
+        }
+    }
+    for line in &lines[i..] {
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    while output.ends_with("\n\n") {
+        output.pop();
+    }
+
+    Some(output)
+}
+
+/// Returns the file content with the given blocks cut out.
+pub fn without_blocks(content: &str, blocks: &[Block]) -> String {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| !blocks.iter().any(|b| (b.start..b.end).contains(i)))
+        .map(|(_, line)| format!("{}\n", line))
+        .collect()
+}
@@ -0,0 +1,546 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::*;
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+use crate::utils::progress;
+use crate::utils::prompt;
+use crate::utils::remote::Fetcher;
+
+use super::add::write_blocks;
+use super::detect::detect_stack;
+use super::matcher::{Matcher, tracked_files, untracked_paths};
+use super::merge::{MergeOptions, Section};
+use super::rules::{self, Line, Rule};
+use super::sections;
+use super::{
+    CACHE_MAX_AGE_SECONDS, GITIGNORE_BODIES_CACHE_NAME, ensure_gitignore_cache,
+    find_template_in_cache, template_url,
+};
+
+/// Leftovers of common builds and tools, to a template worth checking for them.
+///
+/// Hints only decide which templates are downloaded; whether a template covers
+/// an untracked path is decided by the template's own patterns.
+const HINTS: &[(&str, &str)] = &[
+    ("target/", "rust"),
+    ("node_modules/", "node"),
+    ("dist/", "node"),
+    ("coverage/", "node"),
+    ("*.log", "node"),
+    (".env", "node"),
+    (".venv/", "python"),
+    ("venv/", "python"),
+    ("__pycache__/", "python"),
+    ("*.pyc", "python"),
+    (".pytest_cache/", "python"),
+    ("*.egg-info/", "python"),
+    ("*.class", "java"),
+    (".gradle/", "gradle"),
+    ("bin/", "visualstudio"),
+    ("obj/", "visualstudio"),
+    (".terraform/", "terraform"),
+    ("*.tfstate", "terraform"),
+    ("*.o", "c"),
+    (".DS_Store", "global/macos"),
+    ("Thumbs.db", "global/windows"),
+    (".idea/", "global/jetbrains"),
+    (".vscode/", "global/visualstudiocode"),
+    ("*.swp", "global/vim"),
+];
+
+#[derive(clap::Args, Debug)]
+pub struct SuggestArgs {
+    /// Directory to scan (default: repository root)
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Accept every suggestion without asking
+    #[arg(long, short = 'y', conflicts_with = "dry_run")]
+    pub yes: bool,
+
+    /// Print the suggestions without asking or writing anything
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// Strip comments and blank lines from the accepted templates
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Update the gitignore cache and the downloaded templates
+    #[arg(long = "update-cache")]
+    pub update_cache: bool,
+}
+
+/// Untracked paths that one pattern ignores.
+#[derive(Debug, Clone)]
+struct Cluster {
+    pattern: String,
+    /// Paths relative to the root, with whether each is a directory
+    paths: Vec<(String, bool)>,
+}
+
+impl Cluster {
+    fn examples(&self) -> String {
+        let shown: Vec<String> = self
+            .paths
+            .iter()
+            .take(3)
+            .map(|(path, is_dir)| display_path(path, *is_dir))
+            .collect();
+        match self.paths.len() {
+            n if n > 3 => format!("{}, and {} more", shown.join(", "), n - 3),
+            _ => shown.join(", "),
+        }
+    }
+}
+
+/// A downloaded template that ignores some of the clusters.
+struct Recommendation {
+    template: String,
+    template_path: String,
+    content: String,
+    clusters: Vec<Cluster>,
+}
+
+impl super::Runnable for SuggestArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let root = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => file::find_repo_root()?,
+        };
+
+        // A new .gitignore is untracked too, but never something to ignore
+        let untracked: Vec<String> = untracked_paths(&root)?
+            .into_iter()
+            .filter(|p| p.rsplit('/').next() != Some(".gitignore"))
+            .collect();
+        if untracked.is_empty() {
+            println!("{} No untracked files to ignore", "✓".green());
+            return Ok(());
+        }
+
+        let tracked = tracked_files(&root);
+        let clusters = cluster(&root, &untracked, &tracked);
+
+        let mut cache_manager = CacheManager::new()?;
+        let cache = ensure_gitignore_cache(&mut cache_manager, self.update_cache)?;
+        let templates = self.load_templates(&root, &clusters, &cache, &cache_manager)?;
+
+        let (recommendations, rest) = recommend(&root, clusters, templates);
+        let (custom, left_alone) = split_source_like(&root, rest, &tracked);
+
+        println!(
+            "{} Found {} untracked path(s) in {}",
+            "✓".green(),
+            untracked.len(),
+            root.display()
+        );
+        print_suggestions(&recommendations, &custom, &left_alone);
+
+        if recommendations.is_empty() && custom.is_empty() {
+            return Ok(());
+        }
+        if self.dry_run {
+            println!("\nRun without --dry-run to pick the suggestions to add.");
+            return Ok(());
+        }
+
+        println!();
+        let accepted_templates: Vec<&Recommendation> = recommendations
+            .iter()
+            .filter(|r| self.yes || prompt::confirm(&format!("Add template {}?", r.template)))
+            .collect();
+        let accepted_patterns: Vec<&Cluster> = custom
+            .iter()
+            .filter(|c| self.yes || prompt::confirm(&format!("Add pattern {}?", c.pattern)))
+            .collect();
+
+        if accepted_templates.is_empty() && accepted_patterns.is_empty() {
+            println!("Nothing added.");
+            return Ok(());
+        }
+
+        let dest_path = root.join(".gitignore");
+        if !accepted_templates.is_empty() {
+            let sections: Vec<Section> = accepted_templates
+                .iter()
+                .map(|r| Section::new(format!("{}.gitignore", r.template), r.content.clone()))
+                .collect();
+            write_blocks(
+                &sections,
+                &dest_path,
+                MergeOptions {
+                    compact: self.compact,
+                },
+            )?;
+        }
+        if !accepted_patterns.is_empty() {
+            append_patterns(&dest_path, &accepted_patterns)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SuggestArgs {
+    /// Downloads the templates worth matching against, as (name, upstream path, content).
+    ///
+    /// Candidates are the detected stacks, the templates hinted at by the
+    /// clusters and every template downloaded by an earlier run. Downloads are
+    /// kept in their own cache, so later runs work offline.
+    fn load_templates(
+        &self,
+        root: &Path,
+        clusters: &[Cluster],
+        cache: &Cache<String>,
+        cache_manager: &CacheManager,
+    ) -> anyhow::Result<Vec<(String, String, String)>> {
+        let mut bodies: Cache<String> = cache_manager.load_cache(GITIGNORE_BODIES_CACHE_NAME)?;
+
+        let mut names: Vec<String> = detect_stack(root)?
+            .into_iter()
+            .map(|d| d.template)
+            .collect();
+        for (hint, template) in HINTS {
+            let Line::Rule(rule) = rules::parse_line(hint) else {
+                continue;
+            };
+            if clusters.iter().any(|c| covered_by(&rule, c)) {
+                names.push(template.to_string());
+            }
+        }
+        // Templates downloaded before are matched too, under their cache key
+        let mut cached_keys: Vec<&String> = cache
+            .entries
+            .iter()
+            .filter(|(_, entry)| bodies.contains_key(&entry.data))
+            .map(|(key, _)| key)
+            .collect();
+        cached_keys.sort();
+        names.extend(cached_keys.into_iter().cloned());
+
+        let fetcher = Fetcher::new();
+        let mut seen = HashSet::new();
+        let mut templates = Vec::new();
+        let mut downloaded = false;
+
+        for name in names {
+            let Ok(path) = find_template_in_cache(&name, cache) else {
+                continue;
+            };
+            if !seen.insert(path.to_string()) {
+                continue;
+            }
+
+            let content = match bodies.get(path) {
+                Some(content)
+                    if !self.update_cache
+                        && !bodies.is_entry_stale(path, CACHE_MAX_AGE_SECONDS) =>
+                {
+                    content.clone()
+                }
+                _ => {
+                    let pb = progress::spinner(&format!("Fetching gitignore template: {}", name));
+                    let fetched = fetcher.fetch_content(&template_url(path)?);
+                    pb.finish_and_clear();
+                    match fetched {
+                        Ok(content) => {
+                            bodies.insert(path.to_string(), content.clone());
+                            downloaded = true;
+                            content
+                        }
+                        Err(e) => {
+                            eprintln!("{} Skipping template {}: {}", "⚠".yellow(), name, e);
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            templates.push((name, path.to_string(), content));
+        }
+
+        if downloaded {
+            cache_manager.save_cache(GITIGNORE_BODIES_CACHE_NAME, &bodies)?;
+        }
+
+        Ok(templates)
+    }
+}
+
+/// Groups untracked paths under the fewest patterns that ignore only them.
+///
+/// Untracked files are grouped by extension (`*.log`) or, without one, by name
+/// (`.env`), and directories by name (`target/`). A directory holding only files
+/// that an extension group already ignores joins that group. A group whose
+/// pattern would also match tracked files is split into one anchored pattern
+/// per path.
+fn cluster(root: &Path, untracked: &[String], tracked: &[String]) -> Vec<Cluster> {
+    let tracked_names: HashSet<&str> = tracked.iter().flat_map(|path| path.split('/')).collect();
+    let tracked_exts: HashSet<String> = tracked.iter().filter_map(|p| extension(p)).collect();
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    // Files first, so that directories can join their extension groups
+    for path in untracked.iter().filter(|p| !p.ends_with('/')) {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let pattern = match extension(path) {
+            Some(ext) if !tracked_exts.contains(&ext) => format!("*.{}", ext),
+            Some(_) => format!("/{}", path),
+            None if tracked_names.contains(name) => format!("/{}", path),
+            None => name.to_string(),
+        };
+        add_to_cluster(&mut clusters, pattern, path.clone(), false);
+    }
+
+    for dir in untracked.iter().filter_map(|p| p.strip_suffix('/')) {
+        let files = files_below(root, dir);
+        let joinable = clusters
+            .iter()
+            .filter(|c| c.pattern.starts_with("*."))
+            .find(|c| !files.is_empty() && files.iter().all(|f| glob_matches(&c.pattern, f)))
+            .map(|c| c.pattern.clone());
+
+        match joinable {
+            Some(pattern) => {
+                for file in files {
+                    add_to_cluster(&mut clusters, pattern.clone(), file, false);
+                }
+            }
+            None => {
+                let name = dir.rsplit('/').next().unwrap_or(dir);
+                let pattern = match tracked_names.contains(name) {
+                    true => format!("/{}/", dir),
+                    false => format!("{}/", name),
+                };
+                add_to_cluster(&mut clusters, pattern, dir.to_string(), true);
+            }
+        }
+    }
+
+    clusters
+}
+
+fn add_to_cluster(clusters: &mut Vec<Cluster>, pattern: String, path: String, is_dir: bool) {
+    match clusters.iter_mut().find(|c| c.pattern == pattern) {
+        Some(cluster) => cluster.paths.push((path, is_dir)),
+        None => clusters.push(Cluster {
+            pattern,
+            paths: vec![(path, is_dir)],
+        }),
+    }
+}
+
+/// Recommends templates greedily, the one covering the most clusters first.
+///
+/// Returns the recommendations and the clusters no template covers.
+fn recommend(
+    root: &Path,
+    mut clusters: Vec<Cluster>,
+    templates: Vec<(String, String, String)>,
+) -> (Vec<Recommendation>, Vec<Cluster>) {
+    let mut candidates: Vec<(Recommendation, Matcher)> = templates
+        .into_iter()
+        .map(|(template, template_path, content)| {
+            let mut matcher = Matcher::new(root);
+            matcher.add_rules("", &template_path, &content);
+            let recommendation = Recommendation {
+                template,
+                template_path,
+                content,
+                clusters: Vec::new(),
+            };
+            (recommendation, matcher)
+        })
+        .collect();
+
+    let mut recommendations = Vec::new();
+    loop {
+        let covers = |matcher: &Matcher, cluster: &Cluster| {
+            cluster
+                .paths
+                .iter()
+                .all(|(path, is_dir)| matcher.ignored_by(path, *is_dir).is_some())
+        };
+
+        // Earlier candidates win ties, so detected stacks come before hints
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, (_, matcher))| (i, clusters.iter().filter(|c| covers(matcher, c)).count()))
+            .filter(|(_, count)| *count > 0)
+            .max_by(|(i, a), (j, b)| a.cmp(b).then(j.cmp(i)));
+        let Some((index, _)) = best else {
+            break;
+        };
+
+        let (mut recommendation, matcher) = candidates.remove(index);
+        let (covered, rest): (Vec<Cluster>, Vec<Cluster>) =
+            clusters.into_iter().partition(|c| covers(&matcher, c));
+        recommendation.clusters = covered;
+        clusters = rest;
+        recommendations.push(recommendation);
+    }
+
+    (recommendations, clusters)
+}
+
+/// Separates clusters that look like new source files, which should be
+/// committed rather than ignored: any file with an extension or name that
+/// tracked files have.
+fn split_source_like(
+    root: &Path,
+    clusters: Vec<Cluster>,
+    tracked: &[String],
+) -> (Vec<Cluster>, Vec<Cluster>) {
+    let tracked_exts: HashSet<String> = tracked.iter().filter_map(|p| extension(p)).collect();
+    let tracked_names: HashSet<&str> = tracked
+        .iter()
+        .filter_map(|p| p.rsplit('/').next())
+        .collect();
+
+    let looks_like_source = |path: &str| {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        tracked_names.contains(name) || extension(path).is_some_and(|e| tracked_exts.contains(&e))
+    };
+
+    clusters.into_iter().partition(|cluster| {
+        !cluster.paths.iter().any(|(path, is_dir)| match is_dir {
+            true => files_below(root, path).iter().any(|f| looks_like_source(f)),
+            false => looks_like_source(path),
+        })
+    })
+}
+
+/// Files below a directory, relative to the root, looking at no more than a few hundred.
+fn files_below(root: &Path, dir: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_string()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(root.join(&dir)) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            if files.len() >= 500 {
+                return files;
+            }
+            let path = format!("{}/{}", dir, entry.file_name().to_string_lossy());
+            if entry.path().is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Whether a gitignore pattern matches a file path.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    match rules::parse_line(pattern) {
+        Line::Rule(rule) => rule.matches(path, false),
+        _ => false,
+    }
+}
+
+/// Whether every path of the cluster matches the rule.
+fn covered_by(rule: &Rule, cluster: &Cluster) -> bool {
+    cluster
+        .paths
+        .iter()
+        .all(|(path, is_dir)| rule.matches(path, *is_dir))
+}
+
+fn extension(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+}
+
+fn display_path(path: &str, is_dir: bool) -> String {
+    if is_dir {
+        format!("{}/", path)
+    } else {
+        path.to_string()
+    }
+}
+
+fn print_suggestions(recommendations: &[Recommendation], custom: &[Cluster], left: &[Cluster]) {
+    if recommendations.is_empty() {
+        println!("{} Recommended templates: none", "✓".green());
+    } else {
+        println!("{} Recommended templates:", "✓".green());
+        for r in recommendations {
+            let patterns: Vec<&str> = r.clusters.iter().map(|c| c.pattern.as_str()).collect();
+            println!(
+                "  {} {:<24} {:<36} covers {}",
+                ">".green(),
+                r.template,
+                r.template_path,
+                patterns.join(", ")
+            );
+        }
+    }
+
+    if custom.is_empty() {
+        println!("{} Custom patterns: none", "✓".green());
+    } else {
+        println!("{} Custom patterns:", "✓".green());
+        for cluster in custom {
+            println!(
+                "  {} {:<24} {}",
+                ">".green(),
+                cluster.pattern,
+                cluster.examples().dimmed()
+            );
+        }
+    }
+
+    if !left.is_empty() {
+        println!(
+            "{} Left alone, these look like new source files:",
+            "⚠".yellow()
+        );
+        for cluster in left {
+            println!("  {} {}", "!".yellow(), cluster.examples());
+        }
+    }
+}
+
+/// Appends hand-written patterns after everything else in the file, outside any managed block.
+fn append_patterns(dest_path: &Path, clusters: &[&Cluster]) -> anyhow::Result<()> {
+    let mut content = String::new();
+    if let Ok(existing) = fs::read_to_string(dest_path) {
+        if !existing.is_empty() && !existing.ends_with('\n') {
+            content.push('\n');
+        }
+        // A legacy block runs until a blank line, end it so it does not take our patterns
+        let trailing_legacy = sections::find_blocks(&existing)
+            .last()
+            .is_some_and(|b| b.legacy && b.end == existing.lines().count());
+        if trailing_legacy
+            && existing
+                .lines()
+                .last()
+                .is_some_and(|l| !l.trim().is_empty())
+        {
+            content.push('\n');
+        }
+    }
+    for cluster in clusters {
+        content.push_str(&cluster.pattern);
+        content.push('\n');
+    }
+
+    file::append_file(&content, dest_path, None)?;
+    println!(
+        "{} Added {} custom pattern(s) to {}",
+        "✓".green(),
+        clusters.len(),
+        dest_path.display()
+    );
+    Ok(())
+}
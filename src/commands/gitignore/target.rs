@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::ValueEnum;
+use colored::*;
+
+/// Where `gitignore add` writes its templates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Target {
+    /// A .gitignore file in the repository, shared with everyone
+    #[default]
+    Repo,
+    /// The repository's .git/info/exclude, never committed
+    LocalExclude,
+    /// The user's global excludes file (core.excludesFile)
+    Global,
+}
+
+impl Target {
+    /// Resolves the file to write for a non-repo target, creating its directory.
+    ///
+    /// `repo_dir` is the repository whose exclude file is used for `local-exclude`.
+    /// Returns `None` for `repo`, whose file is chosen by `--dir` and `--output`.
+    pub fn resolve(&self, repo_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let path = match self {
+            Target::Repo => return Ok(None),
+            Target::LocalExclude => local_exclude_path(repo_dir)?,
+            Target::Global => global_excludes_path()?,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Some(path))
+    }
+}
+
+/// Asks git where the exclude file lives, which also works in worktrees.
+fn local_exclude_path(repo_dir: &Path) -> anyhow::Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "info/exclude"])
+        .current_dir(repo_dir)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Ok(repo_dir.join(path))
+        }
+        _ if repo_dir.join(".git").is_dir() => {
+            Ok(repo_dir.join(".git").join("info").join("exclude"))
+        }
+        _ => Err(anyhow::anyhow!(
+            "'{}' is not a git repository, so it has no .git/info/exclude.",
+            repo_dir.display()
+        )),
+    }
+}
+
+/// Returns the file configured as `core.excludesFile`, registering git's
+/// default location when none is configured.
+fn global_excludes_path() -> anyhow::Result<PathBuf> {
+    let configured = Command::new("git")
+        .args(["config", "--global", "--get", "core.excludesFile"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    if let Some(value) = configured {
+        return Ok(expand_home(&value));
+    }
+
+    let path = default_excludes_path()?;
+    let status = Command::new("git")
+        .args(["config", "--global", "core.excludesFile"])
+        .arg(&path)
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to register '{}' as core.excludesFile",
+            path.display()
+        ));
+    }
+
+    println!(
+        "{} Registered {} as core.excludesFile",
+        "✓".green(),
+        path.display()
+    );
+    Ok(path)
+}
+
+/// Git's default global excludes file: `$XDG_CONFIG_HOME/git/ignore` or `~/.config/git/ignore`.
+fn default_excludes_path() -> anyhow::Result<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the home directory"))?
+            .join(".config"),
+    };
+    Ok(config_home.join("git").join("ignore"))
+}
+
+fn expand_home(value: &str) -> PathBuf {
+    match (value.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(value),
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use colored::*;
+use toml_edit::{DocumentMut, Item};
+
+use crate::utils::cache::Cache;
+
+use super::detect::{Detection, SKIPPED_DIRS, detect_stack_to_depth};
+use super::find_template_in_cache;
+use super::rules::{self, Line, Rule};
+use super::sections::same_template;
+
+/// How deep below the repository root member globs are expanded.
+const MAX_MEMBER_DEPTH: usize = 4;
+
+/// A package declared by one of the workspace manifests.
+#[derive(Debug, Clone)]
+pub struct Member {
+    /// Directory relative to the repository root, `/`-separated
+    pub path: String,
+    /// The manifests that declare it, e.g. `Cargo.toml`
+    pub declared_in: Vec<&'static str>,
+}
+
+/// A template in the placement plan, with what suggested it.
+#[derive(Debug, Clone)]
+pub struct Placed {
+    pub template: String,
+    pub reason: String,
+}
+
+/// One .gitignore file of the placement plan.
+#[derive(Debug, Clone)]
+pub struct Placement {
+    /// Directory relative to the repository root, empty for the root itself
+    pub dir: String,
+    pub templates: Vec<Placed>,
+}
+
+impl Placement {
+    pub fn file(&self) -> String {
+        if self.dir.is_empty() {
+            ".gitignore".to_string()
+        } else {
+            format!("{}/.gitignore", self.dir)
+        }
+    }
+}
+
+/// Member globs read from a workspace manifest.
+struct Globs {
+    manifest: &'static str,
+    /// File that every member directory must contain
+    member_marker: &'static str,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+/// Finds the workspace members declared by Cargo.toml `[workspace]`, package.json
+/// `workspaces`, pnpm-workspace.yaml and pyproject.toml `[tool.uv.workspace]`.
+pub fn discover_members(root: &Path) -> anyhow::Result<Vec<Member>> {
+    let sources = [
+        cargo_globs(root)?,
+        package_json_globs(root)?,
+        pnpm_globs(root)?,
+        uv_globs(root)?,
+    ];
+
+    let mut members: Vec<Member> = Vec::new();
+    for globs in sources.into_iter().flatten() {
+        for path in expand(root, &globs)? {
+            match members.iter_mut().find(|m| m.path == path) {
+                Some(member) => member.declared_in.push(globs.manifest),
+                None => members.push(Member {
+                    path,
+                    declared_in: vec![globs.manifest],
+                }),
+            }
+        }
+    }
+
+    members.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(members)
+}
+
+fn cargo_globs(root: &Path) -> anyhow::Result<Option<Globs>> {
+    let Some(doc) = read_toml(root, "Cargo.toml")? else {
+        return Ok(None);
+    };
+    let Some(workspace) = doc.get("workspace") else {
+        return Ok(None);
+    };
+
+    Ok(Some(Globs {
+        manifest: "Cargo.toml",
+        member_marker: "Cargo.toml",
+        include: toml_strings(workspace.get("members")),
+        exclude: toml_strings(workspace.get("exclude")),
+    }))
+}
+
+fn uv_globs(root: &Path) -> anyhow::Result<Option<Globs>> {
+    let Some(doc) = read_toml(root, "pyproject.toml")? else {
+        return Ok(None);
+    };
+    let workspace = doc
+        .get("tool")
+        .and_then(|tool| tool.get("uv"))
+        .and_then(|uv| uv.get("workspace"));
+    let Some(workspace) = workspace else {
+        return Ok(None);
+    };
+
+    Ok(Some(Globs {
+        manifest: "pyproject.toml",
+        member_marker: "pyproject.toml",
+        include: toml_strings(workspace.get("members")),
+        exclude: toml_strings(workspace.get("exclude")),
+    }))
+}
+
+fn package_json_globs(root: &Path) -> anyhow::Result<Option<Globs>> {
+    let path = root.join("package.json");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| anyhow::anyhow!("Failed to parse package.json: {}", e))?;
+
+    // Either an array of globs or, with Yarn, `{ "packages": [...] }`
+    let workspaces = match json.get("workspaces") {
+        Some(serde_json::Value::Object(map)) => map.get("packages"),
+        other => other,
+    };
+    let Some(serde_json::Value::Array(globs)) = workspaces else {
+        return Ok(None);
+    };
+
+    let globs: Vec<String> = globs
+        .iter()
+        .filter_map(|g| g.as_str().map(str::to_string))
+        .collect();
+    Ok(Some(split_negations("package.json", "package.json", globs)))
+}
+
+fn pnpm_globs(root: &Path) -> anyhow::Result<Option<Globs>> {
+    let path = root.join("pnpm-workspace.yaml");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| anyhow::anyhow!("Failed to parse pnpm-workspace.yaml: {}", e))?;
+
+    let globs: Vec<String> = yaml
+        .get("packages")
+        .and_then(|p| p.as_sequence())
+        .map(|seq| {
+            seq.iter()
+                .filter_map(|g| g.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Some(split_negations(
+        "pnpm-workspace.yaml",
+        "package.json",
+        globs,
+    )))
+}
+
+fn read_toml(root: &Path, name: &str) -> anyhow::Result<Option<DocumentMut>> {
+    let path = root.join(name);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let doc = fs::read_to_string(&path)?
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", name, e))?;
+    Ok(Some(doc))
+}
+
+fn toml_strings(item: Option<&Item>) -> Vec<String> {
+    item.and_then(|item| item.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// npm and pnpm exclude members with `!` globs in the same list.
+fn split_negations(
+    manifest: &'static str,
+    member_marker: &'static str,
+    globs: Vec<String>,
+) -> Globs {
+    let (exclude, include): (Vec<String>, Vec<String>) =
+        globs.into_iter().partition(|g| g.starts_with('!'));
+    Globs {
+        manifest,
+        member_marker,
+        include,
+        exclude: exclude
+            .into_iter()
+            .map(|g| g.trim_start_matches('!').to_string())
+            .collect(),
+    }
+}
+
+/// Expands member globs to the directories below `root` that hold the member marker.
+fn expand(root: &Path, globs: &Globs) -> anyhow::Result<Vec<String>> {
+    let include: Vec<Rule> = globs.include.iter().filter_map(|g| glob_rule(g)).collect();
+    let exclude: Vec<Rule> = globs.exclude.iter().filter_map(|g| glob_rule(g)).collect();
+
+    let mut found = Vec::new();
+    let mut dirs = VecDeque::from([(String::new(), 0)]);
+
+    while let Some((dir, depth)) = dirs.pop_front() {
+        // An unreadable subdirectory hides its members, it does not stop the expansion
+        let read_dir = match fs::read_dir(root.join(&dir)) {
+            Ok(read_dir) => read_dir,
+            Err(_) if depth > 0 => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries: Vec<_> = read_dir
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            let rel = if dir.is_empty() {
+                name
+            } else {
+                format!("{}/{}", dir, name)
+            };
+
+            if include.iter().any(|r| r.matches(&rel, true))
+                && !exclude.iter().any(|r| r.matches(&rel, true))
+                && entry.path().join(globs.member_marker).is_file()
+            {
+                found.push(rel.clone());
+            }
+            if depth + 1 < MAX_MEMBER_DEPTH {
+                dirs.push_back((rel, depth + 1));
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+/// Reads a member glob (`crates/*`, `./packages/**`) as a rule anchored at the root.
+fn glob_rule(glob: &str) -> Option<Rule> {
+    let glob = glob.trim().trim_start_matches("./").trim_end_matches('/');
+    if glob.is_empty() || glob == "." {
+        return None;
+    }
+    match rules::parse_line(&format!("/{}", glob)) {
+        Line::Rule(rule) => Some(rule),
+        _ => None,
+    }
+}
+
+/// Decides which .gitignore file each detected template goes to.
+///
+/// Templates suggested by the root's own markers, editor and OS templates, and
+/// templates every member needs go to the root file. Every other template goes
+/// to the member that needs it. `requested` templates always go to the root.
+pub fn plan(
+    root: &Path,
+    members: &[Member],
+    requested: &[String],
+) -> anyhow::Result<Vec<Placement>> {
+    let mut shared: Vec<Placed> = requested
+        .iter()
+        .map(|t| Placed {
+            template: t.clone(),
+            reason: "requested".to_string(),
+        })
+        .collect();
+
+    for detection in detect_stack_to_depth(root, 0)? {
+        push_unique(
+            &mut shared,
+            &detection.template,
+            format!("found {}", detection.trigger.display()),
+        );
+    }
+
+    let mut detected: Vec<(&Member, Vec<Detection>)> = Vec::new();
+    for member in members {
+        detected.push((member, detect_stack_to_depth(&root.join(&member.path), 0)?));
+    }
+
+    for (member, detections) in &detected {
+        for detection in detections {
+            let everywhere = members.len() > 1
+                && detected.iter().all(|(_, other)| {
+                    other
+                        .iter()
+                        .any(|d| same_template(&d.template, &detection.template))
+                });
+            if everywhere {
+                push_unique(
+                    &mut shared,
+                    &detection.template,
+                    "used by every member".to_string(),
+                );
+            } else if is_global(&detection.template) {
+                push_unique(
+                    &mut shared,
+                    &detection.template,
+                    format!("found {}/{}", member.path, detection.trigger.display()),
+                );
+            }
+        }
+    }
+
+    let mut placements = vec![Placement {
+        dir: String::new(),
+        templates: shared,
+    }];
+
+    for (member, detections) in &detected {
+        let mut templates = Vec::new();
+        for detection in detections {
+            if !placements[0]
+                .templates
+                .iter()
+                .any(|p| same_template(&p.template, &detection.template))
+            {
+                push_unique(
+                    &mut templates,
+                    &detection.template,
+                    format!("found {}/{}", member.path, detection.trigger.display()),
+                );
+            }
+        }
+        placements.push(Placement {
+            dir: member.path.clone(),
+            templates,
+        });
+    }
+
+    Ok(placements)
+}
+
+fn push_unique(placed: &mut Vec<Placed>, template: &str, reason: String) {
+    if !placed.iter().any(|p| same_template(&p.template, template)) {
+        placed.push(Placed {
+            template: template.to_string(),
+            reason,
+        });
+    }
+}
+
+/// Editor and OS templates describe the developer's machine, not a package.
+fn is_global(template: &str) -> bool {
+    template.to_lowercase().starts_with("global/")
+}
+
+pub fn print_members(members: &[Member]) {
+    println!(
+        "{} Found {} workspace member(s):",
+        "✓".green(),
+        members.len()
+    );
+    for member in members {
+        println!(
+            "  {} {:<32} ({})",
+            ">".green(),
+            member.path,
+            member.declared_in.join(", ")
+        );
+    }
+}
+
+/// Prints each file of the plan with the templates it gets and why.
+pub fn print_plan(placements: &[Placement], cache: &Cache<String>) {
+    println!("{} Placement plan:", "✓".green());
+    for placement in placements {
+        if placement.templates.is_empty() {
+            let note = if placement.dir.is_empty() {
+                "nothing shared".to_string()
+            } else {
+                format!("{} is covered by the root .gitignore", placement.dir)
+            };
+            println!("  {}", note.dimmed());
+            continue;
+        }
+
+        println!("  {}", placement.file().bold());
+        for placed in &placement.templates {
+            match find_template_in_cache(&placed.template, cache) {
+                Ok(path) => println!(
+                    "    {} {:<24} {:<36} ({})",
+                    ">".green(),
+                    placed.template,
+                    path,
+                    placed.reason
+                ),
+                Err(_) => println!(
+                    "    {} {:<24} {:<36} ({})",
+                    "!".red(),
+                    placed.template,
+                    "not in cache".yellow(),
+                    placed.reason
+                ),
+            }
+        }
+    }
+}
@@ -152,8 +152,8 @@ fn download_single_template(
         // Default: .github/ISSUE_TEMPLATE/<template_name>.yml
         let default_path = Path::new(OUTPUT_BASE_PATH).join(OUTPUT);
         dir_path
-            .map(|p| p.join(format!("{}", template_file)))
-            .unwrap_or_else(|| default_path.join(format!("{}", template_file)))
+            .map(|p| p.join(&template_file))
+            .unwrap_or_else(|| default_path.join(&template_file))
     };
 
     file::save_file(&content, &dest_path, force)?;
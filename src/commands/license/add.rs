@@ -1,25 +1,41 @@
 use std::collections::HashMap;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow};
+use clap::ValueEnum;
 use colored::*;
 use regex::Regex;
 
-use crate::utils::cache::CacheManager;
+use crate::utils::cache::{Cache, CacheManager};
 use crate::utils::file;
 use crate::utils::progress;
+use crate::utils::prompt;
 use crate::utils::remote::Fetcher;
 
+use super::defaults::{Inferred, ProjectDefaults};
+use super::expression::{Expression, Term};
+use super::manifest;
+use super::notice;
+use super::template::{self, Variable};
 use super::{
-    SPDX_CACHE_NAME, SPDX_LICENSE_DETAILS_BASE_URL, SPDX_LICENSE_LIST_URL,
-    ensure_spdx_license_cache,
+    SPDX_CACHE_NAME, SPDX_EXCEPTION_TEXT_CACHE_NAME, SPDX_LICENSE_LIST_URL, SPDX_TEXT_CACHE_NAME,
+    canonical_license_id, ensure_spdx_exception_cache, ensure_spdx_license_cache,
+    exception_details, license_details,
 };
 
+/// Where the license texts of a multi-license expression are written.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// LICENSE-MIT, LICENSE-APACHE, ... next to a short LICENSE
+    Suffix,
+    /// LICENSES/MIT.txt, LICENSES/Apache-2.0.txt, ... as in the REUSE specification
+    Reuse,
+}
+
 // Command to add licenses
 #[derive(clap::Args, Debug)]
 pub struct AddArgs {
-    /// License IDs to add (e.g., mit, apache-2.0)
+    /// License IDs to add (e.g., mit, apache-2.0), or an SPDX expression (e.g., "MIT OR Apache-2.0")
     #[arg(value_name = "LICENSE")]
     pub licenses: Vec<String>,
 
@@ -50,6 +66,26 @@ pub struct AddArgs {
     /// Output file names for the licenses (in order of licenses)
     #[arg(short = 'o', long, value_name = "OUTPUT", num_args = 1.., requires = "licenses")]
     pub output: Vec<String>,
+
+    /// Do not fill the year, copyright holder and project name from git and the project manifests
+    #[arg(long)]
+    pub no_defaults: bool,
+
+    /// Leave out the parts of the license text that SPDX marks optional, such as the title
+    #[arg(long)]
+    pub omit_optional: bool,
+
+    /// File layout for an expression with several licenses
+    #[arg(long, value_enum, default_value = "suffix")]
+    pub layout: Layout,
+
+    /// Set the license field of Cargo.toml, package.json and pyproject.toml to the added license
+    #[arg(long, conflicts_with = "all")]
+    pub update_manifests: bool,
+
+    /// Do not create a NOTICE file when adding Apache-2.0
+    #[arg(long)]
+    pub no_notice: bool,
 }
 
 impl super::Runnable for AddArgs {
@@ -79,16 +115,42 @@ impl super::Runnable for AddArgs {
             }
         }
 
+        let defaults = if self.no_defaults {
+            ProjectDefaults::default()
+        } else {
+            ProjectDefaults::detect(&dir)
+        };
+
         let config = LicenseDownloadConfig {
             dir_path: Some(&dir),
             force: self.force,
             interactive: self.interactive,
             placeholder_params: &placeholder_params,
+            defaults: &defaults,
+            omit_optional: self.omit_optional,
             update_cache: self.update_cache,
         };
 
+        let expression = self.licenses.join(" ");
+
+        // The expression the added files are licensed under, for the manifests
+        let mut added = None;
+        let mut added_ids: Vec<String> = Vec::new();
         if self.all {
             download_all_licenses(&config)?;
+        } else if Expression::is_compound(&expression) {
+            if !self.output.is_empty() {
+                return Err(anyhow!(
+                    "--output cannot be used with a license expression. Use --layout to choose the file names."
+                ));
+            }
+            let added_expression = add_expression(&expression, &config, self.layout)?;
+            added_ids = Expression::parse(&added_expression)?
+                .terms()
+                .into_iter()
+                .map(|term| term.id)
+                .collect();
+            added = Some(added_expression);
         } else if self.licenses.is_empty() {
             return Err(anyhow!(
                 "At least one license ID is required (or use --all)"
@@ -102,31 +164,110 @@ impl super::Runnable for AddArgs {
                 }
 
                 for (license_id, output_name) in self.licenses.iter().zip(self.output.iter()) {
-                    if let Err(e) =
-                        download_single_license(license_id, &config, Some(output_name.clone()))
-                    {
-                        eprintln!(
+                    match download_single_license(license_id, &config, Some(output_name.clone())) {
+                        Ok(id) => {
+                            added_ids.push(id.clone());
+                            added = Some(id);
+                        }
+                        Err(e) => eprintln!(
                             "{}",
                             format!("Failed to download {}: {}", license_id, e).red()
-                        );
+                        ),
                     }
                 }
             } else {
                 for license_id in &self.licenses {
-                    if let Err(e) = download_single_license(license_id, &config, None) {
-                        eprintln!(
+                    match download_single_license(license_id, &config, None) {
+                        Ok(id) => {
+                            added_ids.push(id.clone());
+                            added = Some(id);
+                        }
+                        Err(e) => eprintln!(
                             "{}",
                             format!("Failed to download {}: {}", license_id, e).red()
-                        );
+                        ),
                     }
                 }
             }
+            // Separate files do not say how the licenses combine
+            if self.licenses.len() > 1 {
+                added = None;
+                if self.update_manifests {
+                    println!(
+                        "{} Not updating the manifests: pass the licenses as one expression, such as \"MIT OR Apache-2.0\".",
+                        "⚠".yellow()
+                    );
+                }
+            }
+        }
+
+        if added_ids.iter().any(|id| id == "Apache-2.0") && !self.no_notice {
+            add_notice(&dir, &defaults.clone().with_params(&placeholder_params))?;
+        }
+
+        if let Some(added) = added {
+            if self.update_manifests {
+                update_manifests(&dir, &added)?;
+            } else {
+                let ids: Vec<String> = Expression::parse(&added)?
+                    .terms()
+                    .into_iter()
+                    .map(|term| term.id)
+                    .collect();
+                manifest::warn_disagreements(
+                    &dir,
+                    &ids,
+                    "Run with --update-manifests to update them.",
+                );
+            }
         }
 
         Ok(())
     }
 }
 
+/// Creates the NOTICE file Apache-2.0 asks projects to ship, unless there is one.
+fn add_notice(dir: &Path, defaults: &ProjectDefaults) -> Result<()> {
+    if notice::find(dir).is_some() {
+        return Ok(());
+    }
+    match notice::notice_header(dir, defaults) {
+        Some(header) => file::save_file(&header, &dir.join("NOTICE"), false),
+        None => {
+            println!(
+                "{} No NOTICE file created: the copyright holder is unknown. Run `gh-templates license notice init --holder NAME` to create one.",
+                "⚠".yellow()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Sets the manifests' license fields to `expression` and reports the changes.
+fn update_manifests(dir: &Path, expression: &str) -> Result<()> {
+    let updated = manifest::update_license_fields(dir, expression)?;
+    for field in &updated {
+        println!(
+            "{} Set {} {} to \"{}\"",
+            "✓".green(),
+            field.manifest,
+            field.location,
+            expression
+        );
+    }
+    if updated.is_empty() {
+        if manifest::license_fields(dir).is_empty() {
+            println!(
+                "{} No Cargo.toml, package.json or pyproject.toml to update",
+                "⚠".yellow()
+            );
+        } else {
+            println!("{} The manifests already use {}", "✓".green(), expression);
+        }
+    }
+    Ok(())
+}
+
 // Helper functions
 
 // ------------ HANDLE DOWNLOADS ------------
@@ -135,67 +276,350 @@ pub struct LicenseDownloadConfig<'a> {
     pub force: bool,
     pub interactive: bool,
     pub placeholder_params: &'a HashMap<String, String>,
+    /// Values for placeholders not given with `--param`
+    pub defaults: &'a ProjectDefaults,
+    pub omit_optional: bool,
     pub update_cache: bool,
 }
 
+/// Adds one license and returns its SPDX id.
 fn download_single_license(
     id: &str,
     config: &LicenseDownloadConfig,
     output_filename: Option<String>,
-) -> Result<()> {
-    let fetcher = Fetcher::new();
-
+) -> Result<String> {
     let mut cache_manager = CacheManager::new()?;
 
     let license_cache = ensure_spdx_license_cache(&mut cache_manager, config.update_cache)?;
+    let normalized_id = canonical_license_id(&license_cache, id)?;
 
-    let normalized_id = {
-        let id_lower = id.to_lowercase();
-        license_cache
-            .entries
-            .iter()
-            .find(|(k, _)| k.to_lowercase() == id_lower)
-            .map(|(key, _)| key.clone())
-            .ok_or_else(|| {
-                anyhow!(
-                    "License '{}' not found in SPDX cache. Please check the license ID.",
-                    id
-                )
-            })?
+    let license = fetch_license_text(&cache_manager, &normalized_id)?;
+    let processed_text = process_placeholders(&license, config)?;
+
+    let dest_filename = output_filename.unwrap_or_else(|| "LICENSE".to_string());
+    let dest_path: PathBuf = match config.dir_path {
+        Some(dir) => dir.join(dest_filename),
+        None => PathBuf::from(&dest_filename),
     };
 
-    let details_url = format!("{}/{}.json", SPDX_LICENSE_DETAILS_BASE_URL, normalized_id);
-    let pb = progress::spinner(&format!("Fetching license details: {}", id));
+    file::save_file(&processed_text, &dest_path, config.force)?;
 
-    let license_details = fetcher.fetch_json(&details_url).map_err(|e| {
-        anyhow!(
-            "Failed to fetch license '{}'. This might not be a valid SPDX license ID. Error: {}",
-            id,
-            e
-        )
-    })?;
+    Ok(normalized_id)
+}
 
-    pb.set_message("Processing license text");
+/// The SPDX text of a license and, when published, its `standardLicenseTemplate`.
+pub struct LicenseSource {
+    pub text: String,
+    template: Option<String>,
+}
 
-    let license_text = license_details
+/// The SPDX text of a license, downloaded into the text cache the first time it is needed.
+pub fn fetch_license_text(cache_manager: &CacheManager, id: &str) -> Result<LicenseSource> {
+    let details = fetch_details(
+        cache_manager,
+        id,
+        "license",
+        SPDX_TEXT_CACHE_NAME,
+        license_details,
+    )?;
+    let text = details
         .get("licenseText")
         .and_then(|t| t.as_str())
         .ok_or_else(|| anyhow!("License text not found in SPDX data"))?;
+    let template = details
+        .get("standardLicenseTemplate")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.trim().is_empty());
 
-    pb.finish_and_clear();
+    Ok(LicenseSource {
+        text: text.to_string(),
+        template: template.map(str::to_string),
+    })
+}
 
-    let processed_text =
-        process_placeholders(license_text, config.interactive, config.placeholder_params)?;
+/// The SPDX text of a license exception, downloaded into its text cache the first time it is needed.
+pub fn fetch_exception_text(cache_manager: &CacheManager, id: &str) -> Result<String> {
+    let details = fetch_details(
+        cache_manager,
+        id,
+        "license exception",
+        SPDX_EXCEPTION_TEXT_CACHE_NAME,
+        exception_details,
+    )?;
+    details
+        .get("licenseExceptionText")
+        .and_then(|t| t.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("License exception text not found in SPDX data"))
+}
 
-    let dest_filename = output_filename.unwrap_or_else(|| "LICENSE".to_string());
-    let dest_path: PathBuf = match config.dir_path {
-        Some(dir) => dir.join(dest_filename),
-        None => PathBuf::from(&dest_filename),
+type DetailsFn = for<'a> fn(
+    &'a mut Cache<serde_json::Value>,
+    &str,
+    &Fetcher,
+) -> anyhow::Result<&'a serde_json::Value>;
+
+fn fetch_details(
+    cache_manager: &CacheManager,
+    id: &str,
+    kind: &str,
+    cache_name: &str,
+    details: DetailsFn,
+) -> Result<serde_json::Value> {
+    let mut text_cache: Cache<serde_json::Value> = cache_manager.load_cache(cache_name)?;
+    let cached = text_cache.contains_key(id);
+
+    let pb = (!cached).then(|| progress::spinner(&format!("Fetching {} details: {}", kind, id)));
+    let details = details(&mut text_cache, id, &Fetcher::new())
+        .cloned()
+        .map_err(|e| {
+            anyhow!(
+                "Failed to fetch {} '{}'. This might not be a valid SPDX ID. Error: {}",
+                kind,
+                id,
+                e
+            )
+        });
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+    let details = details?;
+
+    if !cached {
+        cache_manager.save_cache(cache_name, &text_cache)?;
+    }
+    Ok(details)
+}
+
+// ------------ HANDLE EXPRESSIONS ------------
+
+/// Adds the licenses of an SPDX expression. A single license goes to `LICENSE`; several
+/// get a file each, laid out by `layout`, plus a `LICENSE` explaining how they combine.
+/// Returns the expression with the ids spelled as SPDX does.
+fn add_expression(input: &str, config: &LicenseDownloadConfig, layout: Layout) -> Result<String> {
+    let mut cache_manager = CacheManager::new()?;
+    let license_list = ensure_spdx_license_cache(&mut cache_manager, config.update_cache)?;
+
+    let mut expression = Expression::parse(input)?;
+    let exception_list = if expression.terms().iter().any(|t| t.exception.is_some()) {
+        ensure_spdx_exception_cache(&mut cache_manager, config.update_cache)?
+    } else {
+        Cache::new()
     };
+    expression.canonicalize(&license_list, &exception_list)?;
+    let terms = expression.terms();
 
-    file::save_file(&processed_text, &dest_path, config.force)?;
+    let mut ids: Vec<&str> = Vec::new();
+    for term in &terms {
+        if !ids.contains(&term.id.as_str()) {
+            ids.push(&term.id);
+        }
+    }
+    let dir = config
+        .dir_path
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("."));
 
-    Ok(())
+    let file_names: HashMap<String, String> = if ids.len() == 1 {
+        HashMap::from([(ids[0].to_string(), "LICENSE".to_string())])
+    } else {
+        license_file_names(&ids, layout)
+    };
+    // REUSE keeps exceptions in files of their own; otherwise they follow the license text
+    let separate_exceptions = layout == Layout::Reuse && ids.len() > 1;
+
+    // Check every file up front so that a layout is never left half written
+    if !config.force {
+        let exception_files = terms
+            .iter()
+            .filter(|_| separate_exceptions)
+            .filter_map(|term| term.exception.as_ref())
+            .map(|exception| format!("LICENSES/{}.txt", exception));
+        let existing = file_names
+            .values()
+            .cloned()
+            .chain(exception_files)
+            .chain(std::iter::once("LICENSE".to_string()))
+            .find(|name| dir.join(name).exists());
+        if let Some(name) = existing {
+            return Err(anyhow!(
+                "File '{}' already exists. Use --force to overwrite.",
+                name
+            ));
+        }
+    }
+    if separate_exceptions {
+        std::fs::create_dir_all(dir.join("LICENSES"))?;
+    }
+
+    let mut written_exceptions = Vec::new();
+    for id in &ids {
+        let license = fetch_license_text(&cache_manager, id)?;
+        let mut processed_text = process_placeholders(&license, config)?;
+
+        for term in terms.iter().filter(|term| term.id == *id) {
+            let Some(exception) = &term.exception else {
+                continue;
+            };
+            if separate_exceptions && written_exceptions.contains(exception) {
+                continue;
+            }
+            let exception_text = fetch_exception_text(&cache_manager, exception)?;
+            if separate_exceptions {
+                let path = dir.join("LICENSES").join(format!("{}.txt", exception));
+                file::save_file(&exception_text, &path, config.force)?;
+                written_exceptions.push(exception.clone());
+            } else {
+                processed_text = format!("{}\n\n{}", processed_text.trim_end(), exception_text);
+            }
+        }
+
+        file::save_file(&processed_text, &dir.join(&file_names[*id]), config.force)?;
+    }
+
+    if ids.len() > 1 {
+        let names = SpdxNames {
+            licenses: &license_list,
+            exceptions: &exception_list,
+        };
+        let summary = expression_summary(
+            &expression,
+            &terms,
+            &names,
+            &file_names,
+            separate_exceptions,
+        );
+        file::save_file(&summary, &dir.join("LICENSE"), config.force)?;
+    }
+
+    println!(
+        "{} Licensed under {}",
+        "✓".green(),
+        expression.to_string().cyan()
+    );
+    Ok(expression.to_string())
+}
+
+/// File names for the licenses of an expression: `LICENSE-MIT`, `LICENSE-APACHE`, ...,
+/// or `LICENSES/<id>.txt`.
+fn license_file_names(ids: &[&str], layout: Layout) -> HashMap<String, String> {
+    // The id up to its version, e.g. APACHE for Apache-2.0 and BSD for BSD-3-Clause
+    let short_name = |id: &str| {
+        id.split('-')
+            .take_while(|part| !part.starts_with(|c: char| c.is_ascii_digit()))
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_uppercase()
+    };
+
+    ids.iter()
+        .map(|id| {
+            let name = match layout {
+                Layout::Reuse => format!("LICENSES/{}.txt", id),
+                Layout::Suffix => {
+                    let short = short_name(id);
+                    let clashes = ids
+                        .iter()
+                        .filter(|other| short_name(other) == short)
+                        .count()
+                        > 1;
+                    if short.is_empty() || clashes {
+                        format!("LICENSE-{}", id.to_uppercase())
+                    } else {
+                        format!("LICENSE-{}", short)
+                    }
+                }
+            };
+            (id.to_string(), name)
+        })
+        .collect()
+}
+
+/// The SPDX license and exception lists, for looking up full names.
+struct SpdxNames<'a> {
+    licenses: &'a Cache<serde_json::Value>,
+    exceptions: &'a Cache<serde_json::Value>,
+}
+
+impl SpdxNames<'_> {
+    fn license<'b>(&'b self, id: &'b str) -> &'b str {
+        full_name(self.licenses, id)
+    }
+
+    fn exception<'b>(&'b self, id: &'b str) -> &'b str {
+        full_name(self.exceptions, id)
+    }
+}
+
+fn full_name<'a>(list: &'a Cache<serde_json::Value>, id: &'a str) -> &'a str {
+    list.get(id)
+        .and_then(|entry| entry.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or(id)
+}
+
+/// The top-level LICENSE of a multi-license project, pointing at each license text.
+fn expression_summary(
+    expression: &Expression,
+    terms: &[Term],
+    names: &SpdxNames,
+    file_names: &HashMap<String, String>,
+    separate_exceptions: bool,
+) -> String {
+    let only_licenses = |parts: &[Expression]| {
+        parts
+            .iter()
+            .all(|part| matches!(part, Expression::License { .. }))
+    };
+    let (intro, outro) = match expression {
+        Expression::Or(parts) if only_licenses(parts) => (
+            "This project is licensed under either of".to_string(),
+            Some(
+                "at your option.\n\n\
+                 Unless you explicitly state otherwise, any contribution intentionally submitted\n\
+                 for inclusion in this project by you shall be licensed as above, without any\n\
+                 additional terms or conditions.",
+            ),
+        ),
+        Expression::And(parts) if only_licenses(parts) => (
+            "This project is licensed under all of the following licenses:".to_string(),
+            None,
+        ),
+        _ => (
+            format!(
+                "This project is licensed under {}, using these licenses:",
+                expression
+            ),
+            None,
+        ),
+    };
+
+    let mut summary = format!("{}\n\n", intro);
+    for term in terms {
+        let name = names.license(&term.id);
+        let file = &file_names[&term.id];
+        match &term.exception {
+            Some(exception) if separate_exceptions => summary.push_str(&format!(
+                "- {} with {} ({}, LICENSES/{}.txt)\n",
+                name,
+                names.exception(exception),
+                file,
+                exception
+            )),
+            Some(exception) => summary.push_str(&format!(
+                "- {} with {} ({})\n",
+                name,
+                names.exception(exception),
+                file
+            )),
+            None => summary.push_str(&format!("- {} ({})\n", name, file)),
+        }
+    }
+    if let Some(outro) = outro {
+        summary.push_str(&format!("\n{}\n", outro));
+    }
+    summary.push_str(&format!("\nSPDX-License-Identifier: {}\n", expression));
+    summary
 }
 
 fn download_all_licenses(config: &LicenseDownloadConfig) -> Result<()> {
@@ -246,11 +670,226 @@ fn download_all_licenses(config: &LicenseDownloadConfig) -> Result<()> {
 
 // ------------ HANDLE PLACEHOLDERS ------------
 
-fn process_placeholders(
+/// Fills in a license text. With an SPDX template only its variables are replaced:
+/// `--param` may name a variable (`copyright=...`) or a `<...>`/`[...]` placeholder
+/// inside one (`year=...`).
+fn process_placeholders(license: &LicenseSource, config: &LicenseDownloadConfig) -> Result<String> {
+    let Some(template) = &license.template else {
+        return process_bracket_placeholders(&license.text, config);
+    };
+    let segments = template::parse(template);
+
+    let mut result = license.text.clone();
+    if config.omit_optional {
+        result = remove_optional_sections(&result, &template::optional_sections(&segments));
+    }
+
+    // Bullets are numbering, not something to fill in
+    let variables: Vec<Variable> = template::variables(&segments)
+        .into_iter()
+        .filter(|v| !v.name.eq_ignore_ascii_case("bullet") && !v.original.trim().is_empty())
+        .filter(|v| !(config.omit_optional && v.optional))
+        .collect();
+
+    let placeholder_re = Regex::new(r"<([^<>]+)>|\[([^\[\]]+)\]")?;
+    let placeholder_name =
+        |caps: &regex::Captures| caps.get(1).or(caps.get(2)).unwrap().as_str().to_string();
+
+    // Placeholders inside the variables, in order of appearance
+    let mut placeholders: Vec<String> = Vec::new();
+    for variable in &variables {
+        for caps in placeholder_re.captures_iter(&variable.original) {
+            let name = placeholder_name(&caps);
+            let key = normalize_placeholder_key(&name);
+            if !placeholders
+                .iter()
+                .any(|p| normalize_placeholder_key(p) == key)
+            {
+                placeholders.push(name);
+            }
+        }
+    }
+
+    let normalized_params: HashMap<String, &String> = config
+        .placeholder_params
+        .iter()
+        .map(|(k, v)| (normalize_placeholder_key(k), v))
+        .collect();
+    let mut used_params = std::collections::HashSet::new();
+
+    if placeholders.is_empty()
+        && !variables
+            .iter()
+            .any(|v| normalized_params.contains_key(&normalize_placeholder_key(&v.name)))
+    {
+        println!("{}", "✓ No placeholders found in license text.".green());
+    } else if !config.interactive
+        && normalized_params.is_empty()
+        && !placeholders
+            .iter()
+            .any(|ph| config.defaults.for_placeholder(ph).is_some())
+    {
+        println!(
+            "{} License contains placeholders. Use --interactive or --param PLACEHOLDER=VALUE to fill them.",
+            "⚠".yellow()
+        );
+    }
+
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut unfilled_placeholders = Vec::new();
+    let mut inferred = Vec::new();
+    for ph in &placeholders {
+        let key = normalize_placeholder_key(ph);
+        let default = config.defaults.for_placeholder(ph);
+        if let Some(value) = normalized_params.get(&key) {
+            used_params.insert(key.clone());
+            values.insert(key, value.to_string());
+        } else if config.interactive {
+            let user_input = prompt_for_placeholder(ph, default);
+            if user_input == format!("[{}]", ph) {
+                unfilled_placeholders.push(ph.clone());
+            } else {
+                values.insert(key, user_input);
+            }
+        } else if let Some(default) = default {
+            values.insert(key, default.value.clone());
+            inferred.push((ph.clone(), default));
+        } else {
+            unfilled_placeholders.push(ph.clone());
+        }
+    }
+
+    // Replace each variable where it appears in the text, in template order
+    let mut kept = Vec::new();
+    let mut position = 0;
+    for variable in &variables {
+        let key = normalize_placeholder_key(&variable.name);
+        let has_placeholders = placeholder_re.is_match(&variable.original);
+        let replacement = if let Some(value) = normalized_params.get(&key) {
+            used_params.insert(key);
+            Some(value.to_string())
+        } else if has_placeholders {
+            Some(
+                placeholder_re
+                    .replace_all(&variable.original, |caps: &regex::Captures| {
+                        values
+                            .get(&normalize_placeholder_key(&placeholder_name(caps)))
+                            .cloned()
+                            .unwrap_or_else(|| caps[0].to_string())
+                    })
+                    .to_string(),
+            )
+        } else if config.interactive {
+            prompt_for_variable(&variable.name, &variable.original)
+        } else {
+            kept.push(variable.name.clone());
+            None
+        };
+
+        let pattern = Regex::new(&template::flexible_pattern(&variable.original))?;
+        let Some(found) = pattern.find_at(&result, position) else {
+            continue;
+        };
+        let range = found.range();
+        match replacement {
+            Some(replacement) => {
+                result.replace_range(range.clone(), &replacement);
+                position = range.start + replacement.len();
+            }
+            None => position = range.end,
+        }
+    }
+
+    print_inferred(&inferred);
+
+    // Warning for unused parameters
+    let unused_params: Vec<&String> = config
+        .placeholder_params
+        .keys()
+        .filter(|k| !used_params.contains(&normalize_placeholder_key(k)))
+        .collect();
+
+    if !unused_params.is_empty() {
+        println!(
+            "{} Warning: {} unused parameter(s):",
+            "⚠".yellow(),
+            unused_params.len()
+        );
+        for param in unused_params {
+            println!("  - {}", param);
+        }
+        println!("  Double-check parameter names match placeholders in the license.");
+    }
+
+    // Warning for unfilled placeholders
+    if !unfilled_placeholders.is_empty() {
+        println!(
+            "{} Warning: {} placeholder(s) remain unfilled:",
+            "⚠".yellow(),
+            unfilled_placeholders.len()
+        );
+        for ph in &unfilled_placeholders {
+            println!("  - [{}]", ph);
+        }
+        println!("  Use --interactive or --param to provide values for these placeholders.");
+    }
+
+    if !kept.is_empty() {
+        kept.dedup();
+        println!(
+            "  Kept the standard text of: {}. Replace it with --param NAME=VALUE.",
+            kept.join(", ")
+        );
+    }
+
+    // Summary message for user verification
+    let filled_count = placeholders.len() - unfilled_placeholders.len();
+    if filled_count > 0 {
+        println!(
+            "{} Filled {} out of {} placeholder(s).",
+            "✓".green(),
+            filled_count,
+            placeholders.len()
+        );
+        println!(
+            "{} Please carefully review the license text above for any missed or incorrect placeholders.",
+            "⚠".yellow()
+        );
+    }
+
+    Ok(result)
+}
+
+/// Removes the optional sections of a template from the text, tidying the blank lines left.
+fn remove_optional_sections(text: &str, sections: &[String]) -> String {
+    let mut result = text.to_string();
+    let mut position = 0;
+    for section in sections {
+        let Ok(pattern) = Regex::new(&format!("[ \t]*{}", template::flexible_pattern(section)))
+        else {
+            continue;
+        };
+        if let Some(found) = pattern.find_at(&result, position) {
+            let range = found.range();
+            result.replace_range(range.clone(), "");
+            position = range.start;
+        }
+    }
+
+    let blank_lines = Regex::new(r"\n[ \t]*\n(?:[ \t]*\n)+").unwrap();
+    blank_lines
+        .replace_all(result.trim_start(), "\n\n")
+        .to_string()
+}
+
+/// Guesses placeholders from `[...]` and `<...>` anywhere in the text, for licenses
+/// without a template.
+fn process_bracket_placeholders(
     license_text: &str,
-    interactive: bool,
-    placeholder_params: &HashMap<String, String>,
+    config: &LicenseDownloadConfig,
 ) -> Result<String> {
+    let interactive = config.interactive;
+    let placeholder_params = config.placeholder_params;
     let square_bracket_re = Regex::new(r"\[([^\]]+)\]")?;
     let angle_bracket_re = Regex::new(r"<([^>]+)>")?;
 
@@ -277,13 +916,18 @@ fn process_placeholders(
                 "⚠".yellow(),
                 placeholder_params.len()
             );
-            for (key, _) in placeholder_params {
+            for key in placeholder_params.keys() {
                 println!("  - {}", key);
             }
         }
 
         return Ok(license_text.to_string());
-    } else if !interactive && placeholder_params.is_empty() {
+    } else if !interactive
+        && placeholder_params.is_empty()
+        && !placeholders
+            .iter()
+            .any(|ph| config.defaults.for_placeholder(ph).is_some())
+    {
         println!(
             "{} License contains placeholders. Use --interactive or --param PLACEHOLDER=VALUE to fill them.",
             "⚠".yellow()
@@ -299,20 +943,25 @@ fn process_placeholders(
     // Track which parameters are actually used
     let mut used_params = std::collections::HashSet::new();
     let mut unfilled_placeholders = Vec::new();
+    let mut inferred = Vec::new();
 
     let mut result = license_text.to_string();
     for ph in &placeholders {
         let norm_ph = normalize_placeholder_key(ph);
+        let default = config.defaults.for_placeholder(ph);
 
         let replacement = if let Some(val) = normalized_params.get(&norm_ph) {
             used_params.insert(norm_ph.clone());
             val.to_string()
         } else if interactive {
-            let user_input = prompt_for_placeholder(ph);
+            let user_input = prompt_for_placeholder(ph, default);
             if user_input == format!("[{}]", ph) {
                 unfilled_placeholders.push(ph.clone());
             }
             user_input
+        } else if let Some(default) = default {
+            inferred.push((ph.clone(), default));
+            default.value.clone()
         } else {
             // Keep original placeholder and track as unfilled
             unfilled_placeholders.push(ph.clone());
@@ -340,6 +989,8 @@ fn process_placeholders(
             .to_string();
     }
 
+    print_inferred(&inferred);
+
     // Warning for unused parameters
     let unused_params: Vec<&String> = placeholder_params
         .keys()
@@ -393,18 +1044,44 @@ fn normalize_placeholder_key(s: &str) -> String {
     s.trim().to_lowercase().replace(' ', "-")
 }
 
-fn prompt_for_placeholder(placeholder_content: &str) -> String {
-    print!("Enter value for '{}': ", placeholder_content);
-    let _ = io::stdout().flush();
-    let mut input = String::new();
-    if io::stdin().read_line(&mut input).is_ok() {
-        let input = input.trim();
-        if !input.is_empty() {
-            input.to_string()
-        } else {
-            format!("[{}]", placeholder_content)
-        }
-    } else {
-        format!("[{}]", placeholder_content)
+/// Asks for a variable without placeholders; an empty answer keeps the standard text.
+fn prompt_for_variable(name: &str, original: &str) -> Option<String> {
+    prompt::read_line(&format!(
+        "Enter value for '{}' (default: {}): ",
+        name, original
+    ))
+    .filter(|input| !input.is_empty())
+}
+
+/// Lists the placeholders filled from project defaults, so they can be checked.
+fn print_inferred(inferred: &[(String, &Inferred)]) {
+    if inferred.is_empty() {
+        return;
+    }
+    println!("{} Filled from project defaults:", "✓".green());
+    for (placeholder, default) in inferred {
+        println!(
+            "  - {} = {} ({})",
+            placeholder,
+            default.value,
+            default.source.dimmed()
+        );
+    }
+    println!("  Override them with --param PLACEHOLDER=VALUE, or skip them with --no-defaults.");
+}
+
+/// Asks for a placeholder; an empty answer takes the default, if there is one.
+fn prompt_for_placeholder(placeholder_content: &str, default: Option<&Inferred>) -> String {
+    let question = match default {
+        Some(default) => format!(
+            "Enter value for '{}' [{}]: ",
+            placeholder_content, default.value
+        ),
+        None => format!("Enter value for '{}': ", placeholder_content),
+    };
+    match (prompt::read_line(&question), default) {
+        (Some(input), _) if !input.is_empty() => input,
+        (_, Some(default)) => default.value.clone(),
+        (_, None) => format!("[{}]", placeholder_content),
     }
 }
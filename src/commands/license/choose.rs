@@ -0,0 +1,343 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use colored::*;
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::progress;
+use crate::utils::prompt;
+
+use super::add::{AddArgs, Layout};
+use super::preview::{ChooseALicenseMeta, fetch_choosealicense_meta};
+use super::{
+    CACHE_MAX_AGE_SECONDS, CHOOSEALICENSE_CACHE_NAME, GITHUB_LICENSES_CACHE_NAME, Runnable,
+    ensure_github_api_license_cache,
+};
+
+#[derive(clap::Args, Debug)]
+pub struct ChooseArgs {
+    /// Whether the license must grant patent rights (asked when not given)
+    #[arg(long, value_enum, value_name = "ANSWER")]
+    pub patents: Option<Answer>,
+
+    /// Whether distributing changed versions must come with their source code
+    #[arg(long, value_enum, value_name = "ANSWER")]
+    pub disclose_source: Option<Answer>,
+
+    /// Whether offering the software over a network counts as distributing it
+    #[arg(long, value_enum, value_name = "ANSWER")]
+    pub network_use: Option<Answer>,
+
+    /// Whether keeping the copyright notice must be the only condition
+    #[arg(long, value_enum, value_name = "ANSWER")]
+    pub attribution_only: Option<Answer>,
+
+    /// Number of licenses to recommend
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub top: usize,
+
+    /// Only show the recommendations, without offering to add one
+    #[arg(long)]
+    pub no_add: bool,
+
+    /// Update the popular licenses and choosealicense.com caches
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+/// What a license should do about one question.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// The license must do it
+    Yes,
+    /// The license must not do it
+    No,
+    /// Either is fine
+    Any,
+}
+
+/// A choosealicense.com question, answered by a license's rules.
+struct Question {
+    prompt: &'static str,
+    /// Whether a license does what the question asks.
+    test: fn(&ChooseALicenseMeta) -> bool,
+    /// Rationale when the license does it, and when it does not.
+    does: &'static str,
+    does_not: &'static str,
+}
+
+const QUESTIONS: [Question; 4] = [
+    Question {
+        prompt: "Should the license explicitly grant patent rights?",
+        test: |meta| has(&meta.permissions, "patent-use"),
+        does: "grants patent rights from contributors",
+        does_not: "grants no patent rights",
+    },
+    Question {
+        prompt: "Should people who distribute changed versions have to share their source code?",
+        test: |meta| has(&meta.conditions, "disclose-source"),
+        does: "requires sharing the source of distributed versions",
+        does_not: "does not require sharing the source",
+    },
+    Question {
+        prompt: "Should letting people use the software over a network count as distributing it?",
+        test: |meta| has(&meta.conditions, "network-use-disclose"),
+        does: "treats network use as distribution",
+        does_not: "does not treat network use as distribution",
+    },
+    Question {
+        prompt: "Should keeping the copyright notice be the only condition?",
+        test: |meta| {
+            meta.conditions
+                .iter()
+                .flatten()
+                .all(|c| c == "include-copyright" || c == "include-copyright--source")
+        },
+        does: "only asks that the copyright notice is kept",
+        does_not: "has conditions beyond keeping the copyright notice",
+    },
+];
+
+/// A popular license with its choosealicense.com rules.
+struct Candidate {
+    meta: ChooseALicenseMeta,
+    /// Indexes of the answered questions the license goes against.
+    violations: Vec<usize>,
+}
+
+impl Runnable for ChooseArgs {
+    fn run(&self) -> Result<()> {
+        let mut cache_manager = CacheManager::new()?;
+        if self.update_cache {
+            cache_manager.clear_cache(GITHUB_LICENSES_CACHE_NAME)?;
+            cache_manager.clear_cache(CHOOSEALICENSE_CACHE_NAME)?;
+        }
+        // A cleared cache is fetched again
+        let popular = ensure_github_api_license_cache(&mut cache_manager, false)?;
+        let metas = load_metas(&cache_manager, &popular)?;
+        if metas.is_empty() {
+            return Err(anyhow!(
+                "Could not load the license rules from choosealicense.com. Check your connection, or try --update-cache."
+            ));
+        }
+
+        let given = [
+            self.patents,
+            self.disclose_source,
+            self.network_use,
+            self.attribution_only,
+        ];
+        let answers: Vec<Answer> = QUESTIONS
+            .iter()
+            .zip(given)
+            .map(|(question, answer)| answer.unwrap_or_else(|| ask(question.prompt)))
+            .collect();
+
+        let mut candidates: Vec<Candidate> = metas
+            .into_iter()
+            .map(|meta| {
+                let violations = QUESTIONS
+                    .iter()
+                    .zip(&answers)
+                    .enumerate()
+                    .filter(|(_, (question, answer))| match answer {
+                        Answer::Yes => !(question.test)(&meta),
+                        Answer::No => (question.test)(&meta),
+                        Answer::Any => false,
+                    })
+                    .map(|(i, _)| i)
+                    .collect();
+                Candidate { meta, violations }
+            })
+            .collect();
+        // Fewest conditions first among equals: the simplest license that does the job
+        candidates.sort_by_key(|c| {
+            (
+                c.violations.len(),
+                !c.meta.featured.unwrap_or(false),
+                c.meta.hidden.unwrap_or(false),
+                c.meta.conditions.as_ref().map_or(0, Vec::len),
+                c.meta.title.clone(),
+            )
+        });
+
+        let closest = candidates[0].violations.len();
+        let shortlist: Vec<&Candidate> = candidates
+            .iter()
+            .take_while(|c| c.violations.len() == closest)
+            .take(self.top.max(1))
+            .collect();
+
+        println!();
+        if closest == 0 {
+            println!("{} Recommended licenses:", "✓".green());
+        } else {
+            println!(
+                "{} No popular license fits every answer; these come closest:",
+                "⚠".yellow()
+            );
+        }
+        for (i, candidate) in shortlist.iter().enumerate() {
+            show_candidate(i + 1, candidate, &answers);
+        }
+
+        if self.no_add {
+            return Ok(());
+        }
+        let Some(id) = pick(&shortlist)? else {
+            println!(
+                "Run `gh-templates license add {}` to add it later.",
+                shortlist[0].meta.spdx_id
+            );
+            return Ok(());
+        };
+        AddArgs {
+            licenses: vec![id],
+            dir: None,
+            force: false,
+            all: false,
+            interactive: false,
+            update_cache: false,
+            params: Vec::new(),
+            output: Vec::new(),
+            no_defaults: false,
+            omit_optional: false,
+            layout: Layout::Suffix,
+            update_manifests: false,
+            no_notice: false,
+        }
+        .run()
+    }
+}
+
+/// The choosealicense.com rules of the popular licenses, downloading the ones that are
+/// not cached yet. Licenses whose rules cannot be fetched are left out.
+fn load_metas(
+    cache_manager: &CacheManager,
+    popular: &Cache<serde_json::Value>,
+) -> Result<Vec<ChooseALicenseMeta>> {
+    let mut cache: Cache<serde_json::Value> =
+        cache_manager.load_cache(CHOOSEALICENSE_CACHE_NAME)?;
+    let mut keys = popular.keys();
+    keys.sort();
+
+    let missing: Vec<&String> = keys
+        .iter()
+        .filter(|key| cache.is_entry_stale(key, CACHE_MAX_AGE_SECONDS))
+        .collect();
+    if !missing.is_empty() {
+        let pb = progress::spinner("Fetching license rules from choosealicense.com...");
+        for key in &missing {
+            if let Some(file) = fetch_choosealicense_meta(key) {
+                cache.insert(key.to_string(), serde_json::to_value(&file.meta)?);
+            }
+        }
+        pb.finish_and_clear();
+        cache_manager.save_cache(CHOOSEALICENSE_CACHE_NAME, &cache)?;
+    }
+
+    Ok(keys
+        .iter()
+        .filter_map(|key| cache.get(key))
+        .filter_map(|data| serde_json::from_value(data.clone()).ok())
+        .collect())
+}
+
+/// Asks a yes/no question where no answer means either is fine.
+fn ask(question: &str) -> Answer {
+    loop {
+        let Some(input) = prompt::read_line(&format!("{} [y/n, Enter to skip] ", question)) else {
+            return Answer::Any;
+        };
+        match input.to_lowercase().as_str() {
+            "y" | "yes" => return Answer::Yes,
+            "n" | "no" => return Answer::No,
+            "" => return Answer::Any,
+            other => println!("{} '{}' is not y or n", "!".red(), other),
+        }
+    }
+}
+
+fn show_candidate(rank: usize, candidate: &Candidate, answers: &[Answer]) {
+    let meta = &candidate.meta;
+    println!();
+    println!(
+        "  {}. {} ({})",
+        rank,
+        meta.title.bold(),
+        meta.spdx_id.cyan()
+    );
+    if let Some(description) = &meta.description {
+        println!("     {}", description.trim().dimmed());
+    }
+    for (i, (question, answer)) in QUESTIONS.iter().zip(answers).enumerate() {
+        if *answer == Answer::Any {
+            continue;
+        }
+        let reason = if (question.test)(meta) {
+            question.does
+        } else {
+            question.does_not
+        };
+        if candidate.violations.contains(&i) {
+            println!("     {} {}", "!".red(), reason);
+        } else {
+            println!("     {} {}", "✓".green(), reason);
+        }
+    }
+    for (label, rules) in [
+        ("Permits", &meta.permissions),
+        ("Requires", &meta.conditions),
+        ("Limits", &meta.limitations),
+    ] {
+        let rules: Vec<&str> = rules.iter().flatten().map(|r| rule_label(r)).collect();
+        if !rules.is_empty() {
+            println!("     {:<9} {}", format!("{}:", label), rules.join(", "));
+        }
+    }
+}
+
+/// Asks which license to add: a number from the shortlist or any license id.
+fn pick(shortlist: &[&Candidate]) -> Result<Option<String>> {
+    println!();
+    let Some(input) = prompt::read_line("Add one now? Enter its number or id (Enter to skip): ")
+    else {
+        return Ok(None);
+    };
+    if input.is_empty() {
+        return Ok(None);
+    }
+    match input.parse::<usize>() {
+        Ok(n) => shortlist
+            .get(n.wrapping_sub(1))
+            .map(|c| Some(c.meta.spdx_id.clone()))
+            .ok_or_else(|| anyhow!("There is no license number {}", n)),
+        Err(_) => Ok(Some(input)),
+    }
+}
+
+/// The wording choosealicense.com uses for a permission, condition or limitation.
+fn rule_label(rule: &str) -> &str {
+    match rule {
+        "commercial-use" => "Commercial use",
+        "modifications" => "Modification",
+        "distribution" => "Distribution",
+        "private-use" => "Private use",
+        "patent-use" => "Patent use",
+        "include-copyright" => "License and copyright notice",
+        "include-copyright--source" => "License and copyright notice for source",
+        "document-changes" => "State changes",
+        "disclose-source" => "Disclose source",
+        "network-use-disclose" => "Network use is distribution",
+        "same-license" => "Same license",
+        "same-license--file" => "Same license (file)",
+        "same-license--library" => "Same license (library)",
+        "trademark-use" => "Trademark use",
+        "liability" => "Liability",
+        "warranty" => "Warranty",
+        other => other,
+    }
+}
+
+fn has(rules: &Option<Vec<String>>, rule: &str) -> bool {
+    rules.iter().flatten().any(|r| r == rule)
+}
@@ -0,0 +1,831 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use colored::*;
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+
+use super::expression::Expression;
+use super::header::{declared_license, project_license, source_files};
+use super::manifest;
+use super::preview::fetch_choosealicense_meta;
+use super::{SPDX_CACHE_NAME, canonical_id, ensure_spdx_license_cache};
+
+#[derive(clap::Args, Debug)]
+pub struct CompatArgs {
+    /// License of the code to include (e.g. mit, "MIT OR Apache-2.0")
+    #[arg(value_name = "LICENSE", required_unless_present = "project")]
+    pub license: Option<String>,
+
+    /// License of the project it goes into
+    #[arg(value_name = "PROJECT_LICENSE", required_unless_present = "project")]
+    pub project_license: Option<String>,
+
+    /// Check against this repository's license; without LICENSE, check the licenses
+    /// declared in its SPDX headers and LICENSES/ directory
+    #[arg(long, conflicts_with = "project_license")]
+    pub project: bool,
+
+    /// Update the license cache
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+/// License families of the compatibility matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Permissive,
+    Apache,
+    Mpl,
+    Epl,
+    Lgpl2,
+    Lgpl3,
+    Gpl2,
+    Gpl3,
+    Agpl3,
+}
+
+impl Family {
+    /// The family an "or later" license may also be used as.
+    fn later(self) -> Option<Family> {
+        match self {
+            Family::Lgpl2 => Some(Family::Lgpl3),
+            Family::Gpl2 => Some(Family::Gpl3),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Family::Permissive => "permissive",
+            Family::Apache => "Apache-2.0",
+            Family::Mpl => "MPL-2.0",
+            Family::Epl => "EPL-2.0",
+            Family::Lgpl2 => "LGPL-2.1",
+            Family::Lgpl3 => "LGPL-3.0",
+            Family::Gpl2 => "GPL-2.0",
+            Family::Gpl3 => "GPL-3.0",
+            Family::Agpl3 => "AGPL-3.0",
+        }
+    }
+
+    /// Whether the family's licenses come in numbered versions with "or later" variants.
+    fn is_versioned(self) -> bool {
+        matches!(
+            self,
+            Family::Lgpl2 | Family::Lgpl3 | Family::Gpl2 | Family::Gpl3 | Family::Agpl3
+        )
+    }
+}
+
+/// How well code under one license fits into a project under another, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Verdict {
+    Compatible,
+    Conditional,
+    Unknown,
+    Incompatible,
+}
+
+use Verdict::{Compatible as Y, Conditional as C, Incompatible as N};
+
+/// Whether code under the row's license can be included in a project under the column's
+/// license, following the FSF and OSI guidance for the common licenses.
+///
+/// Rows and columns: permissive, Apache-2.0, MPL-2.0, EPL-2.0, LGPL-2.1, LGPL-3.0,
+/// GPL-2.0, GPL-3.0, AGPL-3.0.
+const MATRIX: [[Verdict; 9]; 9] = [
+    [Y, Y, Y, Y, Y, Y, Y, Y, Y],
+    [Y, Y, Y, Y, N, Y, N, Y, Y],
+    [C, C, Y, C, Y, Y, Y, Y, Y],
+    [C, C, C, Y, N, N, N, N, N],
+    [C, C, C, C, Y, C, Y, Y, Y],
+    [C, C, C, C, N, Y, N, Y, Y],
+    [N, N, N, N, N, N, Y, N, N],
+    [N, N, N, N, N, N, N, Y, Y],
+    [N, N, N, N, N, N, N, C, Y],
+];
+
+/// A curated license: its family, whether it grants patent rights, and its
+/// choosealicense.com conditions.
+struct Curated {
+    id: &'static str,
+    family: Family,
+    patent_grant: bool,
+    conditions: &'static [&'static str],
+}
+
+const GPL_CONDITIONS: &[&str] = &[
+    "include-copyright",
+    "document-changes",
+    "disclose-source",
+    "same-license",
+];
+const LGPL_CONDITIONS: &[&str] = &[
+    "include-copyright",
+    "disclose-source",
+    "document-changes",
+    "same-license--library",
+];
+
+const CURATED: &[Curated] = &[
+    curated("0BSD", Family::Permissive, false, &[]),
+    curated("MIT", Family::Permissive, false, &["include-copyright"]),
+    curated("MIT-0", Family::Permissive, false, &[]),
+    curated("ISC", Family::Permissive, false, &["include-copyright"]),
+    curated(
+        "BSD-2-Clause",
+        Family::Permissive,
+        false,
+        &["include-copyright"],
+    ),
+    curated(
+        "BSD-3-Clause",
+        Family::Permissive,
+        false,
+        &["include-copyright"],
+    ),
+    curated(
+        "BSL-1.0",
+        Family::Permissive,
+        false,
+        &["include-copyright--source"],
+    ),
+    curated(
+        "Zlib",
+        Family::Permissive,
+        false,
+        &["include-copyright--source", "document-changes"],
+    ),
+    curated("Unlicense", Family::Permissive, false, &[]),
+    curated("CC0-1.0", Family::Permissive, false, &[]),
+    curated(
+        "Apache-2.0",
+        Family::Apache,
+        true,
+        &["include-copyright", "document-changes"],
+    ),
+    curated(
+        "MPL-2.0",
+        Family::Mpl,
+        true,
+        &["disclose-source", "include-copyright", "same-license--file"],
+    ),
+    curated(
+        "EPL-2.0",
+        Family::Epl,
+        true,
+        &["disclose-source", "include-copyright", "same-license"],
+    ),
+    curated("LGPL-2.1-only", Family::Lgpl2, false, LGPL_CONDITIONS),
+    curated("LGPL-2.1-or-later", Family::Lgpl2, false, LGPL_CONDITIONS),
+    curated("LGPL-3.0-only", Family::Lgpl3, true, LGPL_CONDITIONS),
+    curated("LGPL-3.0-or-later", Family::Lgpl3, true, LGPL_CONDITIONS),
+    curated("GPL-2.0-only", Family::Gpl2, false, GPL_CONDITIONS),
+    curated("GPL-2.0-or-later", Family::Gpl2, false, GPL_CONDITIONS),
+    curated("GPL-3.0-only", Family::Gpl3, true, GPL_CONDITIONS),
+    curated("GPL-3.0-or-later", Family::Gpl3, true, GPL_CONDITIONS),
+    curated(
+        "AGPL-3.0-only",
+        Family::Agpl3,
+        true,
+        &[
+            "include-copyright",
+            "document-changes",
+            "disclose-source",
+            "network-use-disclose",
+            "same-license",
+        ],
+    ),
+    curated(
+        "AGPL-3.0-or-later",
+        Family::Agpl3,
+        true,
+        &[
+            "include-copyright",
+            "document-changes",
+            "disclose-source",
+            "network-use-disclose",
+            "same-license",
+        ],
+    ),
+];
+
+const fn curated(
+    id: &'static str,
+    family: Family,
+    patent_grant: bool,
+    conditions: &'static [&'static str],
+) -> Curated {
+    Curated {
+        id,
+        family,
+        patent_grant,
+        conditions,
+    }
+}
+
+/// The curated license for an id. Deprecated ids such as GPL-3.0 mean "only", and
+/// GPL-3.0+ "or later".
+fn find_curated(id: &str, or_later: bool) -> Option<&'static Curated> {
+    let suffix = if or_later { "-or-later" } else { "-only" };
+    CURATED
+        .iter()
+        .find(|c| c.id.eq_ignore_ascii_case(id))
+        .or_else(|| {
+            CURATED
+                .iter()
+                .find(|c| c.id.eq_ignore_ascii_case(&format!("{}{}", id, suffix)))
+        })
+}
+
+/// Spells the ids of an expression as SPDX does, where they are known.
+fn canonicalize(expression: &mut Expression, license_list: &Cache<serde_json::Value>) {
+    match expression {
+        Expression::License { id, or_later, .. } => {
+            if let Some(c) = find_curated(id, *or_later) {
+                *id = c.id.to_string();
+                *or_later = *or_later && !c.id.ends_with("-or-later");
+            } else if let Some(canonical) = canonical_id(license_list, id) {
+                *id = canonical;
+            }
+        }
+        Expression::And(parts) | Expression::Or(parts) => {
+            for part in parts {
+                canonicalize(part, license_list);
+            }
+        }
+    }
+}
+
+/// What is known about one license of an expression.
+struct Profile {
+    id: String,
+    /// `None` for copyleft licenses outside the matrix, or licenses without metadata
+    family: Option<Family>,
+    or_later: bool,
+    patent_grant: bool,
+    conditions: Vec<String>,
+    /// Whether the license is in the curated matrix rather than classified from metadata
+    curated: bool,
+    exception: Option<String>,
+}
+
+impl Profile {
+    /// Looks the license up in the curated list, or else classifies it from its
+    /// choosealicense.com metadata.
+    fn new(id: &str, or_later: bool, exception: Option<String>) -> Self {
+        if let Some(c) = find_curated(id, or_later) {
+            return Profile {
+                id: c.id.to_string(),
+                family: Some(c.family),
+                or_later: or_later || c.id.ends_with("-or-later"),
+                patent_grant: c.patent_grant,
+                conditions: c.conditions.iter().map(|s| s.to_string()).collect(),
+                curated: true,
+                exception,
+            };
+        }
+
+        let meta = fetch_choosealicense_meta(&id.to_lowercase()).map(|m| m.meta);
+        let conditions = meta
+            .as_ref()
+            .and_then(|m| m.conditions.clone())
+            .unwrap_or_default();
+        let copyleft = conditions.iter().any(|c| c.starts_with("same-license"));
+        Profile {
+            id: meta.as_ref().map_or(id.to_string(), |m| m.spdx_id.clone()),
+            // Licenses without a same-license condition are permissive
+            family: (meta.is_some() && !copyleft).then_some(Family::Permissive),
+            or_later,
+            patent_grant: meta
+                .as_ref()
+                .and_then(|m| m.permissions.as_ref())
+                .is_some_and(|p| p.iter().any(|p| p == "patent-use")),
+            conditions,
+            curated: false,
+            exception,
+        }
+    }
+
+    fn has_condition(&self, condition: &str) -> bool {
+        self.conditions.iter().any(|c| c == condition)
+    }
+
+    fn families(&self) -> Vec<Family> {
+        let mut families: Vec<Family> = self.family.into_iter().collect();
+        if self.or_later {
+            families.extend(self.family.and_then(Family::later));
+        }
+        families
+    }
+
+    /// What kind of license it is, in a sentence.
+    fn describe(&self) -> String {
+        let id = &self.id;
+        match self.family {
+            Some(Family::Permissive) if self.curated => format!(
+                "{} is permissive: it only asks that its copyright and license notices be kept.",
+                id
+            ),
+            Some(Family::Permissive) => format!(
+                "{} has no same-license condition on choosealicense.com, so it is treated as permissive.",
+                id
+            ),
+            Some(Family::Apache) => format!(
+                "{} is permissive, and also asks that its NOTICE file be kept and changed files be marked.",
+                id
+            ),
+            Some(Family::Mpl) => format!(
+                "{} is weak, file-level copyleft: its files stay under {}, but may be combined with files under other licenses.",
+                id, id
+            ),
+            Some(Family::Epl) => format!(
+                "{} is weak, module-level copyleft: the covered code and changes to it stay under {}.",
+                id, id
+            ),
+            Some(Family::Lgpl2 | Family::Lgpl3) => format!(
+                "{} is weak, library-level copyleft: the library and changes to it stay under {}, but programs may link to it under their own terms.",
+                id, id
+            ),
+            Some(Family::Gpl2 | Family::Gpl3) => format!(
+                "{} is strong copyleft: a work that includes it must be licensed as a whole under {}.",
+                id, id
+            ),
+            Some(Family::Agpl3) => format!(
+                "{} is strong copyleft with a network clause: a work that includes it must be licensed under {}, and offered in source form to its users over a network.",
+                id, id
+            ),
+            None if self.conditions.is_empty() => format!(
+                "{} is not in the compatibility matrix and has no choosealicense.com metadata.",
+                id
+            ),
+            None => format!(
+                "{} is copyleft ({}) and not in the compatibility matrix.",
+                id,
+                self.conditions.join(", ")
+            ),
+        }
+    }
+}
+
+/// A verdict with the reasons for it.
+struct Assessment {
+    verdict: Verdict,
+    reasons: Vec<String>,
+    /// Conditions to meet when including the code
+    conditions: Vec<String>,
+}
+
+impl super::Runnable for CompatArgs {
+    fn run(&self) -> Result<()> {
+        // The SPDX list only spells the ids outside the matrix, so it is not downloaded
+        // unless asked to
+        let mut cache_manager = CacheManager::new()?;
+        let license_list = if self.update_cache {
+            ensure_spdx_license_cache(&mut cache_manager, true)?
+        } else {
+            cache_manager.load_cache(SPDX_CACHE_NAME)?
+        };
+
+        if !self.project {
+            let (Some(inbound), Some(outbound)) = (&self.license, &self.project_license) else {
+                return Err(anyhow!("Pass two licenses, or use --project"));
+            };
+            let assessment = assess_expressions(inbound, outbound, &license_list)?;
+            if assessment.verdict == Verdict::Incompatible {
+                return Err(anyhow!(
+                    "{} cannot be included in a project under {}",
+                    inbound,
+                    outbound
+                ));
+            }
+            return Ok(());
+        }
+
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let outbound = repository_license(&root)?;
+
+        let inbound: BTreeMap<String, Vec<String>> = match &self.license {
+            Some(license) => BTreeMap::from([(license.clone(), Vec::new())]),
+            None => declared_licenses(&root, &outbound)?,
+        };
+        if inbound.is_empty() {
+            println!(
+                "{} No files declare a license other than {}",
+                "✓".green(),
+                outbound.cyan()
+            );
+            return Ok(());
+        }
+
+        let mut incompatible = Vec::new();
+        for (license, files) in &inbound {
+            if !files.is_empty() {
+                let shown: Vec<&str> = files.iter().take(3).map(String::as_str).collect();
+                let more = files.len().saturating_sub(shown.len());
+                println!(
+                    "{} in {}{}",
+                    license.bold(),
+                    shown.join(", "),
+                    if more > 0 {
+                        format!(" and {} more", more)
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+            let assessment = assess_expressions(license, &outbound, &license_list)?;
+            if assessment.verdict == Verdict::Incompatible {
+                incompatible.push(license.as_str());
+            }
+            println!();
+        }
+
+        if !incompatible.is_empty() {
+            return Err(anyhow!(
+                "{} cannot be included in a project under {}",
+                incompatible.join(", "),
+                outbound
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The license of the repository: the license field of its manifests, or else its LICENSE file.
+fn repository_license(root: &Path) -> Result<String> {
+    let declared = manifest::license_fields(root)
+        .into_iter()
+        .filter_map(|field| field.value.map(|value| (field.manifest, value)))
+        .find(|(_, value)| Expression::parse(&value.replace('/', " OR ")).is_ok());
+    if let Some((manifest, value)) = declared {
+        println!("{} Using {} from {}", "✓".green(), value.cyan(), manifest);
+        return Ok(value.replace('/', " OR "));
+    }
+    project_license(root, None, false)
+}
+
+/// The licenses declared in SPDX headers and the LICENSES/ directory other than `outbound`,
+/// with the files declaring them.
+fn declared_licenses(root: &Path, outbound: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let mut declared: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for path in source_files(root, &[], &[], &[])? {
+        let shown = relative(&path);
+        let license = if shown.starts_with("LICENSES/") {
+            path.file_stem().map(|s| s.to_string_lossy().to_string())
+        } else {
+            declared_license(&path)
+        };
+        if let Some(license) = license {
+            if !license.eq_ignore_ascii_case(outbound) {
+                declared.entry(license).or_default().push(shown);
+            }
+        }
+    }
+    Ok(declared)
+}
+
+/// Parses both expressions, then assesses and prints them; a single license is an
+/// expression too.
+fn assess_expressions(
+    inbound: &str,
+    outbound: &str,
+    license_list: &Cache<serde_json::Value>,
+) -> Result<Assessment> {
+    let mut inbound = Expression::parse(inbound)?;
+    let mut outbound = Expression::parse(outbound)?;
+    canonicalize(&mut inbound, license_list);
+    canonicalize(&mut outbound, license_list);
+    let compound = !matches!(
+        (&inbound, &outbound),
+        (Expression::License { .. }, Expression::License { .. })
+    );
+    let assessment = assess(&inbound, &outbound, compound);
+    print_assessment(&inbound.to_string(), &outbound.to_string(), &assessment);
+    Ok(assessment)
+}
+
+fn assess(inbound: &Expression, outbound: &Expression, compound: bool) -> Assessment {
+    match (inbound, outbound) {
+        (Expression::Or(parts), _) => {
+            let assessments: Vec<Assessment> = parts
+                .iter()
+                .map(|part| assess(part, outbound, compound))
+                .collect();
+            let best = assessments.iter().map(|a| a.verdict).min().unwrap();
+            // Only the conditions of the options that would be chosen apply
+            let mut combined = merge(assessments, best);
+            combined.reasons.push(format!(
+                "{} offers a choice, so the best fitting option counts.",
+                inbound
+            ));
+            combined
+        }
+        (Expression::And(parts), _) => {
+            let assessments: Vec<Assessment> = parts
+                .iter()
+                .map(|part| assess(part, outbound, compound))
+                .collect();
+            let worst = assessments.iter().map(|a| a.verdict).max().unwrap();
+            let mut combined = merge(assessments, worst);
+            combined
+                .reasons
+                .push(format!("{} applies all of its licenses at once.", inbound));
+            combined
+        }
+        (_, Expression::Or(parts)) | (_, Expression::And(parts)) => {
+            let assessments: Vec<Assessment> = parts
+                .iter()
+                .map(|part| assess(inbound, part, compound))
+                .collect();
+            let best = assessments.iter().map(|a| a.verdict).min().unwrap();
+            let worst = assessments.iter().map(|a| a.verdict).max().unwrap();
+            let is_or = matches!(outbound, Expression::Or(_));
+            // A dual-licensed project can drop the options that do not fit
+            let verdict = if is_or && best < worst && best != Verdict::Unknown {
+                best.max(Verdict::Conditional)
+            } else {
+                worst
+            };
+            let mut combined = merge(assessments, verdict);
+            if is_or && verdict == Verdict::Conditional && best < worst {
+                combined.reasons.push(format!(
+                    "The project could no longer offer every option of {}.",
+                    outbound
+                ));
+            }
+            combined
+        }
+        (
+            Expression::License {
+                id: a,
+                or_later: a_later,
+                exception: a_exception,
+            },
+            Expression::License {
+                id: b,
+                or_later: b_later,
+                exception: b_exception,
+            },
+        ) => {
+            let a = Profile::new(a, *a_later, a_exception.clone());
+            let b = Profile::new(b, *b_later, b_exception.clone());
+            let mut assessment = assess_pair(&a, &b);
+            if compound {
+                let prefix = format!("{} → {}: ", a.id, b.id);
+                for reason in &mut assessment.reasons {
+                    reason.insert_str(0, &prefix);
+                }
+            }
+            assessment
+        }
+    }
+}
+
+/// Joins the reasons of several assessments, and the conditions of those with `verdict`.
+fn merge(assessments: Vec<Assessment>, verdict: Verdict) -> Assessment {
+    let mut merged = Assessment {
+        verdict,
+        reasons: Vec::new(),
+        conditions: Vec::new(),
+    };
+    for assessment in assessments {
+        for reason in assessment.reasons {
+            if !merged.reasons.contains(&reason) {
+                merged.reasons.push(reason);
+            }
+        }
+        if assessment.verdict != verdict {
+            continue;
+        }
+        for condition in assessment.conditions {
+            if !merged.conditions.contains(&condition) {
+                merged.conditions.push(condition);
+            }
+        }
+    }
+    merged
+}
+
+/// Assesses code under `a` in a project under `b`.
+fn assess_pair(a: &Profile, b: &Profile) -> Assessment {
+    let mut reasons = vec![a.describe()];
+    let mut network_noted = false;
+
+    // The license the combined work ends up under
+    let mut combined_under = b.id.clone();
+    let verdict = if a.family == Some(Family::Permissive) && b.family.is_none() {
+        reasons.push(format!(
+            "Permissive code can be included in a project under any license, {} included.",
+            b.id
+        ));
+        Verdict::Compatible
+    } else if a.family.is_none() || b.family.is_none() {
+        if b.family.is_none() {
+            reasons.push(b.describe());
+        }
+        reasons.push("Check the terms of both licenses by hand.".to_string());
+        Verdict::Unknown
+    } else {
+        // Try the later versions an "or later" license allows, preferring the named one
+        let mut best: Option<(Verdict, usize, Family, usize, Family)> = None;
+        for (i, from) in a.families().into_iter().enumerate() {
+            for (j, to) in b.families().into_iter().enumerate() {
+                let mut verdict = MATRIX[from as usize][to as usize];
+                if j > 0 && verdict == Verdict::Compatible {
+                    verdict = Verdict::Conditional;
+                }
+                let candidate = (verdict, i + j, from, j, to);
+                if best.is_none_or(|b| (candidate.0, candidate.1) < (b.0, b.1)) {
+                    best = Some(candidate);
+                }
+            }
+        }
+        let (mut verdict, _, from, j, to) = best.unwrap();
+        if a.family != Some(from) {
+            reasons.push(format!(
+                "{} allows any later version, so the code can be used under {}.",
+                a.id,
+                from.name()
+            ));
+        }
+        reasons.push(pair_reason(a, b, from, to));
+        if j > 0 {
+            reasons.push(format!(
+                "{} allows any later version, but the combined work would have to be distributed under {}.",
+                b.id,
+                to.name()
+            ));
+            combined_under = to.name().to_string();
+        } else if from == to && from.is_versioned() && b.or_later && !a.or_later {
+            verdict = verdict.max(Verdict::Conditional);
+            reasons.push(format!(
+                "The combined work could only be distributed under {}, without the \"or later\" option of {}.",
+                a.id, b.id
+            ));
+            combined_under = a.id.clone();
+        }
+        // Section 13 of GPL-3.0 already says so
+        if from == Family::Agpl3 && to == Family::Gpl3 {
+            network_noted = true;
+        }
+        verdict
+    };
+
+    if verdict != Verdict::Incompatible {
+        if a.has_condition("network-use-disclose")
+            && !b.has_condition("network-use-disclose")
+            && !network_noted
+        {
+            reasons.push(format!(
+                "The network clause of {} still applies: users interacting with the software over a network must be offered its source.",
+                a.id
+            ));
+        }
+        if a.patent_grant && !b.patent_grant {
+            reasons.push(format!(
+                "{} grants a patent license that {} does not; it still covers the included code.",
+                a.id, b.id
+            ));
+        }
+    }
+    for exception in [&a.exception, &b.exception].into_iter().flatten() {
+        reasons.push(format!(
+            "The {} exception grants extra permissions that are not evaluated here.",
+            exception
+        ));
+    }
+
+    let conditions = if verdict == Verdict::Incompatible {
+        Vec::new()
+    } else {
+        a.conditions
+            .iter()
+            .filter_map(|c| condition_text(c, &a.id, &combined_under))
+            .collect()
+    };
+    Assessment {
+        verdict,
+        reasons,
+        conditions,
+    }
+}
+
+/// Why a cell of the matrix says what it says.
+fn pair_reason(a: &Profile, b: &Profile, from: Family, to: Family) -> String {
+    use Family::*;
+    let (a, b) = (&a.id, &b.id);
+    match (from, to) {
+        (Permissive, _) => format!(
+            "Permissive code can be included in a project under any license, {} included.",
+            b
+        ),
+        (Apache, Lgpl2 | Gpl2) => "The FSF considers Apache-2.0 incompatible with version 2 of the GNU licenses: its patent termination and indemnity terms are restrictions they do not allow.".to_string(),
+        (Apache, _) => format!(
+            "Apache-2.0 code can be included in a project under {} as long as its license and NOTICE file are kept.",
+            b
+        ),
+        (Mpl, Lgpl2 | Lgpl3 | Gpl2 | Gpl3 | Agpl3) => format!(
+            "MPL-2.0 names the GNU licenses as Secondary Licenses (section 3.3), so its files may be distributed under {} in a larger work, unless they are marked \"Incompatible With Secondary Licenses\".",
+            b
+        ),
+        (Mpl, Permissive | Apache | Epl) => format!(
+            "The combined work may be licensed under {}, but the MPL-2.0 files stay under MPL-2.0 and their source must remain available.",
+            b
+        ),
+        (Epl, Permissive | Apache | Mpl) => format!(
+            "The combined work may be licensed under {}, but the EPL-2.0 code and changes to it stay under EPL-2.0 and their source must be offered.",
+            b
+        ),
+        (Epl, _) if to != Epl => "EPL-2.0 is incompatible with the GNU licenses, unless the Eclipse code names GPL-2.0-or-later as a Secondary License in its notice.".to_string(),
+        (Lgpl2 | Lgpl3, Permissive | Apache | Mpl | Epl) => format!(
+            "A program under {} may use {} code as a library, but not copy it into its own files: the library stays under {}, and users must be able to replace it, e.g. by dynamic linking.",
+            b, a, a
+        ),
+        (Lgpl2, Lgpl3) => "LGPL-2.1 (section 3) allows its code to be relicensed under GPL-2.0 or later, so the combined work would have to be distributed under GPL-3.0.".to_string(),
+        (Lgpl2, Gpl2 | Gpl3 | Agpl3) => format!(
+            "LGPL-2.1 (section 3) allows its code to be relicensed under GPL-2.0 or any later version, so it can be included in a project under {}.",
+            b
+        ),
+        (Lgpl3, Gpl3 | Agpl3) => format!(
+            "LGPL-3.0 is GPL-3.0 with extra permissions, so its code can be included in a project under {}.",
+            b
+        ),
+        (Lgpl3, Lgpl2 | Gpl2) | (Gpl3 | Agpl3, Lgpl2 | Gpl2) => format!(
+            "{} cannot be distributed under version 2 of the GNU licenses, which {} requires.",
+            a, b
+        ),
+        (Gpl2, Lgpl3 | Gpl3 | Agpl3) => format!(
+            "{} requires the whole work under version 2, and {} under version 3; the two cannot both be met.",
+            a, b
+        ),
+        (Gpl3, Agpl3) => "GPL-3.0 (section 13) allows combining its code with AGPL-3.0 code; each part keeps its own license.".to_string(),
+        (Agpl3, Gpl3) => "GPL-3.0 (section 13) allows combining with AGPL-3.0 code, but the network clause of AGPL-3.0 then applies to the combined work.".to_string(),
+        (Gpl2 | Gpl3 | Agpl3, _) if from != to => format!(
+            "The combined work would have to be licensed under {} as a whole, not {}. Relicense the project, or keep the code as a separate program.",
+            a, b
+        ),
+        _ => format!(
+            "{} code fits a project under {}: both use the {} terms.",
+            a,
+            b,
+            from.name()
+        ),
+    }
+}
+
+/// What a choosealicense.com condition asks of whoever includes the `id` code in a work
+/// distributed under `combined_under`.
+fn condition_text(condition: &str, id: &str, combined_under: &str) -> Option<String> {
+    let text = match condition {
+        "include-copyright" => format!("Keep the copyright and license notices of the {} code", id),
+        "include-copyright--source" => format!(
+            "Keep the copyright and license notices of the {} code in source copies",
+            id
+        ),
+        "document-changes" => format!("Mark the changes made to the {} code", id),
+        "disclose-source" => format!("Offer the source of the {} code when distributing it", id),
+        "network-use-disclose" => {
+            "Offer the source to users who interact with the software over a network".to_string()
+        }
+        "same-license" => format!("Distribute the combined work under {}", combined_under),
+        "same-license--file" => format!("Keep the {} files under {}", id, id),
+        "same-license--library" => format!("Keep the library and changes to it under {}", id),
+        _ => return None,
+    };
+    Some(text)
+}
+
+fn print_assessment(inbound: &str, outbound: &str, assessment: &Assessment) {
+    let (mark, verdict) = match assessment.verdict {
+        Verdict::Compatible => ("✓".green(), "Compatible".green()),
+        Verdict::Conditional => ("⚠".yellow(), "Compatible with conditions".yellow()),
+        Verdict::Unknown => ("?".yellow(), "Unknown".yellow()),
+        Verdict::Incompatible => ("!".red(), "Incompatible".red()),
+    };
+    println!(
+        "{} {}: code under {} in a project under {}",
+        mark,
+        verdict.bold(),
+        inbound.cyan(),
+        outbound.cyan()
+    );
+    for reason in &assessment.reasons {
+        println!("  - {}", reason);
+    }
+    if !assessment.conditions.is_empty() {
+        println!("  Conditions:");
+        for condition in &assessment.conditions {
+            println!("  - {}", condition);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::Datelike;
+use toml_edit::DocumentMut;
+
+/// A value guessed for a license placeholder, with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inferred {
+    pub value: String,
+    pub source: String,
+}
+
+/// Values for the common license placeholders, inferred from the project.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDefaults {
+    pub year: Option<Inferred>,
+    pub holder: Option<Inferred>,
+    pub project: Option<Inferred>,
+}
+
+impl ProjectDefaults {
+    /// Looks at git and the manifests in `dir` (Cargo.toml, package.json, pyproject.toml).
+    pub fn detect(dir: &Path) -> Self {
+        let manifest = Manifest::read(dir);
+        let holder = git_user_name(dir)
+            .map(|name| Inferred {
+                value: name,
+                source: "git config user.name".to_string(),
+            })
+            .or_else(|| manifest.author.clone());
+        ProjectDefaults {
+            year: Some(copyright_years(dir)),
+            holder,
+            project: manifest.name,
+        }
+    }
+
+    /// The default for a placeholder such as `year`, `copyright holders` or `project`.
+    pub fn for_placeholder(&self, name: &str) -> Option<&Inferred> {
+        match Field::for_placeholder(name)? {
+            Field::Year => self.year.as_ref(),
+            Field::Holder => self.holder.as_ref(),
+            Field::Project => self.project.as_ref(),
+        }
+    }
+
+    /// Replaces the defaults with the values given for their placeholders, e.g. `year=2020`.
+    pub fn with_params(mut self, params: &HashMap<String, String>) -> Self {
+        for (name, value) in params {
+            let slot = match Field::for_placeholder(name) {
+                Some(Field::Year) => &mut self.year,
+                Some(Field::Holder) => &mut self.holder,
+                Some(Field::Project) => &mut self.project,
+                None => continue,
+            };
+            *slot = Some(Inferred {
+                value: value.clone(),
+                source: "--param".to_string(),
+            });
+        }
+        self
+    }
+}
+
+/// The placeholders that have a project default.
+enum Field {
+    Year,
+    Holder,
+    Project,
+}
+
+impl Field {
+    fn for_placeholder(name: &str) -> Option<Self> {
+        let key = name.trim().to_lowercase().replace([' ', '_'], "-");
+        match key.as_str() {
+            "year" | "yyyy" | "years" => Some(Field::Year),
+            "copyright-holders"
+            | "copyright-holder"
+            | "copyright-owner"
+            | "holder"
+            | "owner"
+            | "fullname"
+            | "full-name"
+            | "name-of-copyright-owner"
+            | "name-of-author"
+            | "author" => Some(Field::Holder),
+            "project" | "project-name" | "program" | "name-of-program" | "software" => {
+                Some(Field::Project)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The git `user.name` configured for `dir`.
+pub fn git_user_name(dir: &Path) -> Option<String> {
+    git_output(dir, &["config", "user.name"])
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !text.is_empty()).then_some(text)
+}
+
+/// The current year, or a range starting at the year of the first commit.
+fn copyright_years(dir: &Path) -> Inferred {
+    let current = chrono::Local::now().year();
+    // A repository may have several roots; the earliest one counts
+    let first = git_output(
+        dir,
+        &["log", "--max-parents=0", "--format=%ad", "--date=format:%Y"],
+    )
+    .and_then(|years| {
+        years
+            .lines()
+            .filter_map(|y| y.trim().parse::<i32>().ok())
+            .min()
+    });
+    match first {
+        Some(first) if first < current => Inferred {
+            value: format!("{}-{}", first, current),
+            source: "first commit to current year".to_string(),
+        },
+        _ => Inferred {
+            value: current.to_string(),
+            source: "current year".to_string(),
+        },
+    }
+}
+
+/// The package name and first author found in the project manifests.
+#[derive(Default)]
+struct Manifest {
+    name: Option<Inferred>,
+    author: Option<Inferred>,
+}
+
+impl Manifest {
+    fn read(dir: &Path) -> Self {
+        let mut manifest = Manifest::default();
+        for found in [cargo(dir), package_json(dir), pyproject(dir)] {
+            manifest.name = manifest.name.or(found.name);
+            manifest.author = manifest.author.or(found.author);
+        }
+        manifest
+    }
+
+    fn new(file: &str, name: Option<String>, author_field: &str, author: Option<String>) -> Self {
+        let inferred = |value: String, field: &str| Inferred {
+            value,
+            source: format!("{} {}", file, field),
+        };
+        Manifest {
+            name: name.filter(|n| !n.is_empty()).map(|n| inferred(n, "name")),
+            author: author
+                .map(|a| strip_email(&a))
+                .filter(|a| !a.is_empty())
+                .map(|a| inferred(a, author_field)),
+        }
+    }
+}
+
+fn read_toml(dir: &Path, name: &str) -> Option<DocumentMut> {
+    fs::read_to_string(dir.join(name)).ok()?.parse().ok()
+}
+
+fn cargo(dir: &Path) -> Manifest {
+    let Some(package) = read_toml(dir, "Cargo.toml").and_then(|doc| doc.get("package").cloned())
+    else {
+        return Manifest::default();
+    };
+    Manifest::new(
+        "Cargo.toml",
+        package
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+        "authors",
+        package
+            .get("authors")
+            .and_then(|authors| authors.as_array())
+            .and_then(|authors| authors.get(0))
+            .and_then(|author| author.as_str())
+            .map(str::to_string),
+    )
+}
+
+fn package_json(dir: &Path) -> Manifest {
+    let Some(json) = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+    else {
+        return Manifest::default();
+    };
+    // `author` is either "Name <email> (url)" or `{ "name": ... }`
+    let author = match &json["author"] {
+        serde_json::Value::String(author) => Some(author.clone()),
+        serde_json::Value::Object(author) => author
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(str::to_string),
+        _ => None,
+    };
+    let name = json["name"].as_str().map(str::to_string);
+    Manifest::new("package.json", name, "author", author)
+}
+
+fn pyproject(dir: &Path) -> Manifest {
+    let Some(project) =
+        read_toml(dir, "pyproject.toml").and_then(|doc| doc.get("project").cloned())
+    else {
+        return Manifest::default();
+    };
+    Manifest::new(
+        "pyproject.toml",
+        project
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+        "authors",
+        project
+            .get("authors")
+            .and_then(|authors| authors.as_array())
+            .and_then(|authors| authors.get(0))
+            .and_then(|author| author.as_inline_table())
+            .and_then(|author| author.get("name"))
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+    )
+}
+
+/// `Jane Doe <jane@example.com> (https://...)` becomes `Jane Doe`.
+fn strip_email(author: &str) -> String {
+    author
+        .split(['<', '('])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,627 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use colored::*;
+use regex::Regex;
+use similar::{Algorithm, DiffOp, capture_diff_slices};
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+use crate::utils::progress;
+use crate::utils::remote::Fetcher;
+
+use super::manifest;
+use super::notice;
+use super::template::{self, Segment};
+use super::{SPDX_TEXT_CACHE_NAME, ensure_spdx_license_cache, license_details};
+
+/// Licenses compared by default, besides those whose text is already cached.
+const COMMON_LICENSES: &[&str] = &[
+    "0BSD",
+    "AGPL-3.0-only",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "CC0-1.0",
+    "EPL-2.0",
+    "GPL-2.0-only",
+    "GPL-3.0-only",
+    "ISC",
+    "LGPL-2.1-only",
+    "LGPL-3.0-only",
+    "MIT",
+    "MIT-0",
+    "MPL-2.0",
+    "Unlicense",
+    "Zlib",
+];
+
+/// Spellings the SPDX matching guidelines treat as the same word.
+const EQUIVALENT_WORDS: &[(&str, &str)] = &[
+    ("acknowledgement", "acknowledgment"),
+    ("analyse", "analyze"),
+    ("artefact", "artifact"),
+    ("authorisation", "authorization"),
+    ("authorised", "authorized"),
+    ("behaviour", "behavior"),
+    ("cancelled", "canceled"),
+    ("catalogue", "catalog"),
+    ("centre", "center"),
+    ("favour", "favor"),
+    ("fulfil", "fulfill"),
+    ("fulfilment", "fulfillment"),
+    ("https", "http"),
+    ("judgement", "judgment"),
+    ("licence", "license"),
+    ("licenced", "licensed"),
+    ("licences", "licenses"),
+    ("licencing", "licensing"),
+    ("licensor's", "licensors"),
+    ("modelling", "modeling"),
+    ("offence", "offense"),
+    ("organisation", "organization"),
+    ("practise", "practice"),
+    ("programme", "program"),
+    ("recognise", "recognize"),
+    ("sublicence", "sublicense"),
+    ("whilst", "while"),
+    ("wilful", "willful"),
+];
+
+/// Confidence, in percent, a file needs to count as a license by default.
+const DEFAULT_THRESHOLD: f64 = 80.0;
+
+/// Words of context shown around a modification.
+const CONTEXT_WORDS: usize = 5;
+
+/// Longest run of missing or added words shown in full.
+const EXCERPT_WORDS: usize = 24;
+
+/// Stand-ins for template markup while the template is normalised.
+const VAR_MARK: char = '\u{E000}';
+const BEGIN_OPTIONAL_MARK: char = '\u{E001}';
+const END_OPTIONAL_MARK: char = '\u{E002}';
+
+#[derive(clap::Args, Debug)]
+pub struct DetectArgs {
+    /// License file, or directory holding LICENSE and COPYING files (default: repository root)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Compare with every SPDX license instead of the common ones
+    #[arg(long)]
+    pub all: bool,
+
+    /// Minimum confidence, in percent, to report a match
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_THRESHOLD)]
+    pub threshold: f64,
+
+    /// Update the license cache
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+impl super::Runnable for DetectArgs {
+    fn run(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => file::find_repo_root().unwrap_or_else(|_| PathBuf::from(".")),
+        };
+        let files = license_files(&path)?;
+
+        let mut cache_manager = CacheManager::new()?;
+        let license_list = ensure_spdx_license_cache(&mut cache_manager, self.update_cache)?;
+
+        let normalizer = Normalizer::new();
+        let references = load_references(&cache_manager, &license_list, self.all, &normalizer)?;
+
+        let mut unmatched = 0;
+        let mut detected: Vec<String> = Vec::new();
+        for license_file in &files {
+            let text = fs::read_to_string(license_file)
+                .map_err(|e| anyhow!("Failed to read '{}': {}", license_file.display(), e))?;
+            let words = normalizer.words(&text);
+
+            let mut comparisons: Vec<Comparison> =
+                references.iter().map(|r| r.compare(&words)).collect();
+            comparisons.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+            let shown = license_file
+                .strip_prefix(&path)
+                .ok()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(license_file);
+            let copyright: Vec<&str> = text
+                .lines()
+                .map(str::trim)
+                .filter(|line| normalizer.is_copyright(line))
+                .collect();
+
+            if !print_result(
+                &shown.display().to_string(),
+                &comparisons,
+                &words,
+                &copyright,
+                self.threshold,
+            ) {
+                unmatched += 1;
+            } else if !detected.contains(&comparisons[0].id) {
+                detected.push(comparisons[0].id.clone());
+            }
+        }
+
+        if path.is_dir() && !detected.is_empty() {
+            manifest::warn_disagreements(
+                &path,
+                &detected,
+                "Fix the license field, or add the license again with --update-manifests.",
+            );
+            if detected.iter().any(|id| id == "Apache-2.0") && notice::find(&path).is_none() {
+                println!(
+                    "{} Apache-2.0 projects should ship a NOTICE file, but there is none",
+                    "⚠".yellow()
+                );
+                println!("  Run `gh-templates license notice init` to create one.");
+            }
+        }
+
+        if unmatched > 0 {
+            return Err(anyhow!(
+                "No SPDX license matched {} of {} file(s)",
+                unmatched,
+                files.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Identifies the licenses of the LICENSE and COPYING files in `dir`, without printing.
+///
+/// Files that match no license are left out.
+pub fn detect_licenses(dir: &Path) -> Result<Vec<String>> {
+    let files = license_files(dir)?;
+
+    let mut cache_manager = CacheManager::new()?;
+    let license_list = ensure_spdx_license_cache(&mut cache_manager, false)?;
+    let normalizer = Normalizer::new();
+    let references = load_references(&cache_manager, &license_list, false, &normalizer)?;
+
+    let mut ids = Vec::new();
+    for license_file in &files {
+        let words = normalizer.words(&fs::read_to_string(license_file)?);
+        let best = references
+            .iter()
+            .map(|r| r.compare(&words))
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence));
+        if let Some(best) = best {
+            if best.confidence * 100.0 >= DEFAULT_THRESHOLD && !ids.contains(&best.id) {
+                ids.push(best.id);
+            }
+        }
+    }
+    Ok(ids)
+}
+
+/// The license file itself, or the LICENSE, LICENCE, COPYING and UNLICENSE files of a directory.
+fn license_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(anyhow!("Path '{}' does not exist.", path.display()));
+    }
+
+    let name_re = Regex::new(r"(?i)^(licen[cs]e|copying|unlicense)([-._].*)?$")?;
+    let mut files: Vec<PathBuf> = fs::read_dir(path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|name| name_re.is_match(&name.to_string_lossy()))
+        })
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err(anyhow!(
+            "No LICENSE or COPYING file found in '{}'.",
+            path.display()
+        ));
+    }
+    Ok(files)
+}
+
+/// Loads the license texts to compare with, downloading the ones not cached yet.
+fn load_references(
+    cache_manager: &CacheManager,
+    license_list: &Cache<serde_json::Value>,
+    all: bool,
+    normalizer: &Normalizer,
+) -> Result<Vec<Reference>> {
+    let mut text_cache: Cache<serde_json::Value> =
+        cache_manager.load_cache(SPDX_TEXT_CACHE_NAME)?;
+
+    let mut ids: Vec<String> = if all {
+        license_list
+            .entries
+            .iter()
+            .filter(|(_, entry)| {
+                !entry
+                    .data
+                    .get("isDeprecatedLicenseId")
+                    .and_then(|d| d.as_bool())
+                    .unwrap_or(false)
+            })
+            .map(|(id, _)| id.clone())
+            .collect()
+    } else {
+        COMMON_LICENSES
+            .iter()
+            .map(|id| id.to_string())
+            .chain(text_cache.keys())
+            .filter(|id| license_list.is_empty() || license_list.contains_key(id))
+            .collect()
+    };
+    ids.sort();
+    ids.dedup();
+
+    let missing = ids.iter().filter(|id| !text_cache.contains_key(id)).count();
+    let fetcher = Fetcher::new();
+    let pb = (missing > 0)
+        .then(|| progress::spinner(&format!("Downloading {} license text(s)...", missing)));
+
+    let mut references = Vec::new();
+    let mut failed = Vec::new();
+    for id in &ids {
+        match license_details(&mut text_cache, id, &fetcher) {
+            Ok(details) => {
+                let name = license_list
+                    .get(id)
+                    .and_then(|entry| entry.get("name"))
+                    .or_else(|| details.get("name"))
+                    .and_then(|n| n.as_str())
+                    .unwrap_or(id)
+                    .to_string();
+                if let Some(reference) = Reference::new(id, &name, details, normalizer) {
+                    references.push(reference);
+                }
+            }
+            Err(_) => failed.push(id.as_str()),
+        }
+    }
+
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+    if missing > failed.len() {
+        cache_manager.save_cache(SPDX_TEXT_CACHE_NAME, &text_cache)?;
+    }
+    if !failed.is_empty() {
+        println!(
+            "{} Could not download {} license text(s): {}",
+            "⚠".yellow(),
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    if references.is_empty() {
+        return Err(anyhow!(
+            "No license texts to compare with. Check your connection or use --update-cache."
+        ));
+    }
+    Ok(references)
+}
+
+/// Reduces license texts to comparable words, following the SPDX matching guidelines:
+/// case, whitespace, punctuation, bullets, comment markers, copyright notices and
+/// varietal spellings are not significant.
+struct Normalizer {
+    copyright_re: Regex,
+    bullet_re: Regex,
+    word_re: Regex,
+}
+
+impl Normalizer {
+    fn new() -> Self {
+        Self {
+            copyright_re: Regex::new(r"(?i)^(copyright\s*(\(c\)|©|\d|\[|<)|©|\(c\)\s*\d)").unwrap(),
+            bullet_re: Regex::new(r"^(?:[-*•]|\(?(?:\d+(?:\.\d+)*|[a-z]|[ivx]+)[.)])\s+").unwrap(),
+            word_re: Regex::new(r"[\p{L}\p{N}]+(?:'[\p{L}]+)?|[\u{E000}-\u{E002}]").unwrap(),
+        }
+    }
+
+    /// Whether a line is a copyright notice, which the comparison skips.
+    fn is_copyright(&self, line: &str) -> bool {
+        let line = line.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '©' && c != '(');
+        self.copyright_re.is_match(line)
+    }
+
+    fn words(&self, text: &str) -> Vec<String> {
+        let mut words = Vec::new();
+
+        for line in text.lines() {
+            // Drop comment markers and other leading punctuation, then list bullets
+            let line = line
+                .trim_start_matches(|c: char| c.is_whitespace() || "/#*;!%-=>|".contains(c))
+                .to_lowercase();
+            if self.is_copyright(&line) {
+                // Keep any template markup on the line so optional parts stay balanced
+                words.extend(
+                    line.chars()
+                        .filter(|c| ('\u{E000}'..='\u{E002}').contains(c))
+                        .map(String::from),
+                );
+                continue;
+            }
+            let line = self.bullet_re.replace(&line, "");
+
+            for word in self.word_re.find_iter(&line) {
+                let word = word.as_str();
+                let word = EQUIVALENT_WORDS
+                    .iter()
+                    .find(|(variant, _)| *variant == word)
+                    .map_or(word, |(_, canonical)| canonical);
+                words.push(word.to_string());
+            }
+        }
+
+        words
+    }
+}
+
+/// A license text in comparable form.
+struct Reference {
+    id: String,
+    name: String,
+    words: Vec<String>,
+    /// Whether each word lies in an optional part of the license
+    optional: Vec<bool>,
+    /// Whether replaceable text may stand before each word; the last entry is after the last word
+    replaceable: Vec<bool>,
+}
+
+impl Reference {
+    /// Builds the reference from the SPDX template if there is one, else from the plain text.
+    fn new(
+        id: &str,
+        name: &str,
+        details: &serde_json::Value,
+        normalizer: &Normalizer,
+    ) -> Option<Self> {
+        let template = details
+            .get("standardLicenseTemplate")
+            .and_then(|t| t.as_str())
+            .filter(|t| !t.trim().is_empty());
+        let segments = match template {
+            Some(template) => template::parse(template),
+            None => vec![Segment::Text(
+                details.get("licenseText")?.as_str()?.to_string(),
+            )],
+        };
+
+        let marked: String = segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.replace(['\u{E000}', '\u{E001}', '\u{E002}'], ""),
+                Segment::Var { .. } => format!(" {} ", VAR_MARK),
+                Segment::BeginOptional => format!(" {} ", BEGIN_OPTIONAL_MARK),
+                Segment::EndOptional => format!(" {} ", END_OPTIONAL_MARK),
+            })
+            .collect();
+
+        let mut reference = Reference {
+            id: id.to_string(),
+            name: name.to_string(),
+            words: Vec::new(),
+            optional: Vec::new(),
+            replaceable: vec![false],
+        };
+        let mut depth = 0usize;
+        for word in normalizer.words(&marked) {
+            match word.chars().next() {
+                Some(VAR_MARK) => *reference.replaceable.last_mut()? = true,
+                Some(BEGIN_OPTIONAL_MARK) => depth += 1,
+                Some(END_OPTIONAL_MARK) => depth = depth.saturating_sub(1),
+                _ => {
+                    reference.words.push(word);
+                    reference.optional.push(depth > 0);
+                    reference.replaceable.push(false);
+                }
+            }
+        }
+
+        (!reference.words.is_empty()).then_some(reference)
+    }
+
+    /// Compares a normalised license file with this license.
+    ///
+    /// The confidence is the share of required license words found in order,
+    /// counting words the file adds outside replaceable text against it.
+    fn compare(&self, words: &[String]) -> Comparison {
+        let required = self.optional.iter().filter(|o| !**o).count();
+        let mut missing = 0;
+        let mut added = 0;
+        let mut changes = Vec::new();
+
+        for op in capture_diff_slices(Algorithm::Myers, &self.words, words) {
+            let (old, old_len, new, new_len) = match op {
+                DiffOp::Equal { .. } => continue,
+                DiffOp::Delete {
+                    old_index,
+                    old_len,
+                    new_index,
+                } => (old_index, old_len, new_index, 0),
+                DiffOp::Insert {
+                    old_index,
+                    new_index,
+                    new_len,
+                } => (old_index, 0, new_index, new_len),
+                DiffOp::Replace {
+                    old_index,
+                    old_len,
+                    new_index,
+                    new_len,
+                } => (old_index, old_len, new_index, new_len),
+            };
+
+            let dropped: Vec<String> = (old..old + old_len)
+                .filter(|&i| !self.optional[i])
+                .map(|i| self.words[i].clone())
+                .collect();
+            let replaceable = self.replaceable[old..=old + old_len].iter().any(|r| *r);
+            let extra = if replaceable { 0 } else { new_len };
+
+            missing += dropped.len();
+            added += extra;
+            if !dropped.is_empty() || extra > 0 {
+                changes.push(Change {
+                    at: new,
+                    len: new_len,
+                    missing: dropped,
+                    added: words[new..new + extra].to_vec(),
+                });
+            }
+        }
+
+        let confidence = if required + added == 0 {
+            0.0
+        } else {
+            (required - missing) as f64 / (required + added) as f64
+        };
+
+        Comparison {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            confidence,
+            changes,
+        }
+    }
+}
+
+/// How well a license file matches one license.
+struct Comparison {
+    id: String,
+    name: String,
+    /// Between 0 and 1
+    confidence: f64,
+    changes: Vec<Change>,
+}
+
+/// A place where a license file departs from the license.
+struct Change {
+    /// Index of the first file word at or after the change
+    at: usize,
+    /// Number of file words the change covers
+    len: usize,
+    /// License words the file leaves out
+    missing: Vec<String>,
+    /// Words the file adds
+    added: Vec<String>,
+}
+
+impl Change {
+    /// Renders the change with some words around it, in `git diff --word-diff` style.
+    fn describe(&self, words: &[String]) -> String {
+        let before = &words[self.at.saturating_sub(CONTEXT_WORDS)..self.at];
+        let after_start = self.at + self.len;
+        let after = &words[after_start..(after_start + CONTEXT_WORDS).min(words.len())];
+
+        let mut parts = Vec::new();
+        if !before.is_empty() {
+            parts.push(format!("…{}", before.join(" ")));
+        }
+        if !self.missing.is_empty() {
+            parts.push(format!("[-{}-]", excerpt(&self.missing)).red().to_string());
+        }
+        if !self.added.is_empty() {
+            parts.push(
+                format!("{{+{}+}}", excerpt(&self.added))
+                    .green()
+                    .to_string(),
+            );
+        }
+        if !after.is_empty() {
+            parts.push(format!("{}…", after.join(" ")));
+        }
+        parts.join(" ")
+    }
+}
+
+fn excerpt(words: &[String]) -> String {
+    if words.len() <= EXCERPT_WORDS {
+        return words.join(" ");
+    }
+    format!(
+        "{} … ({} more words)",
+        words[..EXCERPT_WORDS].join(" "),
+        words.len() - EXCERPT_WORDS
+    )
+}
+
+/// Prints the best match of a file, returning whether it reached the threshold.
+fn print_result(
+    shown: &str,
+    comparisons: &[Comparison],
+    words: &[String],
+    copyright: &[&str],
+    threshold: f64,
+) -> bool {
+    let Some(best) = comparisons.first() else {
+        return false;
+    };
+    let percent = best.confidence * 100.0;
+
+    if words.is_empty() || percent < threshold {
+        println!(
+            "{} {}: no SPDX license matched (closest: {} {:.1}%)",
+            "!".red(),
+            shown.bold(),
+            best.id,
+            percent
+        );
+        return false;
+    }
+
+    let mark = if best.changes.is_empty() {
+        "✓".green()
+    } else {
+        "⚠".yellow()
+    };
+    println!(
+        "{} {}: {} ({}), {:.1}% confidence",
+        mark,
+        shown.bold(),
+        best.id.cyan(),
+        best.name,
+        percent
+    );
+    for line in copyright {
+        println!("  {}", line.dimmed());
+    }
+
+    if best.changes.is_empty() {
+        println!("  Matches the standard text.");
+    } else {
+        println!("  {} non-standard modification(s):", best.changes.len());
+        for change in &best.changes {
+            println!("    {}", change.describe(words));
+        }
+    }
+
+    let runners_up: Vec<String> = comparisons[1..]
+        .iter()
+        .take(2)
+        .filter(|c| c.confidence > 0.5)
+        .map(|c| format!("{} {:.1}%", c.id, c.confidence * 100.0))
+        .collect();
+    if !runners_up.is_empty() {
+        println!("  Next closest: {}", runners_up.join(", "));
+    }
+
+    true
+}
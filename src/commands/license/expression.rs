@@ -0,0 +1,290 @@
+use std::fmt;
+
+use anyhow::{Result, anyhow};
+
+use crate::utils::cache::Cache;
+
+use super::{canonical_exception_id, canonical_license_id};
+
+/// An SPDX license expression, e.g. `MIT OR Apache-2.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    /// A license id, with `+` for "or later" and an optional `WITH` exception
+    License {
+        id: String,
+        or_later: bool,
+        exception: Option<String>,
+    },
+    And(Vec<Expression>),
+    Or(Vec<Expression>),
+}
+
+/// One license of an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub id: String,
+    pub exception: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Id(String),
+}
+
+impl Expression {
+    /// Whether `input` uses operators or parentheses rather than naming a single license.
+    pub fn is_compound(input: &str) -> bool {
+        input.contains(['(', ')'])
+            || input
+                .split_whitespace()
+                .any(|word| matches!(word, "AND" | "OR" | "WITH" | "and" | "or" | "with"))
+    }
+
+    /// Parses an expression. `WITH` binds tighter than `AND`, which binds tighter than `OR`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let expression = parser.or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(expression),
+            Some(token) => Err(anyhow!(
+                "Invalid license expression '{}': unexpected {}",
+                input.trim(),
+                describe(token)
+            )),
+        }
+    }
+
+    /// Spells every license and exception id as the SPDX lists do, failing on ids
+    /// that are not on them.
+    pub fn canonicalize(
+        &mut self,
+        license_list: &Cache<serde_json::Value>,
+        exception_list: &Cache<serde_json::Value>,
+    ) -> Result<()> {
+        match self {
+            Expression::License { id, exception, .. } => {
+                *id = canonical_license_id(license_list, id)?;
+                if let Some(exception) = exception {
+                    *exception = canonical_exception_id(exception_list, exception)?;
+                }
+            }
+            Expression::And(parts) | Expression::Or(parts) => {
+                for part in parts {
+                    part.canonicalize(license_list, exception_list)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The licenses of the expression in order of appearance, without repeats.
+    pub fn terms(&self) -> Vec<Term> {
+        let mut terms = Vec::new();
+        self.collect_terms(&mut terms);
+        terms
+    }
+
+    fn collect_terms(&self, terms: &mut Vec<Term>) {
+        match self {
+            Expression::License { id, exception, .. } => {
+                let term = Term {
+                    id: id.clone(),
+                    exception: exception.clone(),
+                };
+                if !terms.contains(&term) {
+                    terms.push(term);
+                }
+            }
+            Expression::And(parts) | Expression::Or(parts) => {
+                for part in parts {
+                    part.collect_terms(terms);
+                }
+            }
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, parent_is_and: bool) -> fmt::Result {
+        match self {
+            Expression::Or(_) if parent_is_and => write!(f, "({})", self),
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::License {
+                id,
+                or_later,
+                exception,
+            } => {
+                write!(f, "{}{}", id, if *or_later { "+" } else { "" })?;
+                if let Some(exception) = exception {
+                    write!(f, " WITH {}", exception)?;
+                }
+                Ok(())
+            }
+            Expression::And(parts) | Expression::Or(parts) => {
+                let is_and = matches!(self, Expression::And(_));
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(if is_and { " AND " } else { " OR " })?;
+                    }
+                    part.fmt_operand(f, is_and)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let spaced = input.replace('(', " ( ").replace(')', " ) ");
+    for word in spaced.split_whitespace() {
+        let token = match word {
+            "(" => Token::Open,
+            ")" => Token::Close,
+            "AND" | "and" => Token::And,
+            "OR" | "or" => Token::Or,
+            "WITH" | "with" => Token::With,
+            id if id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':')) =>
+            {
+                Token::Id(id.to_string())
+            }
+            other => {
+                return Err(anyhow!(
+                    "Invalid license expression '{}': '{}' is not a license id",
+                    input.trim(),
+                    other
+                ));
+            }
+        };
+        tokens.push(token);
+    }
+    if tokens.is_empty() {
+        return Err(anyhow!("The license expression is empty"));
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+        Token::And => "AND".to_string(),
+        Token::Or => "OR".to_string(),
+        Token::With => "WITH".to_string(),
+        Token::Id(id) => format!("'{}'", id),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn or(&mut self) -> Result<Expression> {
+        let mut parts = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            parts.push(self.and()?);
+        }
+        Ok(flatten(parts, false))
+    }
+
+    fn and(&mut self) -> Result<Expression> {
+        let mut parts = vec![self.term()?];
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            parts.push(self.term()?);
+        }
+        Ok(flatten(parts, true))
+    }
+
+    fn term(&mut self) -> Result<Expression> {
+        match self.next().cloned() {
+            Some(Token::Open) => {
+                let inner = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err(anyhow!("Invalid license expression: missing ')'")),
+                }
+            }
+            Some(Token::Id(id)) => {
+                let (id, or_later) = match id.strip_suffix('+') {
+                    Some(base) => (base.to_string(), true),
+                    None => (id, false),
+                };
+                let exception = if self.peek() == Some(&Token::With) {
+                    self.position += 1;
+                    match self.next() {
+                        Some(Token::Id(exception)) => Some(exception.clone()),
+                        _ => {
+                            return Err(anyhow!(
+                                "Invalid license expression: WITH must be followed by an exception id"
+                            ));
+                        }
+                    }
+                } else {
+                    None
+                };
+                Ok(Expression::License {
+                    id,
+                    or_later,
+                    exception,
+                })
+            }
+            Some(token) => Err(anyhow!(
+                "Invalid license expression: expected a license id, found {}",
+                describe(&token)
+            )),
+            None => Err(anyhow!(
+                "Invalid license expression: expected a license id at the end"
+            )),
+        }
+    }
+}
+
+/// Joins the operands of one operator, merging nested uses of the same operator.
+fn flatten(parts: Vec<Expression>, is_and: bool) -> Expression {
+    if parts.len() == 1 {
+        return parts.into_iter().next().unwrap();
+    }
+    let mut flat = Vec::new();
+    for part in parts {
+        match part {
+            Expression::And(inner) if is_and => flat.extend(inner),
+            Expression::Or(inner) if !is_and => flat.extend(inner),
+            other => flat.push(other),
+        }
+    }
+    if is_and {
+        Expression::And(flat)
+    } else {
+        Expression::Or(flat)
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Datelike;
+use colored::*;
+
+use crate::utils::file;
+
+use super::super::defaults::git_user_name;
+use super::style;
+use super::{ExistingHeader, Skip, is_binary, project_license, source_files};
+
+#[derive(clap::Args, Debug)]
+pub struct HeaderAddArgs {
+    /// Files or directories to add headers to (default: the source code of the whole repository)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// SPDX license identifier or expression, e.g. "MIT OR Apache-2.0" (default: detected from the LICENSE file)
+    #[arg(long, short = 'l', value_name = "SPDX")]
+    pub license: Option<String>,
+
+    /// Copyright holder (default: git user.name)
+    #[arg(long, value_name = "NAME")]
+    pub holder: Option<String>,
+
+    /// Copyright year (default: the current year)
+    #[arg(long, value_name = "YEAR")]
+    pub year: Option<String>,
+
+    /// Only add headers to files matching this glob, including configuration and
+    /// documentation files (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files matching this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Comment style for an extension or file name, e.g. tpl=hash (repeatable)
+    #[arg(long = "comment-style", value_name = "EXT=STYLE")]
+    pub comment_styles: Vec<String>,
+
+    /// Show which files would get a header without changing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Update the license cache
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+impl super::Runnable for HeaderAddArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let overrides = style::parse_overrides(&self.comment_styles)?;
+
+        let license = project_license(&root, self.license.as_deref(), self.update_cache)?;
+        let holder = match &self.holder {
+            Some(holder) => holder.trim().to_string(),
+            None => git_user_name(&root).ok_or_else(|| {
+                anyhow!("No copyright holder: pass --holder or set git user.name")
+            })?,
+        };
+        let year = self
+            .year
+            .clone()
+            .unwrap_or_else(|| chrono::Local::now().year().to_string());
+
+        let header = vec![
+            format!("SPDX-FileCopyrightText: {} {}", year, holder),
+            format!("SPDX-License-Identifier: {}", license),
+        ];
+
+        let mut added = Vec::new();
+        let mut present = 0;
+        let mut other_license = Vec::new();
+        let mut unknown: BTreeMap<String, usize> = BTreeMap::new();
+        let mut not_source = 0;
+
+        for path in source_files(&root, &self.paths, &self.include, &self.exclude)? {
+            let shown = path
+                .strip_prefix(&root)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            // Files named on the command line or matched by --include were asked for
+            let any_file = !self.include.is_empty() || self.paths.contains(&path);
+            let comment_style = match style::style_for(&path, &overrides, any_file) {
+                Ok(comment_style) => comment_style,
+                Err(Skip::Unknown) => {
+                    *unknown.entry(file_kind(&path)).or_default() += 1;
+                    continue;
+                }
+                Err(Skip::NotSource) => {
+                    not_source += 1;
+                    continue;
+                }
+            };
+
+            let bytes = fs::read(&path)?;
+            if is_binary(&bytes) {
+                continue;
+            }
+            let Ok(content) = String::from_utf8(bytes) else {
+                continue;
+            };
+
+            match ExistingHeader::read(&content, comment_style) {
+                Some(existing) => match existing.license {
+                    Some(_) if existing.declares(&license) => present += 1,
+                    Some(id) => other_license.push((shown, id)),
+                    None => present += 1,
+                },
+                None => {
+                    if !self.dry_run {
+                        let updated = style::insert_header(&content, comment_style, &header);
+                        fs::write(&path, updated)
+                            .map_err(|e| anyhow!("Failed to write '{}': {}", shown, e))?;
+                    }
+                    added.push(shown);
+                }
+            }
+        }
+
+        print_summary(
+            &added,
+            present,
+            &other_license,
+            &unknown,
+            not_source,
+            &header,
+            self.dry_run,
+        );
+        Ok(())
+    }
+}
+
+/// The extension (`.json`) or, without one, the name of a file.
+fn file_kind(path: &Path) -> String {
+    match path.extension() {
+        Some(ext) => format!(".{}", ext.to_string_lossy()),
+        None => path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn print_summary(
+    added: &[String],
+    present: usize,
+    other_license: &[(String, String)],
+    unknown: &BTreeMap<String, usize>,
+    not_source: usize,
+    header: &[String],
+    dry_run: bool,
+) {
+    if added.is_empty() {
+        println!("{} No files need a header", "✓".green());
+    } else {
+        let verb = if dry_run { "Would add" } else { "Added" };
+        println!(
+            "{} {} headers to {} file(s):",
+            "✓".green(),
+            verb,
+            added.len()
+        );
+        for line in header {
+            println!("    {}", line.dimmed());
+        }
+        for path in added {
+            println!("  {} {}", ">".green(), path);
+        }
+    }
+
+    if present > 0 {
+        println!("  {} file(s) already have a header", present);
+    }
+
+    if !other_license.is_empty() {
+        println!(
+            "{} {} file(s) declare another license, left unchanged:",
+            "⚠".yellow(),
+            other_license.len()
+        );
+        for (path, license) in other_license {
+            println!("  {} {} ({})", "!".yellow(), path, license);
+        }
+    }
+
+    if !unknown.is_empty() {
+        let kinds: Vec<String> = unknown
+            .iter()
+            .map(|(kind, count)| format!("{} ({})", kind, count))
+            .collect();
+        println!(
+            "  Skipped files with no known comment syntax: {}",
+            kinds.join(", ")
+        );
+        println!("  Use --comment-style EXT=STYLE to add headers to them.");
+    }
+
+    if not_source > 0 {
+        println!(
+            "  Skipped {} configuration or documentation file(s). Use --include GLOB to add headers to them.",
+            not_source
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Datelike;
+use clap::ValueEnum;
+use colored::*;
+use serde_json::json;
+
+use crate::commands::base::CheckFailed;
+use crate::utils::file;
+
+use super::style;
+use super::{ExistingHeader, Skip, is_binary, project_license, source_files};
+
+/// Exit code bits, added together when several kinds of problem are found.
+const EXIT_MISSING: i32 = 4;
+const EXIT_MISMATCHED: i32 = 8;
+const EXIT_OUTDATED: i32 = 16;
+const EXIT_NO_COPYRIGHT: i32 = 32;
+
+/// Which year a copyright notice must reach.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YearPolicy {
+    /// The year the file was last changed in git; uncommitted changes count as this year
+    Modified,
+    /// The current year
+    Current,
+    /// Do not check years
+    Ignore,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Plain JSON listing every finding
+    Json,
+    /// SARIF 2.1.0, for code scanning tools
+    Sarif,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct HeaderCheckArgs {
+    /// Files or directories to check (default: the source code of the whole repository)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// SPDX license identifier or expression every header must name (default: detected from the LICENSE file)
+    #[arg(long, short = 'l', value_name = "SPDX")]
+    pub license: Option<String>,
+
+    /// Only check files matching this glob, including configuration and documentation
+    /// files (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files matching this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Which year the copyright notice must reach
+    #[arg(long, value_enum, default_value = "modified")]
+    pub year_policy: YearPolicy,
+
+    /// Comment style for an extension or file name, e.g. tpl=hash (repeatable)
+    #[arg(long = "comment-style", value_name = "EXT=STYLE")]
+    pub comment_styles: Vec<String>,
+
+    /// Write the findings to this file
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+
+    /// Format of the report file
+    #[arg(long, value_enum, default_value = "json", requires = "report")]
+    pub report_format: ReportFormat,
+
+    /// Update the license cache
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+/// What is wrong with a file's header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Kind {
+    Missing,
+    Mismatched,
+    Outdated,
+    NoCopyright,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Missing => "missing",
+            Kind::Mismatched => "mismatched",
+            Kind::Outdated => "outdated",
+            Kind::NoCopyright => "no-copyright",
+        }
+    }
+
+    fn rule_id(&self) -> &'static str {
+        match self {
+            Kind::Missing => "missing-header",
+            Kind::Mismatched => "mismatched-license",
+            Kind::Outdated => "outdated-year",
+            Kind::NoCopyright => "missing-copyright",
+        }
+    }
+
+    fn exit_bit(&self) -> i32 {
+        match self {
+            Kind::Missing => EXIT_MISSING,
+            Kind::Mismatched => EXIT_MISMATCHED,
+            Kind::Outdated => EXIT_OUTDATED,
+            Kind::NoCopyright => EXIT_NO_COPYRIGHT,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Finding {
+    path: String,
+    /// 1-based line, or 1 when the header is missing
+    line: usize,
+    kind: Kind,
+    message: String,
+}
+
+impl super::Runnable for HeaderCheckArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let overrides = style::parse_overrides(&self.comment_styles)?;
+        let license = project_license(&root, self.license.as_deref(), self.update_cache)?;
+
+        let files = source_files(&root, &self.paths, &self.include, &self.exclude)?;
+        let current_year = chrono::Local::now().year();
+        let modified_years = match self.year_policy {
+            YearPolicy::Modified => last_modified_years(&root, current_year),
+            _ => HashMap::new(),
+        };
+
+        let mut findings = Vec::new();
+        let mut checked = 0;
+        let mut unknown = 0;
+
+        for path in files {
+            let shown = relative(&root, &path);
+            // Files named on the command line or matched by --include were asked for
+            let any_file = !self.include.is_empty() || self.paths.contains(&path);
+            let comment_style = match style::style_for(&path, &overrides, any_file) {
+                Ok(comment_style) => comment_style,
+                Err(Skip::Unknown) => {
+                    unknown += 1;
+                    continue;
+                }
+                Err(Skip::NotSource) => continue,
+            };
+            let bytes = fs::read(&path)?;
+            if is_binary(&bytes) {
+                continue;
+            }
+            let Ok(content) = String::from_utf8(bytes) else {
+                continue;
+            };
+            checked += 1;
+
+            let Some(header) = ExistingHeader::read(&content, comment_style) else {
+                findings.push(Finding {
+                    path: shown,
+                    line: 1,
+                    kind: Kind::Missing,
+                    message: "No SPDX-License-Identifier header".to_string(),
+                });
+                continue;
+            };
+
+            if !header.declares(&license) {
+                let declared = header.license.clone().unwrap_or_default();
+                findings.push(Finding {
+                    path: shown.clone(),
+                    line: header.line,
+                    kind: Kind::Mismatched,
+                    message: format!("Declares {}, expected {}", declared, license),
+                });
+            }
+
+            let expected = match self.year_policy {
+                YearPolicy::Ignore => continue,
+                YearPolicy::Current => current_year,
+                YearPolicy::Modified => *modified_years.get(&shown).unwrap_or(&current_year),
+            };
+            match header.latest_year() {
+                None => findings.push(Finding {
+                    path: shown,
+                    line: header.line,
+                    kind: Kind::NoCopyright,
+                    message: "No copyright notice with a year".to_string(),
+                }),
+                Some((line, year)) if year < expected => findings.push(Finding {
+                    path: shown,
+                    line,
+                    kind: Kind::Outdated,
+                    message: format!("Copyright year {} is older than {}", year, expected),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        findings.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        print_findings(&findings, checked, unknown, &license);
+
+        if let Some(report) = &self.report {
+            let content = match self.report_format {
+                ReportFormat::Json => json_report(&findings, checked, &license, self.year_policy),
+                ReportFormat::Sarif => sarif_report(&findings),
+            };
+            fs::write(report, serde_json::to_string_pretty(&content)? + "\n")
+                .map_err(|e| anyhow!("Failed to write report '{}': {}", report.display(), e))?;
+            println!("Report written to {}", report.display());
+        }
+
+        let kinds: HashSet<Kind> = findings.iter().map(|f| f.kind).collect();
+        let code: i32 = kinds.iter().map(Kind::exit_bit).sum();
+        if code != 0 {
+            return Err(CheckFailed(code).into());
+        }
+        Ok(())
+    }
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// The year each file was last committed, or the current year for files with
+/// uncommitted changes. Files git does not know are left out.
+fn last_modified_years(root: &Path, current_year: i32) -> HashMap<String, i32> {
+    let mut years = HashMap::new();
+
+    let log = std::process::Command::new("git")
+        .args([
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--format=%x01%ad",
+            "--date=format:%Y",
+            "--name-only",
+            "--no-renames",
+        ])
+        .current_dir(root)
+        .output();
+    if let Some(output) = log.ok().filter(|o| o.status.success()) {
+        let mut year = current_year;
+        // Newest commits come first, so the first year seen for a file is the latest
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some(date) = line.strip_prefix('\u{1}') {
+                year = date.trim().parse().unwrap_or(current_year);
+            } else if !line.is_empty() {
+                years.entry(line.to_string()).or_insert(year);
+            }
+        }
+    }
+
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain", "-z", "--untracked-files=all"])
+        .current_dir(root)
+        .output();
+    if let Some(output) = status.ok().filter(|o| o.status.success()) {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut entries = stdout.split('\0');
+        while let Some(entry) = entries.next() {
+            if entry.len() < 4 {
+                continue;
+            }
+            let (state, path) = entry.split_at(3);
+            years.insert(path.to_string(), current_year);
+            // Renames and copies are followed by the original path
+            if state.starts_with(['R', 'C']) {
+                entries.next();
+            }
+        }
+    }
+
+    years
+}
+
+fn print_findings(findings: &[Finding], checked: usize, unknown: usize, license: &str) {
+    if findings.is_empty() {
+        println!(
+            "{} All {} file(s) have an up-to-date {} header",
+            "✓".green(),
+            checked,
+            license
+        );
+    } else {
+        for finding in findings {
+            let mark = match finding.kind {
+                Kind::Outdated => "⚠".yellow(),
+                _ => "!".red(),
+            };
+            println!(
+                "{} {}:{}: {}",
+                mark, finding.path, finding.line, finding.message
+            );
+        }
+
+        let mut counts: BTreeMap<Kind, usize> = BTreeMap::new();
+        for finding in findings {
+            *counts.entry(finding.kind).or_default() += 1;
+        }
+        let counts: Vec<String> = counts
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind.name()))
+            .collect();
+        println!("Checked {} file(s): {}", checked, counts.join(", "));
+    }
+
+    if unknown > 0 {
+        println!(
+            "  Skipped {} file(s) with no known comment syntax. Use --comment-style EXT=STYLE to check them.",
+            unknown
+        );
+    }
+}
+
+fn json_report(
+    findings: &[Finding],
+    checked: usize,
+    license: &str,
+    year_policy: YearPolicy,
+) -> serde_json::Value {
+    let policy = year_policy
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default();
+    json!({
+        "license": license,
+        "year_policy": policy,
+        "checked": checked,
+        "findings": findings
+            .iter()
+            .map(|f| json!({
+                "path": f.path,
+                "line": f.line,
+                "kind": f.kind.name(),
+                "message": f.message,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn sarif_report(findings: &[Finding]) -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = [
+        Kind::Missing,
+        Kind::Mismatched,
+        Kind::Outdated,
+        Kind::NoCopyright,
+    ]
+    .iter()
+    .map(|kind| {
+        let description = match kind {
+            Kind::Missing => "File has no SPDX license header",
+            Kind::Mismatched => "SPDX license header names another license",
+            Kind::Outdated => "Copyright year is out of date",
+            Kind::NoCopyright => "License header has no copyright notice with a year",
+        };
+        json!({ "id": kind.rule_id(), "shortDescription": { "text": description } })
+    })
+    .collect();
+
+    let results: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|f| {
+            json!({
+                "ruleId": f.kind.rule_id(),
+                "level": if f.kind == Kind::Outdated { "warning" } else { "error" },
+                "message": { "text": f.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": f.path },
+                        "region": { "startLine": f.line },
+                    }
+                }],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gh-templates",
+                    "informationUri": "https://github.com/RafaelJohn9/gitforge",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
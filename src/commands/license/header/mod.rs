@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use colored::*;
+use regex::Regex;
+
+use crate::commands::base::Runnable;
+use crate::commands::gitignore::matcher::Matcher;
+use crate::commands::gitignore::rules::{self, Line, Rule};
+use crate::utils::cache::{Cache, CacheManager};
+
+use super::detect::detect_licenses;
+use super::expression::Expression;
+use super::{ensure_spdx_exception_cache, ensure_spdx_license_cache};
+
+use style::split_prologue;
+pub use style::{CommentStyle, Skip, parse_overrides, style_for};
+
+mod add;
+mod check;
+mod style;
+
+/// How many lines below the prologue are searched for an existing header.
+const HEADER_SCAN_LINES: usize = 20;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Add SPDX license and copyright headers to source files
+    Add(add::HeaderAddArgs),
+    /// Check that source files have up-to-date SPDX headers, for CI
+    Check(check::HeaderCheckArgs),
+}
+
+impl Command {
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match self {
+            Command::Add(args) => args.run(),
+            Command::Check(args) => args.run(),
+        }
+    }
+}
+
+/// The SPDX tags found at the top of a file.
+#[derive(Debug, Clone, Default)]
+pub struct ExistingHeader {
+    /// Value of `SPDX-License-Identifier`
+    pub license: Option<String>,
+    /// 1-based line of the `SPDX-License-Identifier` tag
+    pub line: usize,
+    /// Values of `SPDX-FileCopyrightText` and plain `Copyright` lines, with their line
+    pub copyright: Vec<(usize, String)>,
+}
+
+impl ExistingHeader {
+    /// Reads the SPDX tags near the top of `content`, if there are any.
+    pub fn read(content: &str, style: CommentStyle) -> Option<Self> {
+        let license_re = Regex::new(r"SPDX-License-Identifier:\s*(.*?)\s*(\*/|-->)?\s*$").unwrap();
+        let copyright_re =
+            Regex::new(r"(?i)(?:SPDX-FileCopyrightText:|\bcopyright\b)\s*(.*?)\s*(\*/|-->)?\s*$")
+                .unwrap();
+
+        let (prologue, body) = split_prologue(content, style);
+        let first_line = prologue.lines().count() + 1;
+        let mut header = ExistingHeader::default();
+        for (i, line) in body.lines().take(HEADER_SCAN_LINES).enumerate() {
+            if let Some(caps) = license_re.captures(line) {
+                if header.license.is_none() {
+                    header.license = Some(caps[1].to_string());
+                    header.line = first_line + i;
+                }
+            } else if let Some(caps) = copyright_re.captures(line) {
+                if caps[1].chars().any(|c| c.is_ascii_digit()) {
+                    header.copyright.push((first_line + i, caps[1].to_string()));
+                }
+            }
+        }
+
+        header.license.is_some().then_some(header)
+    }
+
+    /// Whether the header names the same license expression as `license`, however
+    /// it is spaced, cased or parenthesised.
+    pub fn declares(&self, license: &str) -> bool {
+        let normalize = |expression: &str| match Expression::parse(expression) {
+            Ok(parsed) => parsed.to_string().to_lowercase(),
+            Err(_) => expression.trim().to_lowercase(),
+        };
+        self.license
+            .as_deref()
+            .is_some_and(|declared| normalize(declared) == normalize(license))
+    }
+
+    /// The latest year of the copyright notices, with its line.
+    pub fn latest_year(&self) -> Option<(usize, i32)> {
+        let year_re = Regex::new(r"\b(19|20)\d{2}\b").unwrap();
+        self.copyright
+            .iter()
+            .flat_map(|(line, text)| {
+                year_re
+                    .find_iter(text)
+                    .filter_map(move |m| m.as_str().parse().ok().map(|year| (*line, year)))
+            })
+            .max_by_key(|(_, year)| *year)
+    }
+}
+
+/// The license expression given on the command line, spelled as the SPDX lists
+/// do, or else the license of the LICENSE file.
+pub fn project_license(
+    root: &Path,
+    license: Option<&str>,
+    update_cache: bool,
+) -> anyhow::Result<String> {
+    let mut cache_manager = CacheManager::new()?;
+
+    if let Some(license) = license {
+        let license_list = ensure_spdx_license_cache(&mut cache_manager, update_cache)?;
+        let mut expression = Expression::parse(license)?;
+        let exception_list = if expression.terms().iter().any(|t| t.exception.is_some()) {
+            ensure_spdx_exception_cache(&mut cache_manager, update_cache)?
+        } else {
+            Cache::new()
+        };
+        expression.canonicalize(&license_list, &exception_list)?;
+        return Ok(expression.to_string());
+    }
+
+    let detected = detect_licenses(root)
+        .map_err(|e| anyhow::anyhow!("{} Pass the license with --license.", e))?;
+    match detected.as_slice() {
+        [license] => {
+            println!(
+                "{} Using {} from the LICENSE file",
+                "✓".green(),
+                license.cyan()
+            );
+            Ok(license.clone())
+        }
+        [] => Err(anyhow::anyhow!(
+            "Could not identify the LICENSE file. Pass the license with --license."
+        )),
+        several => Err(anyhow::anyhow!(
+            "Found several licenses ({}). Pass the SPDX expression with --license.",
+            several.join(", ")
+        )),
+    }
+}
+
+/// Lists the files below `root`, or below `paths` if given, that git would not ignore.
+///
+/// Hidden files are included, `.git` is not. With `include` globs only matching
+/// files are listed; files matching `exclude` globs never are. Globs follow the
+/// gitignore syntax and also match through a parent directory.
+pub fn source_files(
+    root: &Path,
+    paths: &[PathBuf],
+    include: &[String],
+    exclude: &[String],
+) -> anyhow::Result<Vec<PathBuf>> {
+    let matcher = Matcher::load(root)?;
+    let include = parse_globs(include);
+    let exclude = parse_globs(exclude);
+    let base = root.canonicalize()?;
+
+    let starts: Vec<PathBuf> = if paths.is_empty() {
+        vec![root.to_path_buf()]
+    } else {
+        paths.to_vec()
+    };
+
+    let mut files = Vec::new();
+    for start in starts {
+        if !start.exists() {
+            return Err(anyhow::anyhow!(
+                "Path '{}' does not exist.",
+                start.display()
+            ));
+        }
+        // Paths outside the repository are matched relative to themselves
+        let canonical = start.canonicalize()?;
+        let start_rel = canonical
+            .strip_prefix(&base)
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+
+        let mut pending = vec![(start, start_rel, true)];
+        while let Some((path, rel, given)) = pending.pop() {
+            let is_dir = path.is_dir();
+            if !given
+                && (matcher.ignored_by(&rel, is_dir).is_some()
+                    || glob_matches(&exclude, &rel, is_dir))
+            {
+                continue;
+            }
+            if !is_dir {
+                if include.is_empty() || glob_matches(&include, &rel, false) {
+                    files.push(path);
+                }
+                continue;
+            }
+            for entry in fs::read_dir(&path)?.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                // Symbolic links are neither followed nor listed
+                if name == ".git" || !(file_type.is_dir() || file_type.is_file()) {
+                    continue;
+                }
+                let child = if rel.is_empty() {
+                    name
+                } else {
+                    format!("{}/{}", rel, name)
+                };
+                pending.push((entry.path(), child, false));
+            }
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn parse_globs(globs: &[String]) -> Vec<Rule> {
+    globs
+        .iter()
+        .filter_map(|glob| match rules::parse_line(glob) {
+            Line::Rule(rule) => Some(rule),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a glob matches `path` or one of its parent directories.
+fn glob_matches(globs: &[Rule], path: &str, is_dir: bool) -> bool {
+    let components: Vec<&str> = path.split('/').collect();
+    globs.iter().any(|glob| {
+        glob.matches(path, is_dir)
+            || (1..components.len()).any(|i| glob.matches(&components[..i].join("/"), true))
+    })
+}
+
+/// The `SPDX-License-Identifier` of a source file, if it has a header.
+pub fn declared_license(path: &Path) -> Option<String> {
+    let style = style_for(path, &std::collections::HashMap::new(), true).ok()?;
+    let bytes = std::fs::read(path).ok()?;
+    if is_binary(&bytes) {
+        return None;
+    }
+    let content = String::from_utf8(bytes).ok()?;
+    ExistingHeader::read(&content, style)?.license
+}
+
+/// Binary files hold a NUL byte near the start, as git assumes.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(8000).any(|b| *b == 0)
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use clap::ValueEnum;
+use regex::Regex;
+
+pub use crate::utils::get_comment::CommentStyle;
+use crate::utils::get_comment::is_known_file_name;
+
+/// Why a file gets no header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skip {
+    /// No comment syntax is known for the file
+    Unknown,
+    /// A configuration or documentation file that was not asked for
+    NotSource,
+}
+
+/// The comment style of a file's header: user overrides first, then the file name,
+/// then the extension. Configuration and documentation files only get a header
+/// when `any_file` is set, or when an override names them.
+pub fn style_for(
+    path: &Path,
+    overrides: &HashMap<String, CommentStyle>,
+    any_file: bool,
+) -> Result<CommentStyle, Skip> {
+    let name = path.file_name().map(|n| n.to_string_lossy());
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+
+    if let Some(style) = name.as_ref().and_then(|name| overrides.get(name.as_ref())) {
+        return Ok(*style);
+    }
+    if let Some(style) = ext.as_ref().and_then(|ext| overrides.get(ext)) {
+        return Ok(*style);
+    }
+    match CommentStyle::for_path(path) {
+        Some((style, source)) if source || any_file => Ok(style),
+        Some(_) => Err(Skip::NotSource),
+        None => Err(Skip::Unknown),
+    }
+}
+
+/// Parses `KEY=STYLE` overrides, where KEY is an extension (`tpl`) or a file name (`Jenkinsfile`).
+pub fn parse_overrides(values: &[String]) -> anyhow::Result<HashMap<String, CommentStyle>> {
+    let mut overrides = HashMap::new();
+    for value in values {
+        let (key, style) = value.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid comment style '{}'. Use EXT=STYLE, e.g. tpl=hash",
+                value
+            )
+        })?;
+        let style = CommentStyle::from_str(style.trim(), true).map_err(|_| {
+            let names: Vec<String> = CommentStyle::value_variants()
+                .iter()
+                .filter_map(|s| s.to_possible_value().map(|v| v.get_name().to_string()))
+                .collect();
+            anyhow::anyhow!(
+                "Unknown comment style '{}'. Use one of: {}",
+                style.trim(),
+                names.join(", ")
+            )
+        })?;
+        let key = key.trim().trim_start_matches("*.");
+        let key = match key.strip_prefix('.') {
+            // `.tpl` is an extension, `.gitignore` a file name
+            Some(ext) if !is_known_file_name(key) => ext,
+            _ => key,
+        };
+        overrides.insert(key.to_string(), style);
+    }
+    Ok(overrides)
+}
+
+/// Splits off the leading lines that must stay first: shebangs, XML and PHP
+/// prologs, doctypes, encoding pragmas and, in Markdown, YAML front matter.
+pub fn split_prologue(content: &str, style: CommentStyle) -> (&str, &str) {
+    let pragma_re =
+        Regex::new(r"^[ \t\f]*(#|//|--|;+|%)?.*?(coding[:=]|frozen_string_literal:|-\*-.*-\*-)")
+            .unwrap();
+
+    let mut lines = content.split_inclusive('\n').enumerate();
+    let mut end = 0;
+
+    if style == CommentStyle::Html && content.lines().next() == Some("---") {
+        let closing = content
+            .split_inclusive('\n')
+            .skip(1)
+            .position(|line| line.trim_end() == "---");
+        if let Some(closing) = closing {
+            for (_, line) in lines.by_ref().take(closing + 2) {
+                end += line.len();
+            }
+        }
+    }
+
+    for (i, line) in lines {
+        let trimmed = line.trim_start();
+        let keep = (i == 0 && trimmed.starts_with("#!"))
+            || trimmed.starts_with("<?xml")
+            || trimmed.starts_with("<?php")
+            || trimmed.to_lowercase().starts_with("<!doctype")
+            || (i < 2 && is_comment_line(trimmed) && pragma_re.is_match(trimmed));
+        if !keep {
+            break;
+        }
+        end += line.len();
+    }
+    content.split_at(end)
+}
+
+/// Puts the header comment after the prologue, separated from the rest by a blank line.
+pub fn insert_header(content: &str, style: CommentStyle, lines: &[String]) -> String {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (prologue, body) = split_prologue(content, style);
+
+    let mut result = prologue.to_string();
+    if !prologue.is_empty() && !prologue.ends_with('\n') {
+        result.push_str(newline);
+    }
+    result.push_str(&style.comment(lines, newline));
+    if !body.is_empty() {
+        if !body.starts_with(['\n', '\r']) {
+            result.push_str(newline);
+        }
+        result.push_str(body);
+    }
+    result
+}
+
+fn is_comment_line(line: &str) -> bool {
+    ["#", "//", "--", ";", "%", "/*"]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+}
@@ -6,7 +6,10 @@ use crate::utils::progress;
 use crate::utils::remote::Fetcher;
 
 // SPDX license list URL
-use super::{SPDX_LICENSE_LIST_URL, ensure_github_api_license_cache, ensure_spdx_license_cache};
+use super::{
+    SPDX_LICENSE_LIST_URL, ensure_github_api_license_cache, ensure_spdx_exception_cache,
+    ensure_spdx_license_cache,
+};
 
 #[derive(clap::Args)]
 pub struct ListArgs {
@@ -40,15 +43,17 @@ pub struct ListArgs {
     /// Show only FSF libre-approved licenses
     #[arg(long)]
     pub fsf_libre: bool,
+
+    /// List SPDX license exceptions (e.g. LLVM-exception) instead of licenses
+    #[arg(long, short)]
+    pub exceptions: bool,
 }
 
 impl super::Runnable for ListArgs {
     fn run(&self) -> anyhow::Result<()> {
         // Handle any unknown arguments
-        if !self.args.is_empty() {
-            for arg in &self.args {
-                return Err(anyhow::anyhow!("Unknown argument: {}", arg));
-            }
+        if let Some(arg) = self.args.first() {
+            return Err(anyhow::anyhow!("Unknown argument: {}", arg));
         }
 
         // License Args
@@ -60,10 +65,12 @@ impl super::Runnable for ListArgs {
             fsf_libre: self.fsf_libre,
         };
 
-        if self.popular {
-            return list_popular_licenses(license_args);
+        if self.exceptions {
+            list_exceptions(license_args)
+        } else if self.popular {
+            list_popular_licenses(license_args)
         } else if self.non_software {
-            return list_non_software_licenses(self.update_cache);
+            list_non_software_licenses(self.update_cache)
         } else {
             list_all_licenses(license_args)
                 .map_err(|e| anyhow::anyhow!("Failed to list licenses: {}", e))
@@ -191,17 +198,7 @@ fn list_all_licenses(args: LicenseArgs) -> anyhow::Result<()> {
 
         // Apply search filter if provided, supporting wildcard patterns (case-insensitive)
         if let Some(search) = &args.search {
-            let mut search_lower = search.to_lowercase();
-            // If the search does not end with '*' or '?', add a trailing '*'
-            if !search_lower.ends_with('*') && !search_lower.ends_with('?') {
-                search_lower.push('*');
-            }
-            let candidates = vec![license_id.to_lowercase(), license_name.to_lowercase()];
-            let filtered = filter_by_wildcard(&search_lower, &candidates);
-
-            if !filtered.contains(&license_id.to_lowercase())
-                && !filtered.contains(&license_name.to_lowercase())
-            {
+            if !matches_search(search, license_id, license_name) {
                 continue;
             }
         }
@@ -267,6 +264,76 @@ fn list_all_licenses(args: LicenseArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn list_exceptions(args: LicenseArgs) -> anyhow::Result<()> {
+    let mut cache_manager = CacheManager::new()?;
+    let cache: Cache<serde_json::Value> =
+        ensure_spdx_exception_cache(&mut cache_manager, args.update_cache)?;
+
+    let mut exceptions = Vec::new();
+    for (id, entry) in &cache.entries {
+        let name = entry
+            .data
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("Unknown Exception");
+        let is_deprecated = entry
+            .data
+            .get("isDeprecatedLicenseId")
+            .and_then(|d| d.as_bool())
+            .unwrap_or(false);
+
+        if is_deprecated && !args.include_deprecated {
+            continue;
+        }
+        if let Some(search) = &args.search {
+            if !matches_search(search, id, name) {
+                continue;
+            }
+        }
+        exceptions.push((id.as_str(), name, is_deprecated, &entry.data));
+    }
+    exceptions.sort_by(|a, b| a.0.cmp(b.0));
+
+    if exceptions.is_empty() {
+        match &args.search {
+            Some(search) => println!("No license exceptions found matching '{}'", search),
+            None => println!("No license exceptions found"),
+        }
+        return Ok(());
+    }
+
+    let header = match &args.search {
+        Some(search) => format!("License exceptions matching '{}'", search),
+        None => "Available SPDX license exceptions".to_string(),
+    };
+    println!("{} {} ({} found):", "✓".green(), header, exceptions.len());
+    println!();
+
+    display_simple_licenses(&exceptions);
+
+    println!(
+        "\nUse an exception with WITH, e.g. gh-templates license add \"GPL-2.0-only WITH Classpath-exception-2.0\""
+    );
+    if !args.include_deprecated {
+        println!("Note:  deprecated exceptions are hidden. Use --include-deprecated to show them.");
+    }
+
+    Ok(())
+}
+
+/// Matches an id or name against a search term, with wildcards and an implied trailing `*`.
+fn matches_search(search: &str, id: &str, name: &str) -> bool {
+    let mut search_lower = search.to_lowercase();
+    // If the search does not end with '*' or '?', add a trailing '*'
+    if !search_lower.ends_with('*') && !search_lower.ends_with('?') {
+        search_lower.push('*');
+    }
+    let candidates = vec![id.to_lowercase(), name.to_lowercase()];
+    let filtered = filter_by_wildcard(&search_lower, &candidates);
+
+    filtered.contains(&id.to_lowercase()) || filtered.contains(&name.to_lowercase())
+}
+
 fn display_simple_licenses(licenses: &[(&str, &str, bool, &serde_json::Value)]) {
     for (id, name, is_deprecated, _) in licenses {
         let deprecated_marker = if *is_deprecated { " (deprecated)" } else { "" };
@@ -0,0 +1,366 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use colored::*;
+use toml_edit::{DocumentMut, Item};
+
+use super::expression::Expression;
+
+/// Manifests with a license field, in the order they are reported.
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// The license field of a project manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseField {
+    pub manifest: &'static str,
+    /// Where the field is, e.g. `[package] license`
+    pub location: String,
+    /// The field's value, when it is a string
+    pub value: Option<String>,
+}
+
+/// The license fields of the manifests in `dir`. Manifests that cannot be parsed are skipped.
+pub fn license_fields(dir: &Path) -> Vec<LicenseField> {
+    let mut fields = Vec::new();
+    for manifest in MANIFESTS {
+        let Ok(text) = fs::read_to_string(dir.join(manifest)) else {
+            continue;
+        };
+        match *manifest {
+            "package.json" => {
+                let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+                if let Some(value) = json.get("license") {
+                    fields.push(LicenseField {
+                        manifest,
+                        location: "license".to_string(),
+                        value: value.as_str().map(str::to_string),
+                    });
+                }
+            }
+            _ => {
+                let Ok(doc) = text.parse::<DocumentMut>() else {
+                    continue;
+                };
+                for table in toml_tables(manifest, &doc) {
+                    let Some(item) = toml_get(&doc, table).and_then(|t| t.get("license")) else {
+                        continue;
+                    };
+                    // `license.workspace = true` takes the workspace's value
+                    if item.get("workspace").is_some() {
+                        continue;
+                    }
+                    fields.push(LicenseField {
+                        manifest,
+                        location: format!("[{}] license", table.join(".")),
+                        value: item.as_str().map(str::to_string),
+                    });
+                }
+            }
+        }
+    }
+    fields
+}
+
+/// Sets the license field of every manifest in `dir` to `expression`, leaving the rest of
+/// each file as written. Returns the fields that changed.
+pub fn update_license_fields(dir: &Path, expression: &str) -> Result<Vec<LicenseField>> {
+    let mut updated = Vec::new();
+    for manifest in MANIFESTS {
+        let path = dir.join(manifest);
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let (new_text, locations) = match *manifest {
+            "package.json" => update_package_json(&text, expression)?,
+            _ => update_toml(manifest, &text, expression)?,
+        };
+        if new_text != text {
+            fs::write(&path, new_text)
+                .map_err(|e| anyhow!("Failed to write {}: {}", manifest, e))?;
+            updated.extend(locations.into_iter().map(|location| LicenseField {
+                manifest,
+                location,
+                value: Some(expression.to_string()),
+            }));
+        }
+    }
+    Ok(updated)
+}
+
+/// Warns about manifest license fields naming other licenses than `license_ids`, the
+/// licenses of the LICENSE files.
+pub fn warn_disagreements(dir: &Path, license_ids: &[String], hint: &str) {
+    let files = license_ids.join(", ");
+    let mut disagree = false;
+    for field in license_fields(dir) {
+        let Some(value) = &field.value else {
+            continue;
+        };
+        if agrees(value, license_ids) != Some(false) {
+            continue;
+        }
+        disagree = true;
+        println!(
+            "{} {} {} is \"{}\" but the license files are {}",
+            "⚠".yellow(),
+            field.manifest,
+            field.location,
+            value,
+            files.cyan()
+        );
+    }
+    if disagree {
+        println!("  {}", hint);
+    }
+}
+
+/// Whether a manifest expression names the same licenses as `license_ids`, or `None`
+/// when it is not an SPDX expression, as in npm's `SEE LICENSE IN ...`.
+fn agrees(value: &str, license_ids: &[String]) -> Option<bool> {
+    // Cargo used to separate alternatives with `/`
+    let expression = Expression::parse(&value.replace('/', " OR ")).ok()?;
+    let mut named: Vec<String> = expression
+        .terms()
+        .into_iter()
+        .map(|term| term.id.to_lowercase())
+        .collect();
+    let mut expected: Vec<String> = license_ids.iter().map(|id| id.to_lowercase()).collect();
+    named.sort();
+    named.dedup();
+    expected.sort();
+    expected.dedup();
+    Some(named == expected)
+}
+
+/// The tables that may hold a license field: Cargo's `[package]` and `[workspace.package]`,
+/// and `[project]` (PEP 621) or Poetry's `[tool.poetry]`.
+fn toml_tables(manifest: &str, doc: &DocumentMut) -> Vec<&'static [&'static str]> {
+    let candidates: &[&'static [&'static str]] = match manifest {
+        "Cargo.toml" => &[&["package"], &["workspace", "package"]],
+        _ => &[&["project"], &["tool", "poetry"]],
+    };
+    candidates
+        .iter()
+        .copied()
+        .filter(|table| toml_get(doc, table).is_some_and(|t| t.is_table_like()))
+        .collect()
+}
+
+fn toml_get<'a>(doc: &'a DocumentMut, path: &[&str]) -> Option<&'a Item> {
+    let mut item = doc.as_item();
+    for key in path {
+        item = item.get(key)?;
+    }
+    Some(item)
+}
+
+fn update_toml(manifest: &str, text: &str, expression: &str) -> Result<(String, Vec<String>)> {
+    let mut doc = text
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow!("Failed to parse {}: {}", manifest, e))?;
+
+    let tables = toml_tables(manifest, &doc);
+    let only_table = tables.len() == 1;
+    let mut locations = Vec::new();
+    for table in tables {
+        let mut item = doc.as_item_mut();
+        for key in table {
+            item = &mut item[key];
+        }
+        let Some(table_like) = item.as_table_like_mut() else {
+            continue;
+        };
+        // A member inheriting the license from its workspace keeps doing so
+        if table_like
+            .get("license")
+            .is_some_and(|l| l.get("workspace").is_some())
+        {
+            continue;
+        }
+        // [workspace.package] and [tool.poetry] next to [package] or [project] only
+        // change when they already set a license
+        let primary = table.len() == 1;
+        if !primary && !only_table && table_like.get("license").is_none() {
+            continue;
+        }
+        if table_like.get("license").and_then(|l| l.as_str()) == Some(expression) {
+            continue;
+        }
+
+        let mut value = toml_edit::Value::from(expression);
+        if let Some(old) = table_like.get("license").and_then(|l| l.as_value()) {
+            *value.decor_mut() = old.decor().clone();
+        }
+        table_like.insert("license", Item::Value(value));
+        locations.push(format!("[{}] license", table.join(".")));
+    }
+    Ok((doc.to_string(), locations))
+}
+
+/// Edits the text of package.json in place, so its indentation and key order are kept.
+fn update_package_json(text: &str, expression: &str) -> Result<(String, Vec<String>)> {
+    let json: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| anyhow!("Failed to parse package.json: {}", e))?;
+    if !json.is_object() {
+        return Err(anyhow!("package.json is not a JSON object"));
+    }
+    if json.get("license").and_then(|l| l.as_str()) == Some(expression) {
+        return Ok((text.to_string(), Vec::new()));
+    }
+    let quoted = serde_json::to_string(expression)?;
+    let members = top_level_members(text);
+
+    let mut result = text.to_string();
+    if let Some(license) = members.iter().find(|m| m.key == "license") {
+        result.replace_range(license.value.clone(), &quoted);
+    } else {
+        // Next to the name and version, or first when there are none
+        let anchor = members
+            .iter()
+            .find(|m| m.key == "version")
+            .or_else(|| members.iter().find(|m| m.key == "name"));
+        let (at, indent) = match (anchor, members.first()) {
+            (Some(member), _) => (member.value.end, indentation(text, member.key_start)),
+            (None, Some(first)) => (first.key_start, indentation(text, first.key_start)),
+            (None, None) => (text.find('{').map_or(0, |i| i + 1), "  ".to_string()),
+        };
+        let insertion = match (anchor, members.is_empty()) {
+            (Some(_), _) => format!(",\n{}\"license\": {}", indent, quoted),
+            (None, false) => format!("\"license\": {},\n{}", quoted, indent),
+            (None, true) => format!("\n{}\"license\": {}\n", indent, quoted),
+        };
+        result.insert_str(at, &insertion);
+    }
+    Ok((result, vec!["license".to_string()]))
+}
+
+/// A member of the top-level JSON object, with byte offsets into the text.
+struct Member {
+    key: String,
+    key_start: usize,
+    value: std::ops::Range<usize>,
+}
+
+/// Finds the members of the top-level object of a valid JSON text.
+fn top_level_members(text: &str) -> Vec<Member> {
+    let bytes = text.as_bytes();
+    let mut members = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    // The key of the member whose value comes next, at depth 1
+    let mut pending: Option<(String, usize)> = None;
+    let mut expecting_key = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i);
+                if depth == 1 {
+                    if expecting_key {
+                        let key = serde_json::from_str(&text[i..end]).unwrap_or_default();
+                        pending = Some((key, i));
+                        expecting_key = false;
+                    } else if let Some((key, key_start)) = pending.take() {
+                        members.push(Member {
+                            key,
+                            key_start,
+                            value: i..end,
+                        });
+                    }
+                }
+                i = end;
+                continue;
+            }
+            b'{' | b'[' => {
+                depth += 1;
+                if depth == 1 {
+                    expecting_key = true;
+                } else if depth == 2 {
+                    if let Some((key, key_start)) = pending.take() {
+                        let end = container_end(bytes, i);
+                        members.push(Member {
+                            key,
+                            key_start,
+                            value: i..end,
+                        });
+                        depth -= 1;
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b',' if depth == 1 => expecting_key = true,
+            b':' if depth == 1 => {}
+            c if depth == 1 && !c.is_ascii_whitespace() => {
+                // A number, `true`, `false` or `null`
+                let start = i;
+                while i < bytes.len()
+                    && !matches!(bytes[i], b',' | b'}' | b']')
+                    && !bytes[i].is_ascii_whitespace()
+                {
+                    i += 1;
+                }
+                if let Some((key, key_start)) = pending.take() {
+                    members.push(Member {
+                        key,
+                        key_start,
+                        value: start..i,
+                    });
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    members
+}
+
+/// The offset just past the JSON string starting at `start`.
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// The offset just past the JSON object or array starting at `start`.
+fn container_end(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i = string_end(bytes, i);
+                continue;
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The whitespace before the line's first character, up to `offset`.
+fn indentation(text: &str, offset: usize) -> String {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    text[line_start..offset]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
@@ -6,8 +6,18 @@ use crate::utils::progress;
 use crate::utils::remote::Fetcher;
 
 mod add;
+mod choose;
+mod compat;
+mod defaults;
+mod detect;
+mod expression;
+mod header;
 mod list;
+mod manifest;
+mod notice;
 mod preview;
+mod reuse;
+mod template;
 
 // Global constants - these can stay in the main module file
 const CACHE_MAX_AGE_SECONDS: u64 = 60 * 60 * 24 * 30; // 30 days
@@ -18,27 +28,57 @@ const SPDX_LICENSE_LIST_URL: &str =
 const CHOOSEALICENSE_RAW_BASE_URL: &str =
     "https://raw.githubusercontent.com/github/choosealicense.com/gh-pages/_licenses";
 
+const CHOOSEALICENSE_CACHE_NAME: &str = "choosealicense_cache";
+
 const GITHUB_LICENSES_CACHE_NAME: &str = "github_licenses_cache";
 const GITHUB_LICENSE_API_URL: &str = "https://api.github.com/licenses";
 
 const SPDX_CACHE_NAME: &str = "spdx_license_cache";
+const SPDX_TEXT_CACHE_NAME: &str = "spdx_license_text_cache";
+
+const SPDX_EXCEPTION_LIST_URL: &str =
+    "https://raw.githubusercontent.com/spdx/license-list-data/main/json/exceptions.json";
+const SPDX_EXCEPTION_DETAILS_BASE_URL: &str =
+    "https://raw.githubusercontent.com/spdx/license-list-data/main/json/exceptions";
+const SPDX_EXCEPTION_CACHE_NAME: &str = "spdx_exception_cache";
+const SPDX_EXCEPTION_TEXT_CACHE_NAME: &str = "spdx_exception_text_cache";
 
 #[derive(Subcommand)]
 pub enum Command {
     /// Add one or more licenses to the repository
     Add(add::AddArgs),
+    /// Recommend a license by asking what it should allow and require
+    Choose(choose::ChooseArgs),
+    /// Check whether code under one license can be included in a project under another
+    Compat(compat::CompatArgs),
+    /// Identify the SPDX license of existing LICENSE or COPYING files
+    Detect(detect::DetectArgs),
+    /// Manage SPDX license headers in source files
+    #[command(subcommand)]
+    Header(header::Command),
     /// List available licenses
     List(list::ListArgs),
+    /// Manage the NOTICE file of Apache-2.0 projects
+    #[command(subcommand)]
+    Notice(notice::Command),
     /// Preview a specific license
     Preview(preview::PreviewArgs),
+    /// Make the repository follow the REUSE specification, or check that it does
+    Reuse(reuse::ReuseArgs),
 }
 
 impl Command {
     pub fn execute(&self) -> anyhow::Result<()> {
         match self {
             Command::Add(args) => args.run(),
+            Command::Choose(args) => args.run(),
+            Command::Compat(args) => args.run(),
+            Command::Detect(args) => args.run(),
+            Command::Header(cmd) => cmd.execute(),
             Command::List(args) => args.run(),
+            Command::Notice(cmd) => cmd.execute(),
             Command::Preview(args) => args.run(),
+            Command::Reuse(args) => args.run(),
         }
     }
 }
@@ -51,7 +91,7 @@ fn ensure_spdx_license_cache(
     let should_update = cache_manager
         .should_update_cache::<serde_json::Value>(SPDX_CACHE_NAME, CACHE_MAX_AGE_SECONDS)?;
 
-    if !should_update || !update_cache {
+    if !should_update && !update_cache {
         let cache = cache_manager.load_cache(SPDX_CACHE_NAME)?;
         // Only print if running in verbose/debug mode (not implemented here)
         // e.g., println!("Loaded license template cache ({} templates)", cache.entries.len());
@@ -124,3 +164,110 @@ fn ensure_github_api_license_cache(
     cache_manager.save_cache(GITHUB_LICENSES_CACHE_NAME, &new_cache)?;
     Ok(new_cache)
 }
+
+fn ensure_spdx_exception_cache(
+    cache_manager: &mut CacheManager,
+    update_cache: bool,
+) -> Result<Cache<serde_json::Value>, anyhow::Error> {
+    let should_update = cache_manager.should_update_cache::<serde_json::Value>(
+        SPDX_EXCEPTION_CACHE_NAME,
+        CACHE_MAX_AGE_SECONDS,
+    )?;
+
+    if !should_update && !update_cache {
+        return cache_manager.load_cache(SPDX_EXCEPTION_CACHE_NAME);
+    }
+
+    let pb = progress::spinner("Updating license exception cache...");
+
+    let fetcher = Fetcher::new();
+    let data = fetcher.fetch_json(SPDX_EXCEPTION_LIST_URL)?;
+    let mut cache = Cache::new();
+
+    if let Some(exceptions) = data.get("exceptions").and_then(|v| v.as_array()) {
+        for entry in exceptions {
+            if let Some(id) = entry.get("licenseExceptionId").and_then(|id| id.as_str()) {
+                cache.insert(id.to_string(), entry.clone());
+            }
+        }
+    }
+
+    pb.finish_and_clear();
+    println!(
+        "License exception cache updated ({} exceptions available).",
+        cache.entries.len()
+    );
+
+    cache_manager.save_cache(SPDX_EXCEPTION_CACHE_NAME, &cache)?;
+    Ok(cache)
+}
+
+/// Returns the SPDX details of a license (`licenseText`, `standardLicenseTemplate`, ...),
+/// downloading them into `text_cache` the first time they are needed.
+fn license_details<'a>(
+    text_cache: &'a mut Cache<serde_json::Value>,
+    id: &str,
+    fetcher: &Fetcher,
+) -> anyhow::Result<&'a serde_json::Value> {
+    cached_details(text_cache, SPDX_LICENSE_DETAILS_BASE_URL, id, fetcher)
+}
+
+/// Returns the SPDX details of a license exception (`licenseExceptionText`, ...),
+/// downloading them into `text_cache` the first time they are needed.
+fn exception_details<'a>(
+    text_cache: &'a mut Cache<serde_json::Value>,
+    id: &str,
+    fetcher: &Fetcher,
+) -> anyhow::Result<&'a serde_json::Value> {
+    cached_details(text_cache, SPDX_EXCEPTION_DETAILS_BASE_URL, id, fetcher)
+}
+
+fn cached_details<'a>(
+    text_cache: &'a mut Cache<serde_json::Value>,
+    base_url: &str,
+    id: &str,
+    fetcher: &Fetcher,
+) -> anyhow::Result<&'a serde_json::Value> {
+    if !text_cache.contains_key(id) {
+        let url = format!("{}/{}.json", base_url, id);
+        let details = fetcher.fetch_json(&url)?;
+        text_cache.insert(id.to_string(), details);
+    }
+    text_cache
+        .get(id)
+        .ok_or_else(|| anyhow::anyhow!("'{}' not found in the text cache", id))
+}
+
+/// Returns a license id as the SPDX list spells it, e.g. `mit` as `MIT`.
+fn canonical_license_id(
+    license_list: &Cache<serde_json::Value>,
+    id: &str,
+) -> anyhow::Result<String> {
+    canonical_id(license_list, id).ok_or_else(|| {
+        anyhow::anyhow!(
+            "License '{}' not found in SPDX cache. Please check the license ID.",
+            id.trim()
+        )
+    })
+}
+
+/// Returns an exception id as the SPDX list spells it, e.g. `llvm-exception` as `LLVM-exception`.
+fn canonical_exception_id(
+    exception_list: &Cache<serde_json::Value>,
+    id: &str,
+) -> anyhow::Result<String> {
+    canonical_id(exception_list, id).ok_or_else(|| {
+        anyhow::anyhow!(
+            "License exception '{}' not found in SPDX cache. Use `license list --exceptions` to see them.",
+            id.trim()
+        )
+    })
+}
+
+fn canonical_id(list: &Cache<serde_json::Value>, id: &str) -> Option<String> {
+    let id_lower = id.trim().to_lowercase();
+    list.entries
+        .keys()
+        .find(|key| key.to_lowercase() == id_lower)
+        .cloned()
+}
@@ -0,0 +1,345 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Datelike;
+use clap::Subcommand;
+use colored::*;
+
+use crate::utils::file;
+
+use super::Runnable;
+use super::defaults::{Inferred, ProjectDefaults};
+use super::detect::detect_licenses;
+use super::expression::Expression;
+
+/// Names a NOTICE file may have, in order of preference.
+const NOTICE_NAMES: &[&str] = &["NOTICE", "NOTICE.txt", "NOTICE.md"];
+
+/// Line starting the attribution block of each bundled component.
+const SEPARATOR: &str =
+    "--------------------------------------------------------------------------------";
+
+/// Start of the first line of an attribution block, followed by the component's name.
+const BUNDLES: &str = "This product bundles ";
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a NOTICE file with the project name and copyright line
+    Init(NoticeInitArgs),
+    /// Append an attribution block for bundled third-party code
+    Add(NoticeAddArgs),
+    /// Check that Apache-2.0 projects have a NOTICE file, and that it is well formed
+    Check(NoticeCheckArgs),
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<()> {
+        match self {
+            Command::Init(args) => args.run(),
+            Command::Add(args) => args.run(),
+            Command::Check(args) => args.run(),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NoticeInitArgs {
+    /// Project name (default: the name in the project manifest, or the directory name)
+    #[arg(long, value_name = "NAME")]
+    pub project: Option<String>,
+
+    /// Copyright holder (default: git user.name, or the author in the project manifest)
+    #[arg(long, value_name = "NAME")]
+    pub holder: Option<String>,
+
+    /// Copyright year (default: the year of the first commit to the current year)
+    #[arg(long, value_name = "YEAR")]
+    pub year: Option<String>,
+
+    /// Overwrite an existing NOTICE file
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NoticeAddArgs {
+    /// Name of the bundled component, e.g. "libfoo 1.2"
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// SPDX license of the component
+    #[arg(long, short = 'l', value_name = "SPDX")]
+    pub license: Option<String>,
+
+    /// Where the component comes from
+    #[arg(long, value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Copyright line of the component (repeatable)
+    #[arg(long, value_name = "TEXT")]
+    pub copyright: Vec<String>,
+
+    /// The component's own NOTICE file, copied into the block as it is
+    #[arg(long, value_name = "FILE")]
+    pub notice: Option<PathBuf>,
+
+    /// Replace the block of a component with the same name
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NoticeCheckArgs {}
+
+impl Runnable for NoticeInitArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let given = |value: &Option<String>, flag: &str| {
+            value.as_ref().map(|value| Inferred {
+                value: value.trim().to_string(),
+                source: flag.to_string(),
+            })
+        };
+        let detected = ProjectDefaults::detect(&root);
+        let defaults = ProjectDefaults {
+            year: given(&self.year, "--year").or(detected.year),
+            holder: given(&self.holder, "--holder").or(detected.holder),
+            project: given(&self.project, "--project").or(detected.project),
+        };
+
+        let header = notice_header(&root, &defaults)
+            .ok_or_else(|| anyhow!("No copyright holder: pass --holder or set git user.name"))?;
+        let path = find(&root).unwrap_or_else(|| root.join(NOTICE_NAMES[0]));
+        file::save_file(&header, &path, self.force)
+    }
+}
+
+impl Runnable for NoticeAddArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let path = find(&root).ok_or_else(|| {
+            anyhow!("No NOTICE file. Run `gh-templates license notice init` to create one.")
+        })?;
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("The component name cannot be empty"));
+        }
+        if let Some(license) = &self.license {
+            Expression::parse(license)?;
+        }
+        let included = match &self.notice {
+            Some(notice) => Some(
+                fs::read_to_string(notice)
+                    .map_err(|e| anyhow!("Failed to read '{}': {}", notice.display(), e))?,
+            ),
+            None => None,
+        };
+
+        let mut notice = Notice::parse(&fs::read_to_string(&path)?);
+        let block = self.block(name, included.as_deref());
+        let shown = path.file_name().unwrap_or_default().to_string_lossy();
+        let existing = notice
+            .blocks
+            .iter()
+            .position(|b| block_name(b).is_some_and(|n| n.eq_ignore_ascii_case(name)));
+        match existing {
+            Some(_) if !self.force => {
+                return Err(anyhow!(
+                    "{} already attributes '{}'. Use --force to replace it.",
+                    shown,
+                    name
+                ));
+            }
+            Some(i) => notice.blocks[i] = block,
+            None => notice.blocks.push(block),
+        }
+        fs::write(&path, notice.to_string())
+            .map_err(|e| anyhow!("Failed to write {}: {}", shown, e))?;
+
+        let action = if existing.is_some() {
+            "Replaced"
+        } else {
+            "Added"
+        };
+        println!(
+            "{} {} the attribution of {} in {}",
+            "✓".green(),
+            action,
+            name.cyan(),
+            shown
+        );
+        Ok(())
+    }
+}
+
+impl NoticeAddArgs {
+    /// The attribution block, without the separator.
+    fn block(&self, name: &str, included: Option<&str>) -> String {
+        let mut first = format!("{}{}", BUNDLES, name);
+        if let Some(url) = &self.url {
+            first.push_str(&format!(" ({})", url.trim()));
+        }
+        if let Some(license) = &self.license {
+            first.push_str(&format!(",\nwhich is available under {}", license.trim()));
+        }
+        first.push('.');
+
+        let mut paragraphs = vec![first];
+        if !self.copyright.is_empty() {
+            let lines: Vec<String> = self
+                .copyright
+                .iter()
+                .map(|line| copyright_line(line.trim()))
+                .collect();
+            paragraphs.push(lines.join("\n"));
+        }
+        if let Some(included) = included.map(str::trim).filter(|t| !t.is_empty()) {
+            paragraphs.push(included.to_string());
+        }
+        paragraphs.join("\n\n")
+    }
+}
+
+impl Runnable for NoticeCheckArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        // Without a recognisable LICENSE file there is nothing to require
+        let apache = detect_licenses(&root)
+            .unwrap_or_default()
+            .iter()
+            .any(|id| id == "Apache-2.0");
+
+        let Some(path) = find(&root) else {
+            if apache {
+                return Err(anyhow!(
+                    "The project is licensed under Apache-2.0 but has no NOTICE file. Run `gh-templates license notice init` to create one."
+                ));
+            }
+            println!(
+                "{} No NOTICE file, and none is needed: the project is not licensed under Apache-2.0",
+                "✓".green()
+            );
+            return Ok(());
+        };
+        let shown = path.file_name().unwrap_or_default().to_string_lossy();
+        let notice = Notice::parse(&fs::read_to_string(&path)?);
+
+        let mut problems = Vec::new();
+        if notice.head.trim().is_empty() {
+            problems.push("does not name the project".to_string());
+        } else if !notice.head.lines().any(is_copyright) {
+            problems.push("has no copyright line for the project".to_string());
+        }
+        let mut names: Vec<String> = Vec::new();
+        for (i, block) in notice.blocks.iter().enumerate() {
+            match block_name(block) {
+                None => problems.push(format!(
+                    "attribution block {} does not start with \"{}NAME\"",
+                    i + 1,
+                    BUNDLES
+                )),
+                Some(name) if names.iter().any(|n| n.eq_ignore_ascii_case(&name)) => {
+                    problems.push(format!("attributes {} twice", name))
+                }
+                Some(name) => names.push(name),
+            }
+        }
+
+        if !problems.is_empty() {
+            for problem in &problems {
+                println!("{} {} {}", "!".red(), shown, problem);
+            }
+            return Err(anyhow!("{} has {} problem(s)", shown, problems.len()));
+        }
+        let project = notice.head.lines().find(|l| !l.trim().is_empty());
+        println!(
+            "{} {} names {} and attributes {} bundled component(s)",
+            "✓".green(),
+            shown,
+            project.unwrap_or_default().trim().cyan(),
+            names.len()
+        );
+        Ok(())
+    }
+}
+
+/// The NOTICE file of `dir`, if it has one.
+pub fn find(dir: &Path) -> Option<PathBuf> {
+    NOTICE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// The start of a NOTICE file: the project's name and copyright line. `None` when the
+/// copyright holder is unknown.
+pub fn notice_header(dir: &Path, defaults: &ProjectDefaults) -> Option<String> {
+    let holder = &defaults.holder.as_ref()?.value;
+    let project = match &defaults.project {
+        Some(project) => project.value.clone(),
+        None => dir
+            .canonicalize()
+            .ok()?
+            .file_name()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let year = match &defaults.year {
+        Some(year) => year.value.clone(),
+        None => chrono::Local::now().year().to_string(),
+    };
+    Some(format!("{}\nCopyright {} {}\n", project, year, holder))
+}
+
+/// A NOTICE file: the project's own notice, then a block for each bundled component.
+struct Notice {
+    head: String,
+    blocks: Vec<String>,
+}
+
+impl Notice {
+    fn parse(text: &str) -> Self {
+        let separator = format!("\n{}\n", SEPARATOR);
+        let mut parts = text.split(&separator);
+        let head = parts.next().unwrap_or_default().trim_end().to_string();
+        let blocks = parts.map(|block| block.trim().to_string()).collect();
+        Notice { head, blocks }
+    }
+}
+
+impl std::fmt::Display for Notice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.head)?;
+        for block in &self.blocks {
+            write!(f, "\n\n{}\n{}", SEPARATOR, block)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// The component an attribution block is for.
+fn block_name(block: &str) -> Option<String> {
+    let rest = block.lines().next()?.strip_prefix(BUNDLES)?;
+    let end = [" (", ","]
+        .iter()
+        .filter_map(|marker| rest.find(marker))
+        .min()
+        .unwrap_or(rest.len());
+    let name = rest[..end].trim().trim_end_matches('.');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn is_copyright(line: &str) -> bool {
+    let line = line.trim_start().to_lowercase();
+    line.starts_with("copyright") || line.starts_with('©')
+}
+
+/// `2019 Foo Authors` becomes `Copyright 2019 Foo Authors`; full copyright lines are kept.
+fn copyright_line(text: &str) -> String {
+    if is_copyright(text) {
+        text.to_string()
+    } else {
+        format!("Copyright {}", text)
+    }
+}
@@ -1,8 +1,9 @@
 use colored::*;
 
 use super::{
-    CHOOSEALICENSE_RAW_BASE_URL, SPDX_LICENSE_DETAILS_BASE_URL, SPDX_LICENSE_LIST_URL,
-    ensure_spdx_license_cache,
+    CHOOSEALICENSE_RAW_BASE_URL, SPDX_EXCEPTION_TEXT_CACHE_NAME, SPDX_LICENSE_DETAILS_BASE_URL,
+    SPDX_LICENSE_LIST_URL, canonical_exception_id, ensure_spdx_exception_cache,
+    ensure_spdx_license_cache, exception_details,
 };
 
 use crate::utils::cache::{Cache, CacheManager};
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(clap::Args, Debug)]
 pub struct PreviewArgs {
-    /// License ID (e.g. mit, apache-2.0)
+    /// License or license exception ID (e.g. mit, apache-2.0, llvm-exception)
     #[arg(value_name = "LICENSE")]
     pub id: String,
 
@@ -64,6 +65,14 @@ impl super::Runnable for PreviewArgs {
             })
             .unwrap_or((normalized_id.clone(), serde_json::Value::Null));
 
+        // Ids that are not licenses may be license exceptions
+        if license_json.is_null() {
+            let exceptions = ensure_spdx_exception_cache(&mut cache_manager, self.update_cache)?;
+            if let Ok(exception_id) = canonical_exception_id(&exceptions, &self.id) {
+                return self.show_exception(&cache_manager, &exceptions, &exception_id);
+            }
+        }
+
         println!(
             "{} {} ({})\n",
             "License:".cyan(),
@@ -129,7 +138,86 @@ impl super::Runnable for PreviewArgs {
     }
 }
 
-fn fetch_choosealicense_meta(normalized_id: &str) -> Option<ChooseALicenseFile> {
+impl PreviewArgs {
+    fn show_exception(
+        &self,
+        cache_manager: &CacheManager,
+        exceptions: &Cache<serde_json::Value>,
+        id: &str,
+    ) -> anyhow::Result<()> {
+        let entry = exceptions.get(id);
+        println!(
+            "{} {} ({})\n",
+            "Exception:".cyan(),
+            entry
+                .and_then(|e| e.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or(id),
+            id
+        );
+
+        if self.description || self.permissions || self.limitations || self.conditions {
+            println!(
+                "  Description, permissions, limitations and conditions are only available for licenses.\n"
+            );
+        }
+
+        if self.details {
+            println!("{}", "SPDX Metadata:".cyan());
+            println!("  Exception ID: {}", id);
+            if entry
+                .and_then(|e| e.get("isDeprecatedLicenseId"))
+                .and_then(|d| d.as_bool())
+                .unwrap_or(false)
+            {
+                println!("  {}", "Status: DEPRECATED".yellow());
+            }
+            if let Some(urls) = entry
+                .and_then(|e| e.get("seeAlso"))
+                .and_then(|s| s.as_array())
+            {
+                for url in urls.iter().filter_map(|u| u.as_str()) {
+                    println!("  See also: {}", url);
+                }
+            }
+            println!(
+                "  Usage: gh-templates license add \"<LICENSE> WITH {}\"",
+                id
+            );
+            println!();
+        }
+
+        if !self.description
+            && !self.permissions
+            && !self.limitations
+            && !self.conditions
+            && !self.details
+        {
+            let mut text_cache: Cache<serde_json::Value> =
+                cache_manager.load_cache(SPDX_EXCEPTION_TEXT_CACHE_NAME)?;
+            let cached = text_cache.contains_key(id);
+
+            println!("{}", "Exception Text:".cyan());
+            println!("{}", "─".repeat(80));
+            match exception_details(&mut text_cache, id, &Fetcher::new()) {
+                Ok(details) => match details.get("licenseExceptionText").and_then(|t| t.as_str()) {
+                    Some(text) => println!("{}", text),
+                    None => println!("⚠️  Exception text not found."),
+                },
+                Err(e) => println!("⚠️  Could not fetch exception text: {}", e),
+            }
+            println!("{}", "─".repeat(80));
+
+            if !cached && text_cache.contains_key(id) {
+                cache_manager.save_cache(SPDX_EXCEPTION_TEXT_CACHE_NAME, &text_cache)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn fetch_choosealicense_meta(normalized_id: &str) -> Option<ChooseALicenseFile> {
     let url = format!("{}/{}.txt", CHOOSEALICENSE_RAW_BASE_URL, normalized_id);
     let fetcher = Fetcher::new();
     match fetcher.fetch_content(&url) {
@@ -0,0 +1,804 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Datelike;
+use colored::*;
+use regex::Regex;
+use toml_edit::{Array, ArrayOfTables, DocumentMut, Item, Table, Value};
+
+use crate::utils::cache::{Cache, CacheManager};
+use crate::utils::file;
+
+use super::add::{fetch_exception_text, fetch_license_text};
+use super::defaults::git_user_name;
+use super::expression::Expression;
+use super::header::{
+    CommentStyle, is_binary, parse_overrides, project_license, source_files, style_for,
+};
+use super::{
+    SPDX_CACHE_NAME, SPDX_EXCEPTION_CACHE_NAME, canonical_id, ensure_spdx_exception_cache,
+    ensure_spdx_license_cache,
+};
+
+/// REUSE configuration, at the root of the repository.
+const REUSE_TOML: &str = "REUSE.toml";
+
+#[derive(clap::Args, Debug)]
+pub struct ReuseArgs {
+    /// Only report whether the repository follows the REUSE specification, without
+    /// downloading or changing anything
+    #[arg(long, conflicts_with_all = ["license", "holder", "year", "update_cache"])]
+    pub lint: bool,
+
+    /// SPDX license expression for files that cannot carry a header (default: detected from the LICENSE file)
+    #[arg(long, short = 'l', value_name = "SPDX")]
+    pub license: Option<String>,
+
+    /// Copyright holder for files that cannot carry a header (default: git user.name)
+    #[arg(long, value_name = "NAME")]
+    pub holder: Option<String>,
+
+    /// Copyright year for files that cannot carry a header (default: the current year)
+    #[arg(long, value_name = "YEAR")]
+    pub year: Option<String>,
+
+    /// Comment style for an extension or file name, e.g. tpl=hash (repeatable)
+    #[arg(long = "comment-style", value_name = "EXT=STYLE")]
+    pub comment_styles: Vec<String>,
+
+    /// Update the license cache
+    #[arg(long)]
+    pub update_cache: bool,
+}
+
+/// The licensing information found for a file.
+#[derive(Debug, Clone, Default)]
+struct Info {
+    /// SPDX license expressions
+    licenses: Vec<String>,
+    copyrights: Vec<String>,
+}
+
+impl Info {
+    fn is_complete(&self) -> bool {
+        !self.licenses.is_empty() && !self.copyrights.is_empty()
+    }
+}
+
+/// How an annotation combines with the information in the files it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Precedence {
+    /// The file's own information wins; the annotation fills in what is missing
+    Closest,
+    /// Both apply
+    Aggregate,
+    /// Only the annotation applies
+    Override,
+}
+
+/// An `[[annotations]]` table of REUSE.toml.
+#[derive(Debug)]
+struct Annotation {
+    paths: Vec<Regex>,
+    precedence: Precedence,
+    info: Info,
+}
+
+/// A file REUSE expects licensing information for.
+#[derive(Debug)]
+struct CoveredFile {
+    /// Path relative to the root, with `/` separators
+    path: String,
+    info: Info,
+    /// Whether the file is text with a known comment syntax, so it can hold a header
+    can_carry_header: bool,
+}
+
+/// A license or exception id used by the covered files.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Used {
+    id: String,
+    exception: bool,
+}
+
+/// The covered files of a repository and their licensing information.
+#[derive(Debug, Default)]
+struct Scan {
+    files: Vec<CoveredFile>,
+    /// Files that could not be read, with the error
+    read_errors: Vec<(String, String)>,
+}
+
+impl super::Runnable for ReuseArgs {
+    fn run(&self) -> Result<()> {
+        let root = file::find_repo_root().unwrap_or_else(|_| PathBuf::from("."));
+        let overrides = parse_overrides(&self.comment_styles)?;
+        let mut cache_manager = CacheManager::new()?;
+        if self.update_cache {
+            cache_manager.clear_cache(SPDX_CACHE_NAME)?;
+            cache_manager.clear_cache(SPDX_EXCEPTION_CACHE_NAME)?;
+        }
+
+        if root.join(".reuse/dep5").exists() {
+            println!(
+                "{} .reuse/dep5 is not read. Move its entries to {}.",
+                "⚠".yellow(),
+                REUSE_TOML
+            );
+        }
+
+        let mut scan = Scan::new(&root, &overrides)?;
+        if !self.lint {
+            if self.annotate(&root, &scan)? {
+                scan = Scan::new(&root, &overrides)?;
+            }
+            download_missing(&root, &scan, &mut cache_manager)?;
+        }
+        report(&root, &scan, &cache_manager, self.lint)
+    }
+}
+
+impl ReuseArgs {
+    /// Annotates the files that cannot carry a header and lack licensing information in
+    /// REUSE.toml. Returns whether REUSE.toml changed.
+    fn annotate(&self, root: &Path, scan: &Scan) -> Result<bool> {
+        let files: Vec<&CoveredFile> = scan
+            .files
+            .iter()
+            .filter(|f| !f.can_carry_header && !f.info.is_complete())
+            .collect();
+        if files.is_empty() {
+            return Ok(false);
+        }
+
+        let license = project_license(root, self.license.as_deref(), false)?;
+        let holder = match &self.holder {
+            Some(holder) => holder.trim().to_string(),
+            None => git_user_name(root).ok_or_else(|| {
+                anyhow!("No copyright holder: pass --holder or set git user.name")
+            })?,
+        };
+        let year = self
+            .year
+            .clone()
+            .unwrap_or_else(|| chrono::Local::now().year().to_string());
+
+        let paths = annotation_paths(&scan.files, &files);
+        write_annotation(root, &paths, &format!("{} {}", year, holder), &license)?;
+        println!(
+            "{} Annotated {} file(s) that cannot carry a header in {}:",
+            "✓".green(),
+            files.len(),
+            REUSE_TOML
+        );
+        for path in &paths {
+            println!("  - {}", path);
+        }
+        Ok(true)
+    }
+}
+
+impl Scan {
+    /// Reads the licensing information of every covered file below `root`: its own tags, or
+    /// those of its `.license` sidecar, combined with the REUSE.toml annotations.
+    fn new(root: &Path, overrides: &HashMap<String, CommentStyle>) -> Result<Self> {
+        let annotations = read_annotations(root)?;
+        let mut scan = Scan::default();
+
+        for path in source_files(root, &[], &[], &[])? {
+            let shown = relative(root, &path);
+            if !is_covered(&shown) {
+                continue;
+            }
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    scan.read_errors.push((shown, e.to_string()));
+                    continue;
+                }
+            };
+            if bytes.is_empty() {
+                continue;
+            }
+            let text = if is_binary(&bytes) {
+                None
+            } else {
+                String::from_utf8(bytes).ok()
+            };
+
+            let mut sidecar = path.clone().into_os_string();
+            sidecar.push(".license");
+            let own = match fs::read_to_string(&sidecar) {
+                Ok(sidecar) => read_tags(&sidecar),
+                Err(_) => text.as_deref().map(read_tags).unwrap_or_default(),
+            };
+            // The last matching annotation counts
+            let annotation = annotations
+                .iter()
+                .rev()
+                .find(|a| a.paths.iter().any(|p| p.is_match(&shown)));
+
+            scan.files.push(CoveredFile {
+                info: resolve(own, annotation),
+                can_carry_header: text.is_some() && style_for(&path, overrides, true).is_ok(),
+                path: shown,
+            });
+        }
+        Ok(scan)
+    }
+
+    /// The license and exception ids of the files' expressions, with the files using them.
+    /// Expressions that do not parse are left out.
+    fn used(&self) -> BTreeMap<Used, Vec<&str>> {
+        let mut used: BTreeMap<Used, Vec<&str>> = BTreeMap::new();
+        for file in &self.files {
+            for license in &file.info.licenses {
+                let Ok(expression) = Expression::parse(license) else {
+                    continue;
+                };
+                for term in expression.terms() {
+                    let ids = std::iter::once((term.id, false))
+                        .chain(term.exception.map(|exception| (exception, true)));
+                    for (id, exception) in ids {
+                        let files = used.entry(Used { id, exception }).or_default();
+                        if !files.contains(&file.path.as_str()) {
+                            files.push(&file.path);
+                        }
+                    }
+                }
+            }
+        }
+        used
+    }
+}
+
+/// Whether REUSE expects licensing information for a file. License texts, REUSE's own
+/// files and `.license` sidecars are not covered.
+fn is_covered(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let license_file = ["LICENSE", "LICENCE", "COPYING"].iter().any(|base| {
+        name == *base
+            || name
+                .strip_prefix(base)
+                .is_some_and(|rest| rest.starts_with(['.', '-']))
+    });
+    !(license_file
+        || path.starts_with("LICENSES/")
+        || path.starts_with(".reuse/")
+        || name == REUSE_TOML
+        || name.ends_with(".license"))
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// The SPDX license and copyright tags of a file or `.license` sidecar, skipping the text
+/// between `REUSE-IgnoreStart` and `REUSE-IgnoreEnd`.
+fn read_tags(content: &str) -> Info {
+    let license_re =
+        Regex::new(r"^[^\w]*SPDX-License-Identifier:\s*(.*?)\s*(\*/|-->)?\s*$").unwrap();
+    let copyright_re = Regex::new(
+        r"^[^\w]*(?:SPDX-FileCopyrightText:|Copyright\b(?:\s*\([cC]\))?|©)\s*(.*?)\s*(\*/|-->)?\s*$",
+    )
+    .unwrap();
+
+    let mut info = Info::default();
+    let mut ignoring = false;
+    for line in content.lines() {
+        if line.contains("REUSE-IgnoreStart") {
+            ignoring = true;
+        } else if line.contains("REUSE-IgnoreEnd") {
+            ignoring = false;
+        } else if ignoring {
+            continue;
+        } else if let Some(caps) = license_re.captures(line) {
+            if !caps[1].is_empty() {
+                info.licenses.push(caps[1].to_string());
+            }
+        } else if let Some(caps) = copyright_re.captures(line) {
+            if !caps[1].is_empty() {
+                info.copyrights.push(caps[1].to_string());
+            }
+        }
+    }
+    info
+}
+
+/// Combines a file's own information with the annotation matching it, if any.
+fn resolve(own: Info, annotation: Option<&Annotation>) -> Info {
+    let Some(annotation) = annotation else {
+        return own;
+    };
+    let from = &annotation.info;
+    match annotation.precedence {
+        Precedence::Override => from.clone(),
+        Precedence::Aggregate => Info {
+            licenses: [own.licenses, from.licenses.clone()].concat(),
+            copyrights: [own.copyrights, from.copyrights.clone()].concat(),
+        },
+        Precedence::Closest => Info {
+            licenses: if own.licenses.is_empty() {
+                from.licenses.clone()
+            } else {
+                own.licenses
+            },
+            copyrights: if own.copyrights.is_empty() {
+                from.copyrights.clone()
+            } else {
+                own.copyrights
+            },
+        },
+    }
+}
+
+/// The annotations of REUSE.toml, in order; none when there is no REUSE.toml.
+fn read_annotations(root: &Path) -> Result<Vec<Annotation>> {
+    let Some(doc) = read_reuse_toml(root)? else {
+        return Ok(Vec::new());
+    };
+    let Some(tables) = doc.get("annotations").and_then(Item::as_array_of_tables) else {
+        return Ok(Vec::new());
+    };
+
+    let strings = |item: Option<&Item>| -> Vec<String> {
+        match item {
+            Some(item) if item.is_str() => item.as_str().map(str::to_string).into_iter().collect(),
+            Some(item) => item
+                .as_array()
+                .map(|array| {
+                    array
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    };
+
+    let mut annotations = Vec::new();
+    for (i, table) in tables.iter().enumerate() {
+        let paths = strings(table.get("path"));
+        if paths.is_empty() {
+            return Err(anyhow!(
+                "Annotation {} of {} has no path",
+                i + 1,
+                REUSE_TOML
+            ));
+        }
+        let precedence = match table.get("precedence").and_then(Item::as_str) {
+            None | Some("closest") => Precedence::Closest,
+            Some("aggregate") => Precedence::Aggregate,
+            Some("override") => Precedence::Override,
+            Some(other) => {
+                return Err(anyhow!(
+                    "Annotation {} of {} has an unknown precedence '{}'. Use closest, aggregate or override.",
+                    i + 1,
+                    REUSE_TOML,
+                    other
+                ));
+            }
+        };
+        annotations.push(Annotation {
+            paths: paths.iter().map(|p| glob_regex(p)).collect(),
+            precedence,
+            info: Info {
+                licenses: strings(table.get("SPDX-License-Identifier")),
+                copyrights: strings(table.get("SPDX-FileCopyrightText")),
+            },
+        });
+    }
+    Ok(annotations)
+}
+
+fn read_reuse_toml(root: &Path) -> Result<Option<DocumentMut>> {
+    let Ok(text) = fs::read_to_string(root.join(REUSE_TOML)) else {
+        return Ok(None);
+    };
+    let doc = text
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow!("Failed to parse {}: {}", REUSE_TOML, e))?;
+    if doc.get("version").and_then(Item::as_integer) != Some(1) {
+        return Err(anyhow!("{} must start with `version = 1`", REUSE_TOML));
+    }
+    Ok(Some(doc))
+}
+
+/// Converts a REUSE.toml path to a regex: `*` matches within a directory, `**` across
+/// directories, and `\` escapes the next character.
+fn glob_regex(glob: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    regex.push_str(&regex::escape(&next.to_string()));
+                }
+            }
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).unwrap()
+}
+
+fn escape_glob(path: &str) -> String {
+    path.replace('\\', "\\\\").replace('*', "\\*")
+}
+
+/// The paths of an annotation covering `files`: `dir/*.ext` when every covered file with
+/// that extension in the directory is among them, otherwise the files themselves.
+fn annotation_paths(covered: &[CoveredFile], files: &[&CoveredFile]) -> Vec<String> {
+    let group = |path: &str| -> Option<(String, String)> {
+        let (dir, name) = match path.rsplit_once('/') {
+            Some((dir, name)) => (format!("{}/", dir), name),
+            None => (String::new(), path),
+        };
+        let ext = Path::new(name).extension()?.to_string_lossy().to_string();
+        Some((dir, ext))
+    };
+
+    let mut paths = Vec::new();
+    let mut groups: BTreeMap<(String, String), Vec<&str>> = BTreeMap::new();
+    for file in files {
+        match group(&file.path) {
+            Some(key) => groups.entry(key).or_default().push(&file.path),
+            None => paths.push(escape_glob(&file.path)),
+        }
+    }
+    for (key, members) in groups {
+        let total = covered
+            .iter()
+            .filter(|c| group(&c.path).as_ref() == Some(&key))
+            .count();
+        if members.len() > 1 && members.len() == total {
+            paths.push(format!("{}*.{}", escape_glob(&key.0), escape_glob(&key.1)));
+        } else {
+            paths.extend(members.into_iter().map(escape_glob));
+        }
+    }
+    paths.sort();
+    paths
+}
+
+/// Appends an annotation to REUSE.toml, creating it if needed.
+fn write_annotation(root: &Path, paths: &[String], copyright: &str, license: &str) -> Result<()> {
+    let mut doc = match read_reuse_toml(root)? {
+        Some(doc) => doc,
+        None => "version = 1\n".parse::<DocumentMut>()?,
+    };
+
+    let path = match paths {
+        [path] => Value::from(path.as_str()),
+        _ => {
+            let mut array: Array = paths.iter().map(String::as_str).collect();
+            if paths.len() > 2 {
+                for value in array.iter_mut() {
+                    value.decor_mut().set_prefix("\n    ");
+                }
+                array.set_trailing("\n");
+                array.set_trailing_comma(true);
+            }
+            Value::Array(array)
+        }
+    };
+    let mut table = Table::new();
+    table.decor_mut().set_prefix("\n");
+    table.insert("path", Item::Value(path));
+    table.insert("SPDX-FileCopyrightText", toml_edit::value(copyright));
+    table.insert("SPDX-License-Identifier", toml_edit::value(license));
+
+    match doc
+        .get_mut("annotations")
+        .and_then(Item::as_array_of_tables_mut)
+    {
+        Some(annotations) => annotations.push(table),
+        None => {
+            let mut annotations = ArrayOfTables::new();
+            annotations.push(table);
+            doc.insert("annotations", Item::ArrayOfTables(annotations));
+        }
+    }
+
+    fs::write(root.join(REUSE_TOML), doc.to_string())
+        .map_err(|e| anyhow!("Failed to write {}: {}", REUSE_TOML, e))
+}
+
+/// The file names in LICENSES/.
+fn license_files(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(root.join("LICENSES"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// The id a LICENSES/ file holds the text of, e.g. `MIT` for `MIT.txt`.
+fn license_file_id(name: &str) -> String {
+    Path::new(name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Ids that are not on the SPDX lists, but defined by the project.
+fn is_license_ref(id: &str) -> bool {
+    id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-")
+}
+
+/// Downloads the texts of the SPDX licenses and exceptions the files use that are not in
+/// LICENSES/ yet.
+fn download_missing(root: &Path, scan: &Scan, cache_manager: &mut CacheManager) -> Result<()> {
+    let present: BTreeSet<String> = license_files(root)
+        .iter()
+        .map(|name| license_file_id(name).to_lowercase())
+        .collect();
+    let missing: Vec<Used> = scan
+        .used()
+        .into_keys()
+        .filter(|used| !present.contains(&used.id.to_lowercase()))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let license_list = ensure_spdx_license_cache(cache_manager, false)?;
+    let exception_list = if missing.iter().any(|used| used.exception) {
+        ensure_spdx_exception_cache(cache_manager, false)?
+    } else {
+        Cache::new()
+    };
+
+    fs::create_dir_all(root.join("LICENSES"))?;
+    for used in missing {
+        let list = if used.exception {
+            &exception_list
+        } else {
+            &license_list
+        };
+        let Some(id) = canonical_id(list, &used.id).filter(|_| !is_license_ref(&used.id)) else {
+            println!(
+                "{} {} is not on the SPDX list. Add its text to LICENSES/{}.txt yourself.",
+                "⚠".yellow(),
+                used.id,
+                used.id
+            );
+            continue;
+        };
+        let text = if used.exception {
+            fetch_exception_text(cache_manager, &id)?
+        } else {
+            fetch_license_text(cache_manager, &id)?.text
+        };
+        file::save_file(
+            &text,
+            &root.join("LICENSES").join(format!("{}.txt", id)),
+            false,
+        )?;
+    }
+    Ok(())
+}
+
+/// `a, b, c and 2 more`
+fn list_files(files: &[&str]) -> String {
+    let shown: Vec<&str> = files.iter().take(3).copied().collect();
+    match files.len() - shown.len() {
+        0 => shown.join(", "),
+        more => format!("{} and {} more", shown.join(", "), more),
+    }
+}
+
+/// Prints the REUSE checks, offline, and fails when any of them does not pass.
+fn report(root: &Path, scan: &Scan, cache_manager: &CacheManager, lint: bool) -> Result<()> {
+    let cached = |name: &str| -> Result<Option<Cache<serde_json::Value>>> {
+        Ok(if cache_manager.cache_exists(name) {
+            Some(cache_manager.load_cache(name)?)
+        } else {
+            None
+        })
+    };
+    let license_list = cached(SPDX_CACHE_NAME)?;
+    let exception_list = cached(SPDX_EXCEPTION_CACHE_NAME)?;
+    let license_files = license_files(root);
+    let used = scan.used();
+
+    let mut bad = 0;
+    let mut deprecated = 0;
+    let mut unchecked = false;
+    for (used, files) in &used {
+        if is_license_ref(&used.id) {
+            continue;
+        }
+        let list = if used.exception {
+            &exception_list
+        } else {
+            &license_list
+        };
+        let Some(list) = list else {
+            unchecked = true;
+            continue;
+        };
+        match canonical_id(list, &used.id).and_then(|id| list.get(&id)) {
+            None => {
+                bad += 1;
+                println!(
+                    "{} Bad license {} in {}",
+                    "!".red(),
+                    used.id,
+                    list_files(files)
+                );
+            }
+            Some(entry) if entry["isDeprecatedLicenseId"].as_bool() == Some(true) => {
+                deprecated += 1;
+                println!(
+                    "{} Deprecated license {} in {}",
+                    "!".red(),
+                    used.id,
+                    list_files(files)
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mut invalid = 0;
+    for file in &scan.files {
+        for license in &file.info.licenses {
+            if let Err(e) = Expression::parse(license) {
+                invalid += 1;
+                println!("{} {}: {}", "!".red(), file.path, e);
+            }
+        }
+    }
+
+    let mut without_extension = 0;
+    for name in &license_files {
+        if Path::new(name).extension().is_none() {
+            without_extension += 1;
+            println!("{} LICENSES/{} has no file extension", "!".red(), name);
+        }
+    }
+
+    let present: BTreeSet<String> = license_files
+        .iter()
+        .map(|name| license_file_id(name).to_lowercase())
+        .collect();
+    let mut missing = 0;
+    let mut downloadable = 0;
+    for (used, files) in &used {
+        if !present.contains(&used.id.to_lowercase()) {
+            missing += 1;
+            if !is_license_ref(&used.id) {
+                downloadable += 1;
+            }
+            println!(
+                "{} Missing license text LICENSES/{}.txt, used in {}",
+                "!".red(),
+                used.id,
+                list_files(files)
+            );
+        }
+    }
+
+    let used_ids: BTreeSet<String> = used.keys().map(|used| used.id.to_lowercase()).collect();
+    let mut unused = 0;
+    for name in &license_files {
+        if !used_ids.contains(&license_file_id(name).to_lowercase()) {
+            unused += 1;
+            println!("{} Unused license text LICENSES/{}", "!".red(), name);
+        }
+    }
+
+    for (path, error) in &scan.read_errors {
+        println!("{} Could not read {}: {}", "!".red(), path, error);
+    }
+
+    let mut incomplete = 0;
+    let mut need_header = 0;
+    let mut need_annotation = 0;
+    for file in &scan.files {
+        let lacking: Vec<&str> = [
+            (file.info.copyrights.is_empty(), "copyright notice"),
+            (file.info.licenses.is_empty(), "license"),
+        ]
+        .iter()
+        .filter(|(lacks, _)| *lacks)
+        .map(|(_, what)| *what)
+        .collect();
+        if lacking.is_empty() {
+            continue;
+        }
+        incomplete += 1;
+        if file.can_carry_header {
+            need_header += 1;
+        } else {
+            need_annotation += 1;
+        }
+        println!(
+            "{} {} has no {}",
+            "!".red(),
+            file.path,
+            lacking.join(" or ")
+        );
+    }
+
+    let total = scan.files.len();
+    let with = |has: fn(&Info) -> bool| scan.files.iter().filter(|f| has(&f.info)).count();
+    let used_names: Vec<&str> = used.keys().map(|used| used.id.as_str()).collect();
+    println!("REUSE summary:");
+    println!("  Bad licenses: {}", bad);
+    println!("  Deprecated licenses: {}", deprecated);
+    println!("  Licenses without file extension: {}", without_extension);
+    println!("  Missing licenses: {}", missing);
+    println!("  Unused licenses: {}", unused);
+    println!("  Used licenses: {}", used_names.join(", "));
+    println!("  Read errors: {}", scan.read_errors.len());
+    println!(
+        "  Files with copyright information: {} / {}",
+        with(|info| !info.copyrights.is_empty()),
+        total
+    );
+    println!(
+        "  Files with license information: {} / {}",
+        with(|info| !info.licenses.is_empty()),
+        total
+    );
+
+    if unchecked {
+        println!(
+            "  Bad and deprecated licenses were not checked: the SPDX lists are not cached. Run without --lint once to cache them."
+        );
+    }
+    if need_header > 0 {
+        println!(
+            "  Run `gh-templates license header add` to add headers to {} file(s).",
+            need_header
+        );
+    }
+    if lint && need_annotation > 0 {
+        println!(
+            "  Run `gh-templates license reuse` to annotate {} file(s) that cannot carry a header in {}.",
+            need_annotation, REUSE_TOML
+        );
+    }
+    if lint && downloadable > 0 {
+        println!("  Run `gh-templates license reuse` to download the missing license texts.");
+    }
+
+    let problems = bad
+        + deprecated
+        + invalid
+        + without_extension
+        + missing
+        + unused
+        + scan.read_errors.len()
+        + incomplete;
+    if problems > 0 {
+        return Err(anyhow!(
+            "The repository does not follow the REUSE specification"
+        ));
+    }
+    println!(
+        "{} The repository follows the REUSE specification",
+        "✓".green()
+    );
+    Ok(())
+}
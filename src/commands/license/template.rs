@@ -0,0 +1,152 @@
+use regex::Regex;
+
+/// A piece of an SPDX `standardLicenseTemplate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Text that must appear as written
+    Text(String),
+    /// Replaceable text: `<<var;name="...";original="...";match="...">>`
+    Var {
+        name: String,
+        original: String,
+        pattern: String,
+    },
+    /// `<<beginOptional>>`: the text up to the matching end may be left out
+    BeginOptional,
+    EndOptional,
+}
+
+/// Splits an SPDX license template into text, replaceable text and optional markers.
+///
+/// Markup that cannot be read is kept as text.
+pub fn parse(template: &str) -> Vec<Segment> {
+    let attribute_re = Regex::new(r#"(\w+)="((?:[^"\\]|\\.)*)""#).unwrap();
+
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("<<") {
+        text.push_str(&rest[..start]);
+        let markup = &rest[start + 2..];
+
+        // Attribute values may contain `>>`, so a var ends at the first `">>`
+        let end = if markup.starts_with("var;") {
+            markup.find("\">>").map(|i| i + 1)
+        } else {
+            markup.find(">>")
+        };
+        let Some(end) = end else {
+            text.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let inner = &markup[..end];
+        let segment = if inner.starts_with("beginOptional") {
+            Some(Segment::BeginOptional)
+        } else if inner.starts_with("endOptional") {
+            Some(Segment::EndOptional)
+        } else if inner.starts_with("var;") {
+            let attribute = |key: &str| {
+                attribute_re
+                    .captures_iter(inner)
+                    .find(|caps| &caps[1] == key)
+                    .map(|caps| caps[2].replace("\\\"", "\""))
+                    .unwrap_or_default()
+            };
+            Some(Segment::Var {
+                name: attribute("name"),
+                original: attribute("original"),
+                pattern: attribute("match"),
+            })
+        } else {
+            None
+        };
+
+        match segment {
+            Some(segment) => {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(segment);
+            }
+            None => text.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &markup[end + 2..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+/// A replaceable part of a license template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub name: String,
+    /// The text of the standard license at this place
+    pub original: String,
+    /// Whether the variable is inside an optional section
+    pub optional: bool,
+}
+
+/// The variables of a template, in order of appearance.
+pub fn variables(segments: &[Segment]) -> Vec<Variable> {
+    let mut depth = 0usize;
+    let mut variables = Vec::new();
+    for segment in segments {
+        match segment {
+            Segment::BeginOptional => depth += 1,
+            Segment::EndOptional => depth = depth.saturating_sub(1),
+            Segment::Var { name, original, .. } => variables.push(Variable {
+                name: name.clone(),
+                original: original.clone(),
+                optional: depth > 0,
+            }),
+            Segment::Text(_) => {}
+        }
+    }
+    variables
+}
+
+/// The standard text of each outermost optional section, with variables as their originals.
+pub fn optional_sections(segments: &[Segment]) -> Vec<String> {
+    let mut depth = 0usize;
+    let mut sections = Vec::new();
+    let mut current = String::new();
+    for segment in segments {
+        match segment {
+            Segment::BeginOptional => depth += 1,
+            Segment::EndOptional if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let section = std::mem::take(&mut current);
+                    if !section.trim().is_empty() {
+                        sections.push(section);
+                    }
+                }
+            }
+            Segment::EndOptional => {}
+            Segment::Text(text) if depth > 0 => current.push_str(text),
+            Segment::Var { original, .. } if depth > 0 => current.push_str(original),
+            _ => {}
+        }
+    }
+    sections
+}
+
+/// A regex matching `text` however its whitespace is wrapped.
+pub fn flexible_pattern(text: &str) -> String {
+    let words: Vec<String> = text.split_whitespace().map(regex::escape).collect();
+    let mut pattern = words.join(r"\s+");
+    if text.trim_start().starts_with(|c: char| c.is_alphanumeric()) {
+        pattern.insert_str(0, r"\b");
+    }
+    if text.trim_end().ends_with(|c: char| c.is_alphanumeric()) {
+        pattern.push_str(r"\b");
+    }
+    pattern
+}
@@ -1,6 +1,7 @@
 use clap::Subcommand;
 
 pub mod base;
+pub mod cache;
 pub mod gitignore;
 pub mod issue;
 pub mod license;
@@ -23,6 +24,10 @@ pub enum CategoryCommand {
     #[command(subcommand)]
     /// The `Gitignore` subcommand provides functionality related to managing `.gitignore` templates.
     Gitignore(gitignore::Command),
+
+    #[command(subcommand)]
+    /// The `Cache` subcommand provides functionality related to inspecting the template caches.
+    Cache(cache::Command),
 }
 
 impl CategoryCommand {
@@ -32,6 +37,7 @@ impl CategoryCommand {
             Self::License(cmd) => cmd.execute(),
             Self::PR(cmd) => cmd.execute(),
             Self::Gitignore(cmd) => cmd.execute(),
+            Self::Cache(cmd) => cmd.execute(),
         }
     }
 }
@@ -28,7 +28,15 @@ fn main() -> anyhow::Result<()> {
     }
 
     match cli.category {
-        Some(category) => category.execute(),
+        // Findings of a check set the exit code on their own, they are not errors to print
+        Some(category) => {
+            category
+                .execute()
+                .map_err(|e| match e.downcast_ref::<commands::base::CheckFailed>() {
+                    Some(failed) => std::process::exit(failed.0),
+                    None => e,
+                })
+        }
         None => {
             // If no subcommand is provided, show help
             let mut cmd = Cli::command();
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use colored::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,6 +26,16 @@ pub struct CacheEntry<T> {
 pub struct Cache<T> {
     pub metadata: CacheMetadata,
     pub entries: HashMap<String, CacheEntry<T>>,
+    /// Keys of entries read from a system layer, which are never saved to the user layer
+    #[serde(skip)]
+    inherited: HashSet<String>,
+}
+
+/// The part of a cache that is saved: every entry that was not inherited.
+#[derive(Serialize)]
+struct SavedCache<'a, T> {
+    metadata: CacheMetadata,
+    entries: HashMap<&'a String, &'a CacheEntry<T>>,
 }
 
 #[allow(dead_code)]
@@ -33,6 +47,7 @@ impl<T> Cache<T> {
                 total_entries: 0,
             },
             entries: HashMap::new(),
+            inherited: HashSet::new(),
         }
     }
 
@@ -55,6 +70,7 @@ impl<T> Cache<T> {
             metadata,
         };
 
+        self.inherited.remove(&key);
         self.entries.insert(key, entry);
         self.update_metadata();
     }
@@ -72,6 +88,7 @@ impl<T> Cache<T> {
     }
 
     pub fn remove(&mut self, key: &str) -> Option<CacheEntry<T>> {
+        self.inherited.remove(key);
         let result = self.entries.remove(key);
         self.update_metadata();
         result
@@ -91,6 +108,7 @@ impl<T> Cache<T> {
 
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.inherited.clear();
         self.update_metadata();
     }
 
@@ -119,7 +137,7 @@ impl<T> Cache<T> {
     pub fn filter_by_metadata(&self, key: &str, value: &str) -> Vec<(&String, &T)> {
         self.entries
             .iter()
-            .filter(|(_, entry)| entry.metadata.get(key).map_or(false, |v| v == value))
+            .filter(|(_, entry)| entry.metadata.get(key).is_some_and(|v| v == value))
             .map(|(k, entry)| (k, &entry.data))
             .collect()
     }
@@ -133,15 +151,77 @@ impl<T> Cache<T> {
     }
 }
 
+/// System-wide cache directories searched after the per-user cache, in order.
+const SYSTEM_CACHE_DIRS: &[&str] = &["/usr/share/gitforge", "/var/cache/gitforge"];
+
+/// Overrides `SYSTEM_CACHE_DIRS` with a `PATH`-style list of directories.
+const SYSTEM_CACHE_DIRS_ENV: &str = "GITFORGE_SYSTEM_CACHE_DIRS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLayerKind {
+    /// Per-user cache; the only layer that is ever written to
+    User,
+    /// Pre-provisioned, read-only cache shared by every user on the host
+    System,
+}
+
+impl fmt::Display for CacheLayerKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CacheLayerKind::User => write!(f, "user"),
+            CacheLayerKind::System => write!(f, "system"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheLayer {
+    pub kind: CacheLayerKind,
+    pub dir: PathBuf,
+}
+
+impl CacheLayer {
+    pub fn is_writable(&self) -> bool {
+        self.kind == CacheLayerKind::User
+    }
+
+    pub fn cache_file(&self, cache_name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", cache_name))
+    }
+}
+
 pub struct CacheManager {
     cache_dir: PathBuf,
+    layers: Vec<CacheLayer>,
+    /// System cache files already reported as unreadable
+    skipped: RefCell<HashSet<PathBuf>>,
 }
 #[allow(dead_code)]
 impl CacheManager {
     /// Creates a new CacheManager instance.
     pub fn new() -> Result<Self> {
         let cache_dir = Self::get_cache_dir()?;
-        Ok(Self { cache_dir })
+
+        // The user layer always comes first so that fresh downloads shadow
+        // whatever was pre-provisioned on the host.
+        let mut layers = vec![CacheLayer {
+            kind: CacheLayerKind::User,
+            dir: cache_dir.clone(),
+        }];
+        layers.extend(
+            Self::get_system_cache_dirs()
+                .into_iter()
+                .map(|dir| CacheLayer {
+                    kind: CacheLayerKind::System,
+                    dir,
+                }),
+        );
+
+        Ok(Self {
+            cache_dir,
+            layers,
+            skipped: RefCell::new(HashSet::new()),
+        })
     }
 
     fn get_cache_dir() -> Result<PathBuf> {
@@ -153,6 +233,30 @@ impl CacheManager {
         Ok(cache_dir)
     }
 
+    fn get_system_cache_dirs() -> Vec<PathBuf> {
+        match env::var_os(SYSTEM_CACHE_DIRS_ENV) {
+            Some(value) => env::split_paths(&value)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect(),
+            None => SYSTEM_CACHE_DIRS.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    /// Returns the cache layers in lookup order (user first, then system).
+    pub fn layers(&self) -> &[CacheLayer] {
+        &self.layers
+    }
+
+    /// Finds the first layer holding `cache_name`, along with the file that satisfied the lookup.
+    ///
+    /// Entries missing from that file are still read from lower layers by [`Self::load_cache`].
+    pub fn resolve_cache_file(&self, cache_name: &str) -> Option<(&CacheLayer, PathBuf)> {
+        self.layers.iter().find_map(|layer| {
+            let cache_file = layer.cache_file(cache_name);
+            cache_file.is_file().then_some((layer, cache_file))
+        })
+    }
+
     pub fn ensure_cache_dir(&self) -> Result<()> {
         if !self.cache_dir.exists() {
             fs::create_dir_all(&self.cache_dir).with_context(|| {
@@ -162,30 +266,76 @@ impl CacheManager {
         Ok(())
     }
 
+    /// Loads a cache from every layer that has it, or an empty cache if none do.
+    ///
+    /// Each entry is looked up layer by layer, so a user cache holding only some
+    /// entries still falls through to the system layers for the others. The
+    /// metadata comes from the first layer that has the cache. A system layer whose
+    /// file cannot be read is skipped with a warning, since users cannot fix it.
     pub fn load_cache<T>(&self, cache_name: &str) -> Result<Cache<T>>
     where
         T: for<'de> Deserialize<'de>,
     {
-        let cache_file = self.cache_dir.join(format!("{}.json", cache_name));
+        let mut merged: Option<Cache<T>> = None;
 
-        if !cache_file.exists() {
-            return Ok(Cache::new());
+        for layer in &self.layers {
+            let cache_file = layer.cache_file(cache_name);
+            if !cache_file.is_file() {
+                continue;
+            }
+            let cache = match Self::read_cache_file::<T>(&cache_file) {
+                Ok(cache) => cache,
+                Err(e) if !layer.is_writable() => {
+                    if self.skipped.borrow_mut().insert(cache_file.clone()) {
+                        eprintln!(
+                            "{} Skipping system cache {}: {}",
+                            "⚠".yellow(),
+                            cache_file.display(),
+                            e.root_cause()
+                        );
+                    }
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let Some(merged) = merged.as_mut() else {
+                let mut cache = cache;
+                if !layer.is_writable() {
+                    cache.inherited = cache.entries.keys().cloned().collect();
+                }
+                merged = Some(cache);
+                continue;
+            };
+            for (key, entry) in cache.entries {
+                if !merged.entries.contains_key(&key) {
+                    merged.inherited.insert(key.clone());
+                    merged.entries.insert(key, entry);
+                }
+            }
         }
 
-        let content = fs::read_to_string(&cache_file)
+        Ok(merged.unwrap_or_else(Cache::new))
+    }
+
+    fn read_cache_file<T>(cache_file: &Path) -> Result<Cache<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let content = fs::read_to_string(cache_file)
             .with_context(|| format!("Failed to read cache file: {:?}", cache_file))?;
 
-        let cache: Cache<T> = serde_json::from_str(&content).map_err(|e| {
+        serde_json::from_str(&content).map_err(|e| {
             anyhow::anyhow!(
                 "Failed to parse cache file: {:?}\n\nCaused by:\n    {}",
                 cache_file,
                 e
             )
-        })?;
-
-        Ok(cache)
+        })
     }
 
+    /// Saves a cache to the user layer; system layers are never written to, and
+    /// entries read from them are left out.
     pub fn save_cache<T>(&self, cache_name: &str, cache: &Cache<T>) -> Result<()>
     where
         T: Serialize,
@@ -194,7 +344,19 @@ impl CacheManager {
 
         let cache_file = self.cache_dir.join(format!("{}.json", cache_name));
 
-        let content = serde_json::to_string_pretty(cache).context("Failed to serialize cache")?;
+        let entries: HashMap<&String, &CacheEntry<T>> = cache
+            .entries
+            .iter()
+            .filter(|(key, _)| !cache.inherited.contains(*key))
+            .collect();
+        let saved = SavedCache {
+            metadata: CacheMetadata {
+                last_updated: cache.metadata.last_updated,
+                total_entries: entries.len(),
+            },
+            entries,
+        };
+        let content = serde_json::to_string_pretty(&saved).context("Failed to serialize cache")?;
 
         fs::write(&cache_file, content)
             .with_context(|| format!("Failed to write cache file: {:?}", cache_file))?;
@@ -203,9 +365,10 @@ impl CacheManager {
     }
 
     pub fn cache_exists(&self, cache_name: &str) -> bool {
-        self.cache_dir.join(format!("{}.json", cache_name)).exists()
+        self.resolve_cache_file(cache_name).is_some()
     }
 
+    /// Removes a cache from the user layer; copies in system layers are left untouched.
     pub fn clear_cache(&self, cache_name: &str) -> Result<()> {
         let cache_file = self.cache_dir.join(format!("{}.json", cache_name));
 
@@ -230,11 +393,9 @@ impl CacheManager {
     }
 
     pub fn get_cache_size(&self, cache_name: &str) -> Result<u64> {
-        let cache_file = self.cache_dir.join(format!("{}.json", cache_name));
-
-        if !cache_file.exists() {
+        let Some((_, cache_file)) = self.resolve_cache_file(cache_name) else {
             return Ok(0);
-        }
+        };
 
         let metadata = fs::metadata(&cache_file)
             .with_context(|| format!("Failed to get cache file metadata: {:?}", cache_file))?;
@@ -242,26 +403,32 @@ impl CacheManager {
         Ok(metadata.len())
     }
 
+    /// Lists the caches available across all layers, without duplicates.
     pub fn list_caches(&self) -> Result<Vec<String>> {
-        if !self.cache_dir.exists() {
-            return Ok(vec![]);
-        }
-
         let mut caches = Vec::new();
 
-        for entry in fs::read_dir(&self.cache_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        for layer in &self.layers {
+            if !layer.dir.is_dir() {
+                continue;
+            }
 
-            if path.is_file() {
-                if let Some(name) = path.file_stem() {
-                    if let Some(name_str) = name.to_str() {
-                        caches.push(name_str.to_string());
+            for entry in fs::read_dir(&layer.dir)? {
+                let entry = entry?;
+                let path = entry.path();
+
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+                    if let Some(name) = path.file_stem() {
+                        if let Some(name_str) = name.to_str() {
+                            if !caches.iter().any(|c| c == name_str) {
+                                caches.push(name_str.to_string());
+                            }
+                        }
                     }
                 }
             }
         }
 
+        caches.sort();
         Ok(caches)
     }
 
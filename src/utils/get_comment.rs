@@ -1,39 +1,247 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+
+/// How comments are written in a file.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `// ...`
+    Slash,
+    /// `# ...`
+    Hash,
+    /// `/* ... */`
+    Block,
+    /// `<!-- ... -->`
+    Html,
+    /// `-- ...`
+    Dash,
+    /// `; ...`
+    Semicolon,
+    /// `% ...`
+    Percent,
+    /// `" ...`, for Vim script
+    Quote,
+    /// `.. ...`, for reStructuredText
+    Rst,
+}
+
+/// Comment style of source code, by file extension.
+const SOURCE_EXTENSIONS: &[(CommentStyle, &[&str])] = &[
+    (
+        CommentStyle::Slash,
+        &[
+            "c", "h", "cc", "cpp", "cxx", "hh", "hpp", "hxx", "cs", "d", "dart", "fs", "fsx", "go",
+            "gradle", "groovy", "java", "js", "jsx", "cjs", "mjs", "kt", "kts", "less", "m", "mm",
+            "php", "proto", "rs", "sass", "scala", "scss", "sol", "swift", "ts", "tsx", "mts",
+            "cts", "v", "zig",
+        ],
+    ),
+    (CommentStyle::Block, &["css"]),
+    (
+        CommentStyle::Hash,
+        &[
+            "bash",
+            "bzl",
+            "cmake",
+            "coffee",
+            "cr",
+            "ex",
+            "exs",
+            "fish",
+            "gd",
+            "jl",
+            "mk",
+            "nim",
+            "nix",
+            "pl",
+            "pm",
+            "ps1",
+            "psm1",
+            "py",
+            "pyi",
+            "pyx",
+            "r",
+            "rake",
+            "rb",
+            "sh",
+            "tcl",
+            "tf",
+            "hcl",
+            "zsh",
+            "dockerfile",
+            "gemspec",
+            "star",
+        ],
+    ),
+    (
+        CommentStyle::Html,
+        &["html", "htm", "xhtml", "xsl", "xslt", "vue", "svelte"],
+    ),
+    (
+        CommentStyle::Dash,
+        &[
+            "sql", "lua", "hs", "lhs", "elm", "ada", "adb", "ads", "purs",
+        ],
+    ),
+    (
+        CommentStyle::Semicolon,
+        &[
+            "lisp", "lsp", "el", "clj", "cljs", "cljc", "scm", "rkt", "asm",
+        ],
+    ),
+    (CommentStyle::Percent, &["sty", "cls", "erl", "hrl", "mat"]),
+    (CommentStyle::Quote, &["vim"]),
+];
+
+/// Comment style of configuration, data and documentation files, by file extension.
+const OTHER_EXTENSIONS: &[(CommentStyle, &[&str])] = &[
+    (
+        CommentStyle::Hash,
+        &["cfg", "conf", "properties", "tfvars", "toml", "yaml", "yml"],
+    ),
+    (
+        CommentStyle::Html,
+        &[
+            "xml", "xsd", "svg", "md", "markdown", "plist", "csproj", "fsproj", "vbproj", "props",
+            "targets",
+        ],
+    ),
+    (CommentStyle::Semicolon, &["ini", "edn"]),
+    (CommentStyle::Percent, &["tex", "bib"]),
+    (CommentStyle::Rst, &["rst"]),
+];
+
+/// Comment style of source code recognised by file name rather than extension.
+const SOURCE_FILE_NAMES: &[(CommentStyle, &[&str])] = &[
+    (
+        CommentStyle::Hash,
+        &[
+            "Dockerfile",
+            "Containerfile",
+            "Makefile",
+            "GNUmakefile",
+            "CMakeLists.txt",
+            "Gemfile",
+            "Rakefile",
+            "Vagrantfile",
+            "Justfile",
+            "justfile",
+            "BUILD",
+            "WORKSPACE",
+        ],
+    ),
+    (CommentStyle::Slash, &["Jenkinsfile"]),
+];
+
+/// Comment style of configuration files recognised by file name rather than extension.
+const OTHER_FILE_NAMES: &[(CommentStyle, &[&str])] = &[(
+    CommentStyle::Hash,
+    &[
+        "Pipfile",
+        ".gitignore",
+        ".gitattributes",
+        ".dockerignore",
+        ".editorconfig",
+    ],
+)];
+
+fn lookup(tables: &[&[(CommentStyle, &[&str])]], key: &str) -> Option<CommentStyle> {
+    tables
+        .iter()
+        .flat_map(|table| table.iter())
+        .find(|(_, keys)| keys.contains(&key))
+        .map(|(style, _)| *style)
+}
+
+impl CommentStyle {
+    /// The comment style of a file, from its name or else its extension, and whether
+    /// the file is source code rather than configuration or documentation.
+    pub fn for_path(path: &Path) -> Option<(Self, bool)> {
+        let name = path.file_name()?.to_string_lossy();
+        if let Some(style) = lookup(&[SOURCE_FILE_NAMES], &name) {
+            return Some((style, true));
+        }
+        if let Some(style) = lookup(&[OTHER_FILE_NAMES], &name) {
+            return Some((style, false));
+        }
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        let source = lookup(&[SOURCE_EXTENSIONS], &ext).is_some();
+        Self::for_extension(&ext).map(|style| (style, source))
+    }
+
+    /// The comment style of files with the given extension.
+    pub fn for_extension(ext: &str) -> Option<Self> {
+        lookup(&[SOURCE_EXTENSIONS, OTHER_EXTENSIONS], ext)
+    }
+
+    /// Writes `lines` as a comment, each line ending with `newline`.
+    pub fn comment(&self, lines: &[String], newline: &str) -> String {
+        let (start, prefix, end) = match self {
+            CommentStyle::Slash => (None, "// ", None),
+            CommentStyle::Hash => (None, "# ", None),
+            CommentStyle::Block => (Some("/*"), " * ", Some(" */")),
+            CommentStyle::Html => (Some("<!--"), "", Some("-->")),
+            CommentStyle::Dash => (None, "-- ", None),
+            CommentStyle::Semicolon => (None, ";; ", None),
+            CommentStyle::Percent => (None, "% ", None),
+            CommentStyle::Quote => (None, "\" ", None),
+            CommentStyle::Rst => (Some(".."), "   ", None),
+        };
+
+        let mut comment = String::new();
+        for line in start
+            .map(str::to_string)
+            .into_iter()
+            .chain(lines.iter().map(|line| format!("{}{}", prefix, line)))
+            .chain(end.map(str::to_string))
+        {
+            comment.push_str(line.trim_end());
+            comment.push_str(newline);
+        }
+        comment
+    }
+
+    /// The marker starting a line comment, for styles that have one.
+    fn line_marker(&self) -> Option<&'static str> {
+        match self {
+            CommentStyle::Slash => Some("//"),
+            CommentStyle::Hash => Some("#"),
+            CommentStyle::Dash => Some("--"),
+            CommentStyle::Semicolon => Some(";"),
+            CommentStyle::Percent => Some("%"),
+            CommentStyle::Quote => Some("\""),
+            CommentStyle::Rst => Some(".."),
+            CommentStyle::Block | CommentStyle::Html => None,
+        }
+    }
+}
+
+/// Whether `name` is a file name with a known comment style, such as `.gitignore`.
+pub fn is_known_file_name(name: &str) -> bool {
+    lookup(&[SOURCE_FILE_NAMES, OTHER_FILE_NAMES], name).is_some()
+}
+
 // extracts first line comment from a file content based on its extension
 pub fn extract_comment(content: &str, ext: &str) -> Option<String> {
-    match ext {
-        "rs" | "c" | "cpp" | "js" | "ts" | "java" => {
-            // Handle block comments /* ... */
-            if let Some(start) = content.find("/*") {
-                if let Some(end) = content.find("*/") {
-                    let comment = &content[start + 2..end];
-                    return Some(comment.trim().to_string());
-                }
-            }
-            // Handle line comments //
-            if let Some(start) = content.find("//") {
-                let comment = &content[start + 2..];
-                return Some(comment.trim().to_string());
-            }
-            None
-        }
-        "yml" | "py" | "sh" | "rb" => {
-            // Handle line comments #
-            if let Some(start) = content.find('#') {
-                let comment = &content[start + 1..];
+    let style = CommentStyle::for_extension(ext)?;
+
+    // Handle block comments /* ... */ and <!-- ... -->
+    let block = match style {
+        CommentStyle::Slash | CommentStyle::Block => Some(("/*", "*/")),
+        CommentStyle::Html => Some(("<!--", "-->")),
+        _ => None,
+    };
+    if let Some((open, close)) = block {
+        if let Some(start) = content.find(open) {
+            if let Some(end) = content.find(close) {
+                let comment = &content[start + open.len()..end];
                 return Some(comment.trim().to_string());
             }
-            None
         }
-        "md" | "html" => {
-            // Handle HTML comments <!-- ... -->
-            if let Some(start) = content.find("<!--") {
-                if let Some(end) = content.find("-->") {
-                    let comment = &content[start + 4..end];
-                    return Some(comment.trim().to_string());
-                }
-            }
-            None
-        }
-        _ => None,
     }
+
+    // Handle line comments
+    let marker = style.line_marker()?;
+    let start = content.find(marker)?;
+    Some(content[start + marker.len()..].trim().to_string())
 }
@@ -140,8 +140,8 @@ impl ManifestNavigator {
                 }
             }
             // Handle list items
-            else if trimmed.starts_with("- ") {
-                let filename = trimmed[2..].trim().trim_matches('"').trim_matches('\'');
+            else if let Some(item) = trimmed.strip_prefix("- ") {
+                let filename = item.trim().trim_matches('"').trim_matches('\'');
                 if !filename.is_empty() {
                     if current_subsection.is_empty() {
                         // Direct template list
@@ -5,4 +5,5 @@ pub mod manifest_navigator;
 pub mod pattern;
 pub mod pretty_print;
 pub mod progress;
+pub mod prompt;
 pub mod remote;
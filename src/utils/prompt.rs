@@ -0,0 +1,24 @@
+use std::io::{self, Write};
+
+/// Prints `prompt` and reads one line of input, trimmed.
+///
+/// Returns `None` when stdin is closed or cannot be read, so callers can tell
+/// an empty answer from no answer at all.
+pub fn read_line(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    let _ = io::stdout().flush();
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => {
+            println!();
+            None
+        }
+        Ok(_) => Some(input.trim().to_string()),
+    }
+}
+
+/// Asks a yes/no question, defaulting to yes; no input at all counts as no.
+pub fn confirm(question: &str) -> bool {
+    read_line(&format!("{} [Y/n] ", question))
+        .is_some_and(|answer| matches!(answer.to_lowercase().as_str(), "" | "y" | "yes"))
+}
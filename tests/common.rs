@@ -1,2 +1,4 @@
+#![allow(dead_code)]
+
 #[path = "common/test_utils.rs"]
 mod test_utils;
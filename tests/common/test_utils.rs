@@ -1,14 +1,14 @@
-use std::path::PathBuf;
+use assert_cmd::Command as AssertCommand;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
 pub fn setup_test_env() -> TempDir {
-    let temp_dir = TempDir::new().unwrap();
-    temp_dir
+    TempDir::new().unwrap()
 }
 
 pub fn create_git_repo(path: &PathBuf) {
     std::process::Command::new("git")
-        .args(&["init"])
+        .args(["init"])
         .current_dir(path)
         .output()
         .unwrap();
@@ -22,3 +22,41 @@ pub fn assert_file_contains(file_path: &PathBuf, content: &str) {
 pub fn assert_file_exists(file_path: &PathBuf) {
     assert!(file_path.exists(), "File {:?} should exist", file_path);
 }
+
+/// Writes a fresh cache file in the on-disk format used by `CacheManager`.
+pub fn seed_cache(dir: &Path, cache_name: &str, entries: &[(&str, serde_json::Value)]) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let entries: serde_json::Map<String, serde_json::Value> = entries
+        .iter()
+        .map(|(key, data)| {
+            (
+                key.to_string(),
+                serde_json::json!({ "data": data, "timestamp": now, "metadata": {} }),
+            )
+        })
+        .collect();
+
+    let cache = serde_json::json!({
+        "metadata": { "last_updated": now, "total_entries": entries.len() },
+        "entries": entries,
+    });
+
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(
+        dir.join(format!("{}.json", cache_name)),
+        serde_json::to_string_pretty(&cache).unwrap(),
+    )
+    .unwrap();
+}
+
+/// Builds a `gh-templates` command whose user and system cache layers live under temporary directories.
+pub fn isolated_command(home: &Path, system_cache_dir: &Path) -> AssertCommand {
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.env("HOME", home)
+        .env("GITFORGE_SYSTEM_CACHE_DIRS", system_cache_dir);
+    cmd
+}
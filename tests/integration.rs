@@ -16,3 +16,6 @@ mod issue_tests;
 
 #[path = "integration/pr_tests.rs"]
 mod pr_tests;
+
+#[path = "integration/cache_tests.rs"]
+mod cache_tests;
@@ -0,0 +1,195 @@
+use predicates::prelude::*;
+use serde_json::json;
+
+// Import utility functions
+use crate::common::test_utils::{isolated_command, seed_cache, setup_test_env};
+
+/**
+Integration tests for the `gh-templates` cache subcommand.
+
+This test suite covers the following scenarios:
+
+- `test_cache_info_lists_layers`: Verifies that `cache info` lists the user layer followed by the system layers.
+- `test_cache_info_system_layer_satisfies_lookup`: Ensures a cache present only in a system layer is reported as satisfied by that layer.
+- `test_cache_info_user_layer_shadows_system`: Checks that the user layer wins over a system layer holding the same cache.
+- `test_cache_user_layer_falls_back_to_system_entries`: Ensures entries missing from the user layer are still read from the system layer, and never copied into the user layer.
+- `test_cache_info_missing_cache`: Confirms that a cache absent from every layer is reported as not cached.
+- `test_gitignore_list_from_system_cache`: Ensures commands read templates pre-provisioned in a system layer without writing to it.
+- `test_cache_skips_corrupt_system_layer`: Ensures a corrupt file in a read-only system layer is skipped with a warning instead of failing the command.
+
+Each test points `HOME` and `GITFORGE_SYSTEM_CACHE_DIRS` at temporary directories so no real cache is touched.
+*/
+
+// --------     INFO COMMAND TESTS     --------
+
+#[test]
+fn test_cache_info_lists_layers() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let system_dir = temp_dir.path().join("system");
+
+    isolated_command(&home, &system_dir)
+        .args(["cache", "info"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. user"))
+        .stdout(predicate::str::contains("2. system"))
+        .stdout(predicate::str::contains("read-only, not present"))
+        .stdout(predicate::str::contains("No caches found in any layer."));
+}
+
+#[test]
+fn test_cache_info_system_layer_satisfies_lookup() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let system_dir = temp_dir.path().join("system");
+
+    seed_cache(
+        &system_dir,
+        "gitignore_templates",
+        &[("rust", json!("Rust.gitignore"))],
+    );
+
+    isolated_command(&home, &system_dir)
+        .args(["cache", "info", "gitignore_templates"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"gitignore_templates\s+system").unwrap())
+        .stdout(predicate::str::contains("1 entries"));
+}
+
+#[test]
+fn test_cache_info_user_layer_shadows_system() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let user_dir = home.join(".local").join("share").join("gh-templates");
+    let system_dir = temp_dir.path().join("system");
+
+    seed_cache(
+        &system_dir,
+        "gitignore_templates",
+        &[("rust", json!("Rust.gitignore"))],
+    );
+    seed_cache(
+        &user_dir,
+        "gitignore_templates",
+        &[
+            ("rust", json!("Rust.gitignore")),
+            ("python", json!("Python.gitignore")),
+        ],
+    );
+
+    isolated_command(&home, &system_dir)
+        .args(["cache", "info"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"gitignore_templates\s+user").unwrap())
+        .stdout(predicate::str::contains("2 entries"))
+        .stdout(predicate::str::contains("falls back to"));
+}
+
+#[test]
+fn test_cache_user_layer_falls_back_to_system_entries() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let user_dir = home.join(".local").join("share").join("gh-templates");
+    let system_dir = temp_dir.path().join("system");
+
+    seed_cache(
+        &system_dir,
+        "gitignore_templates",
+        &[
+            ("rust", json!("Rust.gitignore")),
+            ("python", json!("Python.gitignore")),
+            ("global-macos", json!("Global/macOS.gitignore")),
+        ],
+    );
+    seed_cache(
+        &user_dir,
+        "gitignore_templates",
+        &[("rust", json!("Rust.gitignore"))],
+    );
+
+    isolated_command(&home, &system_dir)
+        .args(["cache", "info", "gitignore_templates"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"gitignore_templates\s+user").unwrap())
+        .stdout(predicate::str::contains("3 entries"));
+
+    isolated_command(&home, &system_dir)
+        .args(["gitignore", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("python"))
+        .stdout(predicate::str::contains("Global/macOS.gitignore"));
+
+    // The user layer keeps only its own entry
+    let user_cache: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(user_dir.join("gitignore_templates.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(user_cache["entries"].as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn test_cache_info_missing_cache() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let system_dir = temp_dir.path().join("system");
+
+    isolated_command(&home, &system_dir)
+        .args(["cache", "info", "spdx_license_cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("not cached in any layer"));
+}
+
+#[test]
+fn test_gitignore_list_from_system_cache() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let system_dir = temp_dir.path().join("system");
+
+    seed_cache(
+        &system_dir,
+        "gitignore_templates",
+        &[
+            ("rust", json!("Rust.gitignore")),
+            ("global-macos", json!("Global/macOS.gitignore")),
+        ],
+    );
+
+    isolated_command(&home, &system_dir)
+        .args(["gitignore", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rust"))
+        .stdout(predicate::str::contains("Global/macOS.gitignore"));
+
+    // Reads must never populate the user layer from a system layer
+    assert!(!home.join(".local").exists());
+}
+
+#[test]
+fn test_cache_skips_corrupt_system_layer() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+    let user_dir = home.join(".local").join("share").join("gh-templates");
+    let system_dir = temp_dir.path().join("system");
+
+    std::fs::create_dir_all(&system_dir).unwrap();
+    std::fs::write(system_dir.join("gitignore_templates.json"), "{ not json").unwrap();
+    seed_cache(
+        &user_dir,
+        "gitignore_templates",
+        &[("rust", json!("Rust.gitignore"))],
+    );
+
+    isolated_command(&home, &system_dir)
+        .args(["gitignore", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rust"))
+        .stderr(predicate::str::contains("Skipping system cache"));
+}
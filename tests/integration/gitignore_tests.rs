@@ -4,7 +4,8 @@ use std::fs;
 
 // Import utility functions
 use crate::common::test_utils::{
-    assert_file_contains, assert_file_exists, create_git_repo, setup_test_env,
+    assert_file_contains, assert_file_exists, create_git_repo, isolated_command, seed_cache,
+    setup_test_env,
 };
 
 /**
@@ -22,6 +23,52 @@ use crate::common::test_utils::{
 - `test_gitignore_add_no_template`: Ensures that running add without templates or --all returns an error.
 - `test_gitignore_add_update_cache`: Ensures that the add command with --update-cache refreshes the cache.
 - `test_gitignore_add_valid_and_invalid_template`: Tests adding a valid template alongside an invalid one, ensuring the valid template is added while the invalid one is reported.
+- `test_gitignore_add_merge_deduplicates`: Verifies that merging overlapping templates keeps each pattern only once.
+- `test_gitignore_add_compact`: Ensures `--compact` strips comments and blank lines but keeps section headers.
+- `test_gitignore_add_append_replaces_section`: Ensures re-adding a template with `--append` replaces its managed section in place.
+- `test_gitignore_remove_section`: Verifies that `remove` strips a template's managed section and keeps hand-written lines.
+- `test_gitignore_remove_legacy_section`: Checks that sections written with the old single-header format can be removed.
+- `test_gitignore_remove_legacy_section_keeps_user_lines`: Ensures that hand-written lines after a legacy section survive its removal.
+- `test_gitignore_remove_restores_shared_patterns`: Ensures removing one of two overlapping templates brings back the patterns the other one shared with it.
+- `test_gitignore_remove_missing_template`: Confirms that removing a template without a section returns an error.
+- `test_gitignore_detect_stacks`: Verifies that `detect` suggests templates from marker files and names the file that triggered each one.
+- `test_gitignore_detect_no_markers`: Ensures `detect` reports when no marker files are found.
+- `test_gitignore_detect_skips_unreadable_dirs`: Ensures an unreadable subdirectory does not stop the detection.
+- `test_gitignore_add_auto`: Checks that `add --auto` adds the detected templates.
+- `test_gitignore_add_workspace_plan`: Verifies that `--workspace --dry-run` discovers Cargo and pnpm members, places shared templates at the root and writes nothing.
+- `test_gitignore_add_workspace_skips_unreadable_dirs`: Ensures an unreadable directory does not stop the workspace member discovery.
+- `test_gitignore_add_workspace`: Checks that `--workspace` writes the root and per-member .gitignore files.
+- `test_gitignore_add_workspace_no_members`: Ensures `--workspace` fails when no workspace members are declared.
+- `test_gitignore_add_target_local_exclude`: Verifies that `--target local-exclude` writes a managed section into .git/info/exclude and keeps existing lines.
+- `test_gitignore_add_target_global`: Verifies that `--target global` registers a core.excludesFile when none is set and writes to it.
+- `test_gitignore_add_target_conflicts_with_output`: Ensures `--target` cannot be combined with `--output`.
+- `test_gitignore_check_current_rules`: Verifies that `check` honours nested .gitignore files, negations, directory patterns and .git/info/exclude, and lists matching tracked files.
+- `test_gitignore_check_candidate`: Ensures `check --candidate` only reports the files the candidate would newly ignore, without writing it.
+- `test_gitignore_diff_against_upstream`: Verifies that `diff` shows patterns missing from a section, patterns not in the upstream template and local-only patterns.
+- `test_gitignore_diff_no_sections`: Ensures `diff` without templates fails when the file has no template sections.
+- `test_gitignore_diff_missing_file`: Confirms that `diff` reports a missing .gitignore file.
+- `test_gitignore_explain_section`: Verifies that `explain` reports the deciding pattern, its location and the template section it came from, including nested files and legacy headers.
+- `test_gitignore_explain_parent_excluded`: Checks that `explain` names the excluded parent directory and the negation that cannot apply.
+- `test_gitignore_explain_not_ignored`: Ensures `explain` reports re-included and unmatched paths, and rules from .git/info/exclude.
+- `test_gitignore_export_dockerignore`: Verifies that exporting to .dockerignore prefixes unanchored patterns so they match at any depth and warns about directory-only patterns.
+- `test_gitignore_export_helmignore`: Ensures patterns Helm cannot express are commented out with a warning and directory globs become directory patterns.
+- `test_gitignore_export_npmignore_stdout`: Checks that `--stdout` prints without writing and that npm's always-published files are flagged.
+- `test_gitignore_export_no_overwrite`: Confirms that an existing export is not overwritten without `--force`.
+- `test_gitignore_lint_reports_issues`: Verifies that `lint` reports duplicates, shadowed patterns, dead negations, meaningless lines and trailing whitespace, and exits with code 4.
+- `test_gitignore_lint_json`: Ensures `lint --json` prints machine-readable issues.
+- `test_gitignore_lint_fix`: Checks that `lint --fix` rewrites the file so it lints clean.
+- `test_gitignore_lint_single_char_wildcard`: Ensures a `?`-led pattern only shadows patterns it fully covers, so `--fix` keeps unrelated lines.
+- `test_gitignore_lint_clean`: Confirms a file without issues passes.
+- `test_gitignore_suggest_dry_run`: Verifies that `suggest --dry-run` recommends templates that cover untracked paths, clusters the rest into patterns, leaves likely source files alone and writes nothing.
+- `test_gitignore_suggest_interactive`: Checks that only the suggestions accepted on stdin are written, templates as sections and patterns after them.
+- `test_gitignore_suggest_yes`: Ensures `--yes` accepts every suggestion.
+- `test_gitignore_suggest_after_legacy_section`: Ensures custom patterns are written outside a trailing legacy section, so removing that section keeps them.
+- `test_gitignore_suggest_nothing_untracked`: Confirms `suggest` reports when there are no untracked files.
+- `test_gitignore_toptal_backend`: Verifies that the gitignore.io backend lists and downloads templates from a mirror and resolves GitHub names through the alias table.
+- `test_gitignore_add_merge_keeps_reignore_after_negation`: Ensures a pattern ignored again after another template negated it is kept and reported.
+- `test_gitignore_add_merge_keeps_repeat_after_other_negation`: Ensures a pattern repeated after a negation of a narrower pattern is kept.
+- `test_gitignore_unknown_backend`: Ensures an unknown backend name is reported.
+- `test_gitignore_list_aliases`: Checks that `list --aliases` prints the alias table.
 - `test_gitignore_list_default`: Ensures the list command displays popular templates.
 - `test_gitignore_list_popular`: Ensures the list command with --popular displays popular templates.
 - `test_gitignore_list_global`: Ensures the list command with --global displays global templates.
@@ -49,7 +96,7 @@ fn test_gitignore_add_rust() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust"])
+    cmd.args(["gitignore", "add", "rust"])
         .assert()
         .success()
         .stdout(
@@ -69,7 +116,7 @@ fn test_gitignore_add_rust_gitignore() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust.gitignore"])
+    cmd.args(["gitignore", "add", "rust.gitignore"])
         .assert()
         .success()
         .stdout(
@@ -89,7 +136,7 @@ fn test_gitignore_add_multiple() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust", "python"])
+    cmd.args(["gitignore", "add", "rust", "python"])
         .assert()
         .success()
         .stdout(
@@ -109,7 +156,7 @@ fn test_gitignore_add_with_dir() {
     fs::create_dir_all(&target_dir).unwrap();
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&[
+    cmd.args([
         "gitignore",
         "add",
         "rust",
@@ -136,7 +183,7 @@ fn test_gitignore_add_append() {
     // First add Rust template
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust"])
+    cmd.args(["gitignore", "add", "rust"])
         .assert()
         .success()
         .stdout(
@@ -149,7 +196,7 @@ fn test_gitignore_add_append() {
     // Then append Python template
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "python", "--append"])
+    cmd.args(["gitignore", "add", "python", "--append"])
         .assert()
         .success()
         .stdout(
@@ -175,7 +222,7 @@ fn test_gitignore_add_force_overwrite() {
     // Try to add without force (should fail)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust"])
+    cmd.args(["gitignore", "add", "rust"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("already exists"));
@@ -183,7 +230,7 @@ fn test_gitignore_add_force_overwrite() {
     // Try with force flag (should succeed)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust", "--force"])
+    cmd.args(["gitignore", "add", "rust", "--force"])
         .assert()
         .success();
 
@@ -202,7 +249,7 @@ fn test_gitignore_add_all() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "--all"])
+    cmd.args(["gitignore", "add", "--all"])
         .assert()
         .success()
         .stdout(
@@ -224,7 +271,7 @@ fn test_gitignore_add_invalid_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "not-a-template"])
+    cmd.args(["gitignore", "add", "not-a-template"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("not found").or(predicate::str::contains("Unknown")));
@@ -239,7 +286,7 @@ fn test_gitignore_add_no_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add"])
+    cmd.args(["gitignore", "add"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("No gitignore template specified"));
@@ -254,7 +301,7 @@ fn test_gitignore_add_update_cache() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust", "--update-cache"])
+    cmd.args(["gitignore", "add", "rust", "--update-cache"])
         .assert()
         .success()
         .stdout(
@@ -272,7 +319,7 @@ fn test_gitignore_add_valid_and_invalid_template() {
     // Try to add one valid and one invalid template
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust", "not-a-template"])
+    cmd.args(["gitignore", "add", "rust", "not-a-template"])
         .assert()
         .success()
         .stdout(
@@ -297,7 +344,7 @@ fn test_gitignore_add_default_with_output() {
     // Add rust template with output file .gitignore
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "add", "rust", "-o", ".gitignore"])
+    cmd.args(["gitignore", "add", "rust", "-o", ".gitignore"])
         .assert()
         .success()
         .stdout(
@@ -318,7 +365,7 @@ fn test_gitignore_add_multiple_templates_uneven_output_files() {
     // Pass 3 output files for 2 templates (should error)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&[
+    cmd.args([
         "gitignore",
         "add",
         "python",
@@ -335,6 +382,1420 @@ fn test_gitignore_add_multiple_templates_uneven_output_files() {
     ));
 }
 
+#[test]
+fn test_gitignore_add_merge_deduplicates() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args([
+        "gitignore",
+        "add",
+        "python",
+        "jupyternotebooks",
+        "global/macos",
+        "-o",
+        ".gitignore",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("duplicate pattern(s)"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN python.gitignore ====="));
+    assert!(content.contains("# ===== BEGIN jupyternotebooks.gitignore ====="));
+    assert!(content.contains("# ===== BEGIN global/macos.gitignore ====="));
+
+    let mut seen = std::collections::HashSet::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        assert!(
+            seen.insert(line),
+            "Duplicate pattern '{}' in merged file",
+            line
+        );
+    }
+}
+
+#[test]
+fn test_gitignore_add_compact() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "add", "rust", "python", "--compact"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN rust.gitignore ====="));
+    assert!(content.contains("# ===== BEGIN python.gitignore ====="));
+    for line in content.lines() {
+        if line.starts_with('#') {
+            assert!(line.starts_with("# ====="), "Unexpected comment: {}", line);
+        }
+    }
+}
+
+#[test]
+fn test_gitignore_add_append_replaces_section() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# hand-written\nsecrets.env\n",
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+        cmd.current_dir(&temp_path);
+        cmd.args(["gitignore", "add", "rust", "--append"])
+            .assert()
+            .success();
+    }
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.starts_with("# hand-written\nsecrets.env\n"));
+    assert_eq!(
+        content
+            .matches("# ===== BEGIN rust.gitignore =====")
+            .count(),
+        1
+    );
+    assert_eq!(
+        content.matches("# ===== END rust.gitignore =====").count(),
+        1
+    );
+}
+
+// --------     REMOVE COMMAND TESTS     --------
+
+#[test]
+fn test_gitignore_remove_section() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# hand-written\nsecrets.env\n\n\
+         # ===== BEGIN python.gitignore =====\n__pycache__/\n# ===== END python.gitignore =====\n\n\
+         # ===== BEGIN rust.gitignore =====\n/target\n# ===== END rust.gitignore =====\n\n\
+         local.log\n",
+    )
+    .unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "remove", "python"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed gitignore template: python",
+        ));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(!content.contains("python.gitignore"));
+    assert!(!content.contains("__pycache__/"));
+    assert!(content.contains("secrets.env"));
+    assert!(content.contains("# ===== BEGIN rust.gitignore ====="));
+    assert!(content.contains("local.log"));
+}
+
+#[test]
+fn test_gitignore_remove_legacy_section() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# ===== rust.gitignore =====\n/target\n\n# ===== Global/macOS.gitignore =====\n.DS_Store\n",
+    )
+    .unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "remove", "global/macos"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(content, "# ===== rust.gitignore =====\n/target\n");
+}
+
+#[test]
+fn test_gitignore_remove_legacy_section_keeps_user_lines() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    let gitignore = temp_path.join(".gitignore");
+    let remove = |template: &str| {
+        let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+        cmd.current_dir(&temp_path);
+        cmd.args(["gitignore", "remove", template])
+            .assert()
+            .success();
+    };
+
+    // Old versions ended each section with two blank lines
+    fs::write(
+        &gitignore,
+        "# ===== Rust.gitignore =====\n/target\n\n# Backups\n*.rs.bk\n\n\n# mine\nsecrets.env\n",
+    )
+    .unwrap();
+    remove("rust");
+    assert_eq!(
+        fs::read_to_string(&gitignore).unwrap(),
+        "# mine\nsecrets.env\n"
+    );
+
+    // Without them, the section ends at its first blank line
+    fs::write(
+        &gitignore,
+        "# ===== Rust.gitignore =====\n/target\n\nsecrets.env\n",
+    )
+    .unwrap();
+    remove("rust");
+    assert_eq!(fs::read_to_string(&gitignore).unwrap(), "secrets.env\n");
+}
+
+#[test]
+fn test_gitignore_remove_missing_template() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), "secrets.env\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "remove", "python"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no managed section"));
+
+    assert_file_contains(&temp_path.join(".gitignore"), "secrets.env");
+}
+
+#[test]
+fn test_gitignore_remove_restores_shared_patterns() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    seed_cache(
+        &cache_dir,
+        "gitignore_templates",
+        &[
+            ("p", serde_json::json!("P.gitignore")),
+            ("q", serde_json::json!("Q.gitignore")),
+        ],
+    );
+    seed_cache(
+        &cache_dir,
+        "gitignore_template_bodies",
+        &[
+            (
+                "P.gitignore",
+                serde_json::json!("*.pyc\n__pycache__/\n.venv\n"),
+            ),
+            (
+                "Q.gitignore",
+                serde_json::json!("*.pyc\n**/__pycache__/\n.venv/\n"),
+            ),
+        ],
+    );
+    // As `gitignore add p q` writes it, with q's shared patterns merged away
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# ===== BEGIN p.gitignore =====\n*.pyc\n__pycache__/\n.venv\n# ===== END p.gitignore =====\n\n\
+         # ===== BEGIN q.gitignore =====\n.venv/\n# ===== END q.gitignore =====\n",
+    )
+    .unwrap();
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "remove", "p"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored patterns"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(
+        content,
+        "# ===== BEGIN q.gitignore =====\n*.pyc\n**/__pycache__/\n.venv/\n# ===== END q.gitignore =====\n"
+    );
+}
+
+// --------     DETECT COMMAND TESTS     --------
+
+fn seed_gitignore_cache(home: &std::path::Path) {
+    seed_cache(
+        &home.join(".local").join("share").join("gh-templates"),
+        "gitignore_templates",
+        &[
+            ("rust", serde_json::json!("Rust.gitignore")),
+            ("python", serde_json::json!("Python.gitignore")),
+            ("node", serde_json::json!("Node.gitignore")),
+            (
+                "global-visualstudiocode",
+                serde_json::json!("Global/VisualStudioCode.gitignore"),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_gitignore_detect_stacks() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(temp_path.join(".vscode")).unwrap();
+    fs::create_dir_all(temp_path.join("web")).unwrap();
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(temp_path.join("web").join("package.json"), "{}").unwrap();
+    seed_gitignore_cache(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "detect"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"rust\s+Rust.gitignore\s+\(found Cargo.toml\)").unwrap())
+        .stdout(predicate::str::contains("Node.gitignore"))
+        .stdout(predicate::str::contains("web/package.json"))
+        .stdout(predicate::str::contains(
+            "Global/VisualStudioCode.gitignore",
+        ))
+        .stdout(predicate::str::contains("Python.gitignore").not());
+}
+
+#[test]
+fn test_gitignore_detect_no_markers() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    seed_gitignore_cache(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "detect"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No known project markers found"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gitignore_detect_skips_unreadable_dirs() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+    let private = temp_path.join("private");
+
+    fs::create_dir_all(&private).unwrap();
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::set_permissions(&private, fs::Permissions::from_mode(0o000)).unwrap();
+    seed_gitignore_cache(&home);
+
+    let assert = isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "detect"])
+        .assert();
+    fs::set_permissions(&private, fs::Permissions::from_mode(0o755)).unwrap();
+
+    assert
+        .success()
+        .stdout(predicate::str::is_match(r"rust\s+Rust.gitignore\s+\(found Cargo.toml\)").unwrap());
+}
+
+#[test]
+fn test_gitignore_add_auto() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(temp_path.join("requirements.txt"), "requests\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "add", "--auto"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cargo.toml"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN rust.gitignore ====="));
+    assert!(content.contains("# ===== BEGIN python.gitignore ====="));
+}
+
+/// A Cargo workspace with a pnpm web package and a uv-managed Python tool.
+fn create_workspace(path: &std::path::Path) {
+    for dir in [
+        "crates/core",
+        "crates/cli",
+        "crates/old",
+        "web/app",
+        "web/docs",
+        "tools/py",
+    ] {
+        fs::create_dir_all(path.join(dir)).unwrap();
+    }
+    fs::write(
+        path.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+    )
+    .unwrap();
+    for dir in ["crates/core", "crates/cli", "crates/old"] {
+        fs::write(path.join(dir).join("Cargo.toml"), "[package]\n").unwrap();
+    }
+    fs::write(
+        path.join("pnpm-workspace.yaml"),
+        "packages:\n  - \"web/*\"\n  - \"!web/docs\"\n",
+    )
+    .unwrap();
+    fs::write(path.join("web/app/package.json"), "{}").unwrap();
+    fs::write(path.join("web/docs/package.json"), "{}").unwrap();
+    fs::write(
+        path.join("pyproject.toml"),
+        "[tool.uv.workspace]\nmembers = [\"tools/*\"]\n",
+    )
+    .unwrap();
+    fs::write(path.join("tools/py/pyproject.toml"), "[project]\n").unwrap();
+}
+
+#[test]
+fn test_gitignore_add_workspace_plan() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    create_workspace(&temp_path);
+    seed_gitignore_cache(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "add", "--workspace", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 4 workspace member(s)"))
+        .stdout(predicate::str::is_match(r"crates/core\s+\(Cargo.toml\)").unwrap())
+        .stdout(predicate::str::is_match(r"web/app\s+\(pnpm-workspace.yaml\)").unwrap())
+        .stdout(predicate::str::contains("crates/old").not())
+        .stdout(predicate::str::contains("web/docs").not())
+        .stdout(predicate::str::is_match(r"rust\s+Rust.gitignore\s+\(found Cargo.toml\)").unwrap())
+        .stdout(predicate::str::contains(
+            "crates/cli is covered by the root .gitignore",
+        ))
+        .stdout(predicate::str::contains("web/app/.gitignore"))
+        .stdout(predicate::str::contains("(found web/app/package.json)"))
+        .stdout(predicate::str::contains("Run without --dry-run"));
+
+    assert!(!temp_path.join(".gitignore").exists());
+    assert!(!temp_path.join("web/app/.gitignore").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gitignore_add_workspace_skips_unreadable_dirs() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+    let private = temp_path.join("crates/private");
+
+    fs::create_dir_all(&private).unwrap();
+    create_git_repo(&temp_path);
+    create_workspace(&temp_path);
+    fs::set_permissions(&private, fs::Permissions::from_mode(0o000)).unwrap();
+    seed_gitignore_cache(&home);
+
+    let assert = isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "add", "--workspace", "--dry-run"])
+        .assert();
+    fs::set_permissions(&private, fs::Permissions::from_mode(0o755)).unwrap();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("Found 4 workspace member(s)"))
+        .stdout(predicate::str::is_match(r"crates/core\s+\(Cargo.toml\)").unwrap());
+}
+
+#[test]
+fn test_gitignore_add_workspace() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    create_workspace(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "add", "--workspace"])
+        .assert()
+        .success();
+
+    assert_file_contains(
+        &temp_path.join(".gitignore"),
+        "# ===== BEGIN rust.gitignore =====",
+    );
+    assert_file_contains(
+        &temp_path.join("web/app/.gitignore"),
+        "# ===== BEGIN node.gitignore =====",
+    );
+    assert_file_contains(
+        &temp_path.join("tools/py/.gitignore"),
+        "# ===== BEGIN python.gitignore =====",
+    );
+    assert!(!temp_path.join("crates/core/.gitignore").exists());
+}
+
+#[test]
+fn test_gitignore_add_workspace_no_members() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join("Cargo.toml"), "[package]\n").unwrap();
+    seed_gitignore_cache(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "add", "--workspace"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No workspace members found"));
+}
+
+#[test]
+fn test_gitignore_add_target_local_exclude() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    let exclude = temp_path.join(".git").join("info").join("exclude");
+    fs::write(&exclude, "scratch/\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args([
+        "gitignore",
+        "add",
+        "global/vim",
+        "--target",
+        "local-exclude",
+    ])
+    .assert()
+    .success();
+
+    let content = fs::read_to_string(&exclude).unwrap();
+    assert!(content.starts_with("scratch/\n"));
+    assert!(content.contains("# ===== BEGIN global/vim.gitignore ====="));
+    assert!(!temp_path.join(".gitignore").exists());
+}
+
+#[test]
+fn test_gitignore_add_target_global() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    fs::create_dir_all(&home).unwrap();
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path)
+        .env("HOME", &home)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("GIT_CONFIG_GLOBAL");
+    cmd.args(["gitignore", "add", "global/macos", "--target", "global"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Registered"));
+
+    let excludes = home.join(".config").join("git").join("ignore");
+    assert_file_contains(&excludes, "# ===== BEGIN global/macos.gitignore =====");
+    assert_file_contains(&home.join(".gitconfig"), "excludesFile");
+    assert!(!temp_path.join(".gitignore").exists());
+}
+
+#[test]
+fn test_gitignore_add_target_conflicts_with_output() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args([
+        "gitignore",
+        "add",
+        "rust",
+        "--target",
+        "global",
+        "--output",
+        "custom.gitignore",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+// --------     CHECK COMMAND TESTS     --------
+
+fn git_add_all(path: &std::path::Path) {
+    std::process::Command::new("git")
+        .args(["add", "--force", "."])
+        .current_dir(path)
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_gitignore_check_current_rules() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    for dir in ["build", "logs", "keep", "src"] {
+        fs::create_dir_all(temp_path.join(dir)).unwrap();
+    }
+    for file in [
+        "src/main.rs",
+        "logs/a.log",
+        "logs/keep.log",
+        "keep/x.log",
+        "notes.tmp",
+    ] {
+        fs::write(temp_path.join(file), "").unwrap();
+    }
+    fs::write(temp_path.join(".gitignore"), "*.log\n!keep.log\n").unwrap();
+    fs::write(temp_path.join("keep").join(".gitignore"), "!x.log\n").unwrap();
+    fs::write(temp_path.join(".git/info/exclude"), "*.tmp\n").unwrap();
+    git_add_all(&temp_path);
+
+    // Untracked from here on
+    fs::write(temp_path.join("build").join("out.bin"), "").unwrap();
+    fs::write(temp_path.join(".gitignore"), "*.log\n!keep.log\nbuild/\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Files ignored by the current rules (1)",
+        ))
+        .stdout(predicate::str::is_match(r"build/\s+.gitignore:3 build/").unwrap())
+        .stdout(predicate::str::contains(
+            "Tracked files matching ignore patterns (2)",
+        ))
+        .stdout(predicate::str::is_match(r"logs/a.log\s+.gitignore:1 \*.log").unwrap())
+        .stdout(predicate::str::is_match(r"notes.tmp\s+.git/info/exclude:1").unwrap())
+        .stdout(predicate::str::contains(
+            "git rm --cached -- logs/a.log notes.tmp",
+        ))
+        .stdout(predicate::str::contains("logs/keep.log").not())
+        .stdout(predicate::str::contains("keep/x.log").not());
+}
+
+#[test]
+fn test_gitignore_check_candidate() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+
+    fs::create_dir_all(temp_path.join("dist")).unwrap();
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join("app.log"), "").unwrap();
+    fs::write(temp_path.join("secrets.env"), "").unwrap();
+    fs::write(temp_path.join(".gitignore"), "*.log\n").unwrap();
+    git_add_all(&temp_path);
+    fs::write(temp_path.join("dist").join("bundle.js"), "").unwrap();
+
+    let candidate = temp_dir.path().join("candidate.gitignore");
+    fs::write(&candidate, "*.log\n/dist\n*.env\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "check", "--candidate"])
+        .arg(&candidate)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Files that would become ignored (1)",
+        ))
+        .stdout(predicate::str::contains("dist/"))
+        .stdout(predicate::str::contains(
+            "Tracked files that would match ignore patterns (1)",
+        ))
+        .stdout(predicate::str::contains("git rm --cached -- secrets.env"))
+        .stdout(predicate::str::contains("app.log").not());
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(content, "*.log\n");
+}
+
+// --------     DIFF COMMAND TESTS     --------
+
+#[test]
+fn test_gitignore_diff_against_upstream() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "add", "rust"]).assert().success();
+
+    // Drop an upstream pattern, add one inside the section and one outside it
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    let content = content
+        .replace("*.pdb\n", "")
+        .replace("# ===== END", "custom-build/\n# ===== END");
+    fs::write(
+        temp_path.join(".gitignore"),
+        format!("secret.env\n{}", content),
+    )
+    .unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "diff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "rust.gitignore differs from upstream Rust.gitignore",
+        ))
+        .stdout(predicate::str::contains("+ *.pdb"))
+        .stdout(predicate::str::contains("- custom-build/"))
+        .stdout(predicate::str::contains("Local-only patterns (1)"))
+        .stdout(predicate::str::contains("> secret.env"));
+}
+
+#[test]
+fn test_gitignore_diff_no_sections() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), "target/\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "diff"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No template sections found"));
+}
+
+#[test]
+fn test_gitignore_diff_missing_file() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "diff", "rust"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not exist"));
+}
+
+// --------     EXPLAIN COMMAND TESTS     --------
+
+#[test]
+fn test_gitignore_explain_section() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::create_dir_all(temp_path.join("web")).unwrap();
+    fs::write(
+        temp_path.join(".gitignore"),
+        "secret.txt\n\n# ===== BEGIN Python.gitignore =====\n__pycache__/\n*.py[cod]\n# ===== END Python.gitignore =====\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join("web").join(".gitignore"),
+        "# ===== Node.gitignore =====\nnode_modules/\n*.log\n",
+    )
+    .unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args([
+        "gitignore",
+        "explain",
+        "app.pyc",
+        "web/npm.log",
+        "secret.txt",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("app.pyc is ignored"))
+    .stdout(predicate::str::is_match(r"source\s+.gitignore:5").unwrap())
+    .stdout(predicate::str::is_match(r"section\s+Python.gitignore").unwrap())
+    .stdout(predicate::str::is_match(r"source\s+web/.gitignore:3").unwrap())
+    .stdout(predicate::str::is_match(r"section\s+Node.gitignore").unwrap())
+    .stdout(predicate::str::is_match(r"source\s+.gitignore:1").unwrap())
+    .stdout(predicate::str::contains("none (hand-written)"));
+}
+
+#[test]
+fn test_gitignore_explain_parent_excluded() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::create_dir_all(temp_path.join("build")).unwrap();
+    fs::write(temp_path.join("build").join("keep.txt"), "").unwrap();
+    fs::write(temp_path.join(".gitignore"), "build/\n!build/keep.txt\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(temp_path.join("build"));
+    cmd.args(["gitignore", "explain", "keep.txt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("build/keep.txt is ignored"))
+        .stdout(predicate::str::is_match(r"via\s+parent directory build/").unwrap())
+        .stdout(predicate::str::contains(
+            "`!build/keep.txt` (.gitignore:2) cannot re-include it",
+        ));
+}
+
+#[test]
+fn test_gitignore_explain_not_ignored() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), "*.log\n!keep.log\n").unwrap();
+    fs::write(temp_path.join(".git/info/exclude"), "*.swp\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "explain", "keep.log", "main.rs", "notes.swp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "keep.log is not ignored, it is re-included",
+        ))
+        .stdout(predicate::str::contains(
+            "main.rs is not ignored (no pattern matches)",
+        ))
+        .stdout(predicate::str::is_match(r"source\s+.git/info/exclude:1").unwrap());
+}
+
+// --------     EXPORT COMMAND TESTS     --------
+
+const EXPORT_SAMPLE: &str =
+    "# deps\nnode_modules/\n*.log\n!keep.log\n/dist\nlogs/**\na/**/b\n[!a]x\nREADME.md\n";
+
+#[test]
+fn test_gitignore_export_dockerignore() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), EXPORT_SAMPLE).unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "export", "--to", "dockerignore"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Directory-only patterns also match files of the same name in .dockerignore (line 2)",
+        ));
+
+    let content = fs::read_to_string(temp_path.join(".dockerignore")).unwrap();
+    assert!(content.starts_with("# Generated from .gitignore"));
+    assert!(content.contains(
+        "# deps\n**/node_modules\n**/*.log\n!**/keep.log\ndist\nlogs/**\na/**/b\n**/[^a]x\n"
+    ));
+}
+
+#[test]
+fn test_gitignore_export_helmignore() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), EXPORT_SAMPLE).unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args([
+        "gitignore",
+        "export",
+        "--to",
+        "helmignore",
+        "--dir",
+        "chart",
+    ]);
+    fs::create_dir_all(temp_path.join("chart")).unwrap();
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "`**` is not supported by .helmignore",
+        ))
+        .stderr(predicate::str::contains("(line 7)"));
+
+    let content = fs::read_to_string(temp_path.join("chart").join(".helmignore")).unwrap();
+    assert!(content.contains("/dist\n/logs/\n# untranslatable: a/**/b\n[^a]x\n"));
+}
+
+#[test]
+fn test_gitignore_export_npmignore_stdout() {
+    let temp_dir = setup_test_env();
+    let source = temp_dir.path().join("web.gitignore");
+    fs::write(&source, EXPORT_SAMPLE).unwrap();
+
+    AssertCommand::cargo_bin("gh-templates")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args([
+            "gitignore",
+            "export",
+            "--to",
+            "npmignore",
+            "--stdout",
+            "--from",
+        ])
+        .arg(&source)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "node_modules/\n*.log\n!keep.log\n",
+        ))
+        .stderr(
+            predicate::str::contains("npm always publishes")
+                .and(predicate::str::contains("(line 9)")),
+        );
+
+    assert!(!temp_dir.path().join(".npmignore").exists());
+}
+
+#[test]
+fn test_gitignore_export_no_overwrite() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), "*.log\n").unwrap();
+    fs::write(temp_path.join(".prettierignore"), "mine\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "export", "--to", "prettierignore"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "export", "--to", "prettierignore", "--force"])
+        .assert()
+        .success();
+    assert_file_contains(&temp_path.join(".prettierignore"), "*.log");
+}
+
+// --------     LINT COMMAND TESTS     --------
+
+const LINT_SAMPLE: &str =
+    "*.log\nlogs/app.log\n*.log\nbuild/\n!build/keep.txt\ndist/*\n!dist/keep\ncache \n/\n";
+
+#[test]
+fn test_gitignore_lint_reports_issues() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), LINT_SAMPLE).unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "lint"])
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("5 issue(s) found"))
+        .stdout(predicate::str::is_match(r"2:\s+shadowed\s+logs/app.log").unwrap())
+        .stdout(predicate::str::is_match(r"3:\s+duplicate\s+\*.log").unwrap())
+        .stdout(predicate::str::is_match(r"5:\s+dead-negation\s+!build/keep.txt").unwrap())
+        .stdout(predicate::str::is_match(r"8:\s+trailing-whitespace").unwrap())
+        .stdout(predicate::str::is_match(r"9:\s+meaningless").unwrap())
+        .stdout(predicate::str::contains("dist/keep").not());
+}
+
+#[test]
+fn test_gitignore_lint_json() {
+    let temp_dir = setup_test_env();
+    let file = temp_dir.path().join("custom.gitignore");
+    fs::write(&file, "*.o\n*.o\n").unwrap();
+
+    let output = AssertCommand::cargo_bin("gh-templates")
+        .unwrap()
+        .args(["gitignore", "lint", "--json"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(4));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["issues"][0]["kind"], "duplicate");
+    assert_eq!(report["issues"][0]["line"], 2);
+    assert_eq!(report["issues"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_gitignore_lint_fix() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), LINT_SAMPLE).unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "lint", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 5 issue(s)"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(content, "*.log\nbuild/\ndist/*\n!dist/keep\ncache\n");
+}
+
+#[test]
+fn test_gitignore_lint_single_char_wildcard() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(temp_path.join(".gitignore"), "?oo\nfoo.txt\nbuild/\nboo\n").unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "lint", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 issue(s)"));
+
+    // `?oo` covers `boo` but not the longer or unrelated patterns
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(content, "?oo\nfoo.txt\nbuild/\n");
+}
+
+#[test]
+fn test_gitignore_lint_clean() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().to_path_buf();
+
+    create_git_repo(&temp_path);
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# build output\ntarget/\n*.log\n!keep.log\n",
+    )
+    .unwrap();
+
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.current_dir(&temp_path);
+    cmd.args(["gitignore", "lint"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found"));
+}
+
+// --------     SUGGEST COMMAND TESTS     --------
+
+/// Seeds a cache of only the Rust and Python templates, both already downloaded.
+fn seed_suggest_caches(home: &std::path::Path) {
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+    seed_cache(
+        &cache_dir,
+        "gitignore_templates",
+        &[
+            ("rust", serde_json::json!("Rust.gitignore")),
+            ("python", serde_json::json!("Python.gitignore")),
+        ],
+    );
+    seed_cache(
+        &cache_dir,
+        "gitignore_template_bodies",
+        &[
+            (
+                "Rust.gitignore",
+                serde_json::json!("/target/\n**/*.rs.bk\n"),
+            ),
+            (
+                "Python.gitignore",
+                serde_json::json!("__pycache__/\n*.py[cod]\n.venv\n"),
+            ),
+        ],
+    );
+}
+
+/// A committed Rust project with build output, logs and a new source file lying around.
+fn create_untracked_project(path: &std::path::Path) {
+    for dir in ["src", "target/debug", ".venv/lib", "logs", "scratch"] {
+        fs::create_dir_all(path.join(dir)).unwrap();
+    }
+    create_git_repo(&path.to_path_buf());
+    fs::write(path.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(path.join("src/main.rs"), "fn main() {}\n").unwrap();
+    git_add_all(path);
+
+    for file in [
+        "target/debug/app",
+        ".venv/lib/site.py",
+        "app.log",
+        "server.log",
+        "logs/build.log",
+        "scratch/notes.bin",
+        "src/new.rs",
+    ] {
+        fs::write(path.join(file), "").unwrap();
+    }
+}
+
+#[test]
+fn test_gitignore_suggest_dry_run() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    create_untracked_project(&temp_path);
+    seed_suggest_caches(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "suggest", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"rust\s+Rust.gitignore\s+covers target/").unwrap())
+        .stdout(predicate::str::is_match(r"python\s+Python.gitignore\s+covers .venv/").unwrap())
+        .stdout(predicate::str::is_match(r"\*\.log\s+app.log, server.log, logs/build.log").unwrap())
+        .stdout(predicate::str::is_match(r"scratch/\s+scratch/").unwrap())
+        .stdout(predicate::str::contains("look like new source files"))
+        .stdout(predicate::str::contains("! src/new.rs"));
+
+    assert!(!temp_path.join(".gitignore").exists());
+}
+
+#[test]
+fn test_gitignore_suggest_interactive() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    create_untracked_project(&temp_path);
+    seed_suggest_caches(&home);
+
+    // Accept rust, decline python, accept *.log, decline scratch/
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "suggest"])
+        .write_stdin("y\nn\n\nn\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added 1 custom pattern(s)"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN rust.gitignore =====\n/target/\n"));
+    assert!(content.ends_with("# ===== END rust.gitignore =====\n*.log\n"));
+    assert!(!content.contains("python"));
+    assert!(!content.contains("scratch/"));
+}
+
+#[test]
+fn test_gitignore_suggest_yes() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    create_untracked_project(&temp_path);
+    seed_suggest_caches(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "suggest", "--yes"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN rust.gitignore ====="));
+    assert!(content.contains("# ===== BEGIN python.gitignore ====="));
+    assert!(content.ends_with("*.log\nscratch/\n"));
+    assert!(!content.contains("new.rs"));
+}
+
+#[test]
+fn test_gitignore_suggest_after_legacy_section() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    create_untracked_project(&temp_path);
+    seed_suggest_caches(&home);
+    fs::write(
+        temp_path.join(".gitignore"),
+        "# ===== Global/macOS.gitignore =====\n.DS_Store\n",
+    )
+    .unwrap();
+
+    // Decline both templates, accept *.log, decline scratch/
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "suggest"])
+        .write_stdin("n\nn\ny\nn\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added 1 custom pattern(s)"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(
+        content,
+        "# ===== Global/macOS.gitignore =====\n.DS_Store\n\n*.log\n"
+    );
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "remove", "global/macos"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert_eq!(content, "*.log\n");
+}
+
+#[test]
+fn test_gitignore_suggest_nothing_untracked() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    seed_suggest_caches(&home);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .args(["gitignore", "suggest"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No untracked files to ignore"));
+}
+
+// --------     BACKEND TESTS     --------
+
+/// Serves gitignore.io's `api/list` and `api/<a,b>` endpoints on a local port, returning its URL.
+fn serve_gitignore_io(templates: &'static [(&'static str, &'static str)]) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().filter_map(|s| s.ok()) {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            let body = match path.strip_prefix("/api/") {
+                Some("list") => Some(
+                    templates
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+                Some(names) => names
+                    .split(',')
+                    .map(|name| templates.iter().find(|(t, _)| *t == name).map(|(_, b)| *b))
+                    .collect::<Option<Vec<_>>>()
+                    .map(|bodies| bodies.join("\n")),
+                None => None,
+            };
+
+            let response = match body {
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    url
+}
+
+#[test]
+fn test_gitignore_toptal_backend() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    let url = serve_gitignore_io(&[
+        ("rust", "### Rust ###\n/target/\n"),
+        ("osx", "### OSX ###\n.DS_Store\n"),
+    ]);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .env("GITFORGE_GITIGNORE_BACKEND", "toptal")
+        .env("GITFORGE_GITIGNORE_URL", format!("{}/", url))
+        .env("NO_PROXY", "127.0.0.1")
+        .args(["gitignore", "add", "rust", "global/macos"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(2 templates available)"));
+
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    assert!(content.contains("# ===== BEGIN rust.gitignore =====\n### Rust ###\n/target/\n"));
+    assert!(
+        content.contains("# ===== BEGIN global/macos.gitignore =====\n### OSX ###\n.DS_Store\n")
+    );
+
+    // The catalogue is cached apart from the GitHub one
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+    assert_file_exists(&cache_dir.join("gitignore_templates_toptal.json"));
+    assert!(!cache_dir.join("gitignore_templates.json").exists());
+}
+
+#[test]
+fn test_gitignore_add_merge_keeps_reignore_after_negation() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    let url = serve_gitignore_io(&[("one", "foo\n"), ("two", "!foo\n"), ("three", "foo\n")]);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .env("GITFORGE_GITIGNORE_BACKEND", "toptal")
+        .env("GITFORGE_GITIGNORE_URL", &url)
+        .env("NO_PROXY", "127.0.0.1")
+        .args(["gitignore", "add", "one", "two", "three"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Negation '!foo' is overridden by a later pattern",
+        ));
+
+    // git lets the last match decide, so `foo` must stay ignored
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    let patterns: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    assert_eq!(patterns, ["foo", "!foo", "foo"]);
+}
+
+#[test]
+fn test_gitignore_add_merge_keeps_repeat_after_other_negation() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path().join("repo");
+    let home = temp_dir.path().join("home");
+
+    fs::create_dir_all(&temp_path).unwrap();
+    create_git_repo(&temp_path);
+    let url = serve_gitignore_io(&[
+        ("a", "*.log\n"),
+        ("b", "!important.log\n"),
+        ("c", "*.log\n"),
+    ]);
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .current_dir(&temp_path)
+        .env("GITFORGE_GITIGNORE_BACKEND", "toptal")
+        .env("GITFORGE_GITIGNORE_URL", &url)
+        .env("NO_PROXY", "127.0.0.1")
+        .args(["gitignore", "add", "a", "b", "c"])
+        .assert()
+        .success();
+
+    // c's `*.log` ignores important.log again, as concatenating the templates would
+    let content = fs::read_to_string(temp_path.join(".gitignore")).unwrap();
+    let patterns: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    assert_eq!(patterns, ["*.log", "!important.log", "*.log"]);
+}
+
+#[test]
+fn test_gitignore_unknown_backend() {
+    let temp_dir = setup_test_env();
+    let home = temp_dir.path().join("home");
+
+    isolated_command(&home, &temp_dir.path().join("system"))
+        .env("GITFORGE_GITIGNORE_BACKEND", "svn")
+        .args(["gitignore", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown gitignore backend 'svn'"));
+}
+
+#[test]
+fn test_gitignore_list_aliases() {
+    let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
+    cmd.args(["gitignore", "list", "--aliases"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"global/macos\s+osx").unwrap())
+        .stdout(predicate::str::is_match(r"dotnet\s+dotnetcore").unwrap());
+}
+
 // --------     LIST COMMAND TESTS     --------
 
 #[test]
@@ -344,7 +1805,7 @@ fn test_gitignore_list_default() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "list"])
+    cmd.args(["gitignore", "list"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Available gitignore templates"))
@@ -360,7 +1821,7 @@ fn test_gitignore_list_popular() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "list", "--popular"])
+    cmd.args(["gitignore", "list", "--popular"])
         .assert()
         .success()
         .stdout(predicate::str::contains("POPULAR"));
@@ -373,7 +1834,7 @@ fn test_gitignore_list_global() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "list", "--global"])
+    cmd.args(["gitignore", "list", "--global"])
         .assert()
         .success()
         .stdout(predicate::str::contains("global"));
@@ -386,7 +1847,7 @@ fn test_gitignore_list_community() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "list", "--community"])
+    cmd.args(["gitignore", "list", "--community"])
         .assert()
         .success()
         .stdout(predicate::str::contains("community"));
@@ -399,7 +1860,7 @@ fn test_gitignore_list_update_cache() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "list", "--update-cache"])
+    cmd.args(["gitignore", "list", "--update-cache"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Available gitignore templates"));
@@ -414,7 +1875,7 @@ fn test_gitignore_preview_single_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview", "rust"])
+    cmd.args(["gitignore", "preview", "rust"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Rust"));
@@ -427,7 +1888,7 @@ fn test_gitignore_preview_single_template_update_cache() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview", "rust", "--update-cache"])
+    cmd.args(["gitignore", "preview", "rust", "--update-cache"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Rust"));
@@ -440,7 +1901,7 @@ fn test_gitignore_preview_multiple_templates() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview", "rust", "python"])
+    cmd.args(["gitignore", "preview", "rust", "python"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Rust"))
@@ -454,7 +1915,7 @@ fn test_gitignore_preview_update_cache() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview", "rust", "--update-cache"])
+    cmd.args(["gitignore", "preview", "rust", "--update-cache"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Rust"));
@@ -467,7 +1928,7 @@ fn test_gitignore_preview_invalid_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview", "not-a-template"])
+    cmd.args(["gitignore", "preview", "not-a-template"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("not found").or(predicate::str::contains("Unknown")));
@@ -480,7 +1941,7 @@ fn test_gitignore_preview_no_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "preview"])
+    cmd.args(["gitignore", "preview"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("No gitignore template specified"));
@@ -495,7 +1956,7 @@ fn test_gitignore_help_command() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["gitignore", "--help"])
+    cmd.args(["gitignore", "--help"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Gitignore"))
@@ -42,7 +42,7 @@ fn test_issue_add_bug() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "bug"])
+    cmd.args(["issue", "add", "bug"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Added issue template").or(predicate::str::contains("✓")));
@@ -63,7 +63,7 @@ fn test_issue_add_multiple() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "bug", "feature"])
+    cmd.args(["issue", "add", "bug", "feature"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Added issue template").or(predicate::str::contains("✓")));
@@ -80,7 +80,7 @@ fn test_issue_add_with_dir() {
     fs::create_dir_all(&target_dir).unwrap();
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "add", "bug", "--dir", target_dir.to_str().unwrap()])
+    cmd.args(["issue", "add", "bug", "--dir", target_dir.to_str().unwrap()])
         .assert()
         .success()
         .stdout(predicate::str::contains("Added issue template").or(predicate::str::contains("✓")));
@@ -103,7 +103,7 @@ fn test_issue_add_force_overwrite() {
     // Try to add without force (should fail)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "bug"])
+    cmd.args(["issue", "add", "bug"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("already exists"));
@@ -111,7 +111,7 @@ fn test_issue_add_force_overwrite() {
     // Try with force flag (should succeed)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "bug", "--force"])
+    cmd.args(["issue", "add", "bug", "--force"])
         .assert()
         .success();
 
@@ -127,7 +127,7 @@ fn test_issue_add_invalid_type() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "invalid-template"])
+    cmd.args(["issue", "add", "invalid-template"])
         .assert()
         .failure()
         .stderr(
@@ -144,7 +144,7 @@ fn test_issue_add_no_template() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add"])
+    cmd.args(["issue", "add"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("No issue template specified"));
@@ -159,7 +159,7 @@ fn test_issue_add_unknown_argument() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "--unknown"])
+    cmd.args(["issue", "add", "--unknown"])
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -177,7 +177,7 @@ fn test_issue_add_valid_and_invalid_templates() {
     // Attempt to add both a valid ("bug") and invalid ("not-a-template") template in one command
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "bug", "not-a-template"])
+    cmd.args(["issue", "add", "bug", "not-a-template"])
         .assert()
         .failure()
         .stderr(
@@ -204,7 +204,7 @@ fn test_issue_add_default_with_output_without_ext() {
     // Add "feature" template and specify output file without extension
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "feature", "-o", "feat"])
+    cmd.args(["issue", "add", "feature", "-o", "feat"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Added issue template").or(predicate::str::contains("✓")));
@@ -227,7 +227,7 @@ fn test_issue_add_default_with_output_with_ext() {
     // Add "feature" template and specify output file with extension
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "feature", "-o", "feat.yml"])
+    cmd.args(["issue", "add", "feature", "-o", "feat.yml"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Added issue template").or(predicate::str::contains("✓")));
@@ -250,7 +250,7 @@ fn test_issue_add_uneven_templates_and_outputs() {
     // Pass two templates but only one output file
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["issue", "add", "feature", "bug", "-o", "feat"])
+    cmd.args(["issue", "add", "feature", "bug", "-o", "feat"])
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -264,7 +264,7 @@ fn test_issue_add_uneven_templates_and_outputs() {
 fn test_issue_list() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "list"])
+    cmd.args(["issue", "list"])
         .assert()
         .success()
         .stdout(predicate::str::contains("bug"))
@@ -277,7 +277,7 @@ fn test_issue_list() {
 fn test_issue_preview_bug() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "preview", "bug"])
+    cmd.args(["issue", "preview", "bug"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Bug Report"));
@@ -287,7 +287,7 @@ fn test_issue_preview_bug() {
 fn test_issue_preview_multiple() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "preview", "bug", "feature"])
+    cmd.args(["issue", "preview", "bug", "feature"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Bug Report"))
@@ -298,7 +298,7 @@ fn test_issue_preview_multiple() {
 fn test_issue_preview_invalid_id() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "preview", "not-a-template"])
+    cmd.args(["issue", "preview", "not-a-template"])
         .assert()
         .failure()
         .stderr(
@@ -312,7 +312,7 @@ fn test_issue_preview_invalid_id() {
 fn test_issue_help_command() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["issue", "--help"])
+    cmd.args(["issue", "--help"])
         .assert()
         .success()
         .stdout(predicate::str::contains(
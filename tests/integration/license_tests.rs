@@ -1,9 +1,13 @@
 use assert_cmd::Command as AssertCommand;
 use predicates::prelude::*;
 use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
 
 // Import utility functions
-use crate::common::test_utils::{assert_file_exists, create_git_repo, setup_test_env};
+use crate::common::test_utils::{
+    assert_file_exists, create_git_repo, isolated_command, seed_cache, setup_test_env,
+};
 
 /**
 Integration tests for the `gh-templates` license subcommand.
@@ -16,16 +20,48 @@ This test suite covers the following scenarios:
 - `test_license_add_update_cache_flag`: Tests that the `--update-cache` flag works when adding a license.
 - `test_license_add_multiple_licenses_with_output_files`: Validates that multiple licenses can be added with specified output files.
 - `test_license_add_multiple_licenses_with_output_files_mismatched_count`: Ensures that an error is raised when the number of licenses does not match the number of output files.
+- `test_license_add_template_variables`: Verifies that placeholders are filled from the SPDX template variables, by placeholder or variable name, leaving other bracketed text alone.
+- `test_license_add_project_defaults`: Verifies that the year and copyright holder are filled from git and the project manifest, that `--param` and interactive answers win, and that `--no-defaults` keeps the placeholders.
+- `test_license_add_omit_optional`: Checks that `--omit-optional` drops the optional sections of the license text.
+- `test_license_add_expression_suffix_layout`: Verifies that an `OR` expression writes `LICENSE-<ID>` files and a top-level LICENSE explaining the choice.
+- `test_license_add_expression_reuse_layout`: Checks that `--layout reuse` writes the license texts to a `LICENSES/` directory.
+- `test_license_add_expression_invalid`: Ensures that malformed expressions, unknown license ids and `--output` with an expression are rejected.
+- `test_license_add_expression_with_exception`: Verifies that `WITH` appends the exception text to the license, or writes it to `LICENSES/` with the REUSE layout, and rejects unknown exceptions.
+- `test_license_add_update_manifests`: Verifies that `--update-manifests` sets the license field of Cargo.toml, package.json and pyproject.toml while keeping their formatting, and that mismatched fields are reported otherwise.
 - `test_license_list_popular`: Ensures the list command displays popular licenses such as "mit" and "apache-2.0".
 - `test_license_list_non_software`: Checks that non-software licenses are listed when the appropriate flag is used.
 - `test_license_list_search_wildcard`: Validates that searching for a license by name returns matching results.
 - `test_license_list_osi_approved`: Ensures that OSI-approved licenses are listed.
 - `test_license_list_fsf_libre`: Checks that FSF-libre licenses are listed.
 - `test_license_list_include_deprecated`: Validates that deprecated licenses are included when requested.
+- `test_license_list_exceptions`: Checks that `--exceptions` lists cached license exceptions, honouring search and hiding deprecated ones.
+- `test_license_list_exceptions_refreshes_stale_cache`: Ensures a stale exceptions cache is fetched again instead of being listed as is.
 - `test_license_list_unknown_argument`: Confirms that an unknown argument results in an error.
 - `test_license_preview_with_update_cache`: Tests that previewing a license with `--update-cache` works and displays license content.
 - `test_license_preview_with_all_flags`: Ensures that all preview flags display the correct sections for a license.
 - `test_license_preview_nonexistent_license`: Checks that previewing a nonexistent license returns an error message.
+- `test_license_preview_exception`: Verifies that previewing a license exception shows its name, text and SPDX metadata.
+- `test_license_detect_standard_text`: Verifies that a re-wrapped MIT license with a copyright line is identified as MIT with full confidence.
+- `test_license_detect_manifest_disagreement`: Checks that manifest license fields naming other licenses than the LICENSE file are reported, ignoring values that are not SPDX expressions.
+- `test_license_detect_modified_text`: Checks that words removed from or added to a license are reported as non-standard modifications.
+- `test_license_detect_no_match`: Ensures that a file resembling no known license fails with the closest candidate.
+- `test_license_detect_missing_file`: Confirms that a directory without LICENSE or COPYING files is reported.
+- `test_license_header_add`: Verifies that headers use each language's comment syntax, keep shebangs, pragmas, XML prologs and front matter first, skip ignored files, only go into configuration and documentation files matched by `--include` or named on the command line, and are not added twice.
+- `test_license_header_add_comment_style`: Checks that `--comment-style` adds headers to files of unknown types and rejects unknown styles.
+- `test_license_header_dual_license`: Ensures `--license` takes an SPDX expression, and that headers declaring it are recognised however it is spelled.
+- `test_license_header_add_other_license`: Ensures that files declaring another license are reported and left unchanged.
+- `test_license_header_add_detects_license`: Confirms that the license is taken from the LICENSE file when `--license` is not given.
+- `test_license_header_check_up_to_date`: Verifies that files with headers as recent as their last commit pass the check.
+- `test_license_header_check_exit_codes`: Checks that missing, mismatched and outdated headers and headers without a copyright notice are reported with distinct exit codes.
+- `test_license_header_check_globs_and_report`: Ensures that include and exclude globs limit the check and that the JSON and SARIF reports list the findings.
+- `test_license_compat_pairs`: Verifies the compatibility verdicts and explanations for common license pairs, including "or later" versions and expressions, and that incompatible pairs fail.
+- `test_license_compat_project`: Checks that `--project` compares the licenses declared in SPDX headers with the repository's license and fails on incompatible ones.
+- `test_license_reuse_lint`: Verifies that `--lint` reports bad, missing and unused licenses and files without licensing information, reading `.license` sidecars, without changing anything.
+- `test_license_reuse_annotate_and_download`: Checks that files which cannot carry a header are annotated in REUSE.toml, used license texts are downloaded into LICENSES/, and the repository then passes the lint.
+- `test_license_notice_init_and_add`: Verifies that `notice init` writes the project name and copyright line, that `notice add` appends, refuses and replaces attribution blocks, and that `notice check` reports malformed NOTICE files.
+- `test_license_notice_for_apache`: Checks that adding Apache-2.0 creates a NOTICE file unless one exists or `--no-notice` is given, and that `detect` and `notice check` report a missing NOTICE.
+- `test_license_choose_shortlist`: Verifies that `choose` asks the questions not given as options, ranks the matching licenses with a rationale built from their choosealicense.com rules, and falls back to the closest licenses when none fit.
+- `test_license_choose_adds_license`: Checks that picking a recommended license by number adds it, and that skipping or an unknown number adds nothing.
 - `test_license_help_command`: Validates that the help command displays usage information for the license subcommands.
 
 Each test uses a temporary directory to avoid side effects and leverages `assert_cmd` and `predicates` for command-line assertions.
@@ -42,7 +78,7 @@ fn test_license_add_with_params() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&[
+    cmd.args([
         "license",
         "add",
         "mit",
@@ -70,7 +106,7 @@ fn test_license_add_with_unused_param_warning() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["license", "add", "mit", "--param", "unusedparam=foobar"])
+    cmd.args(["license", "add", "mit", "--param", "unusedparam=foobar"])
         .assert()
         .success()
         .stdout(predicate::str::contains("unused parameter"));
@@ -92,7 +128,7 @@ fn test_license_add_interactive_mode() {
     // Simulate interactive input by piping values (requires assert_cmd::Command::write_stdin)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["license", "add", "mit", "--interactive"])
+    cmd.args(["license", "add", "mit", "--interactive"])
         .write_stdin("2025\nJohn Doe\n")
         .assert()
         .success()
@@ -114,7 +150,7 @@ fn test_license_add_update_cache_flag() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["license", "add", "mit", "--update-cache"])
+    cmd.args(["license", "add", "mit", "--update-cache"])
         .assert()
         .success()
         .stdout(predicate::str::contains("added license"));
@@ -134,7 +170,7 @@ fn test_license_add_multiple_licenses_with_output_files() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&[
+    cmd.args([
         "license",
         "add",
         "mit",
@@ -169,7 +205,7 @@ fn test_license_add_multiple_licenses_with_output_files_mismatched_count() {
     // Only one output file for two licenses
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["license", "add", "mit", "apache-2.0", "-o", "LICENSE-MIT"])
+    cmd.args(["license", "add", "mit", "apache-2.0", "-o", "LICENSE-MIT"])
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -189,7 +225,7 @@ fn test_license_add_multiple_licenses_with_params_and_output_files() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&[
+    cmd.args([
         "license",
         "add",
         "mit",
@@ -219,13 +255,312 @@ fn test_license_add_multiple_licenses_with_params_and_output_files() {
     assert!(apache_content.contains("Jane Doe"));
 }
 
+#[test]
+fn test_license_add_template_variables() {
+    let repo = TestRepo::new();
+    let add = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "add", "--force"]).args(args);
+        cmd.assert().success()
+    };
+
+    // A placeholder inside a variable, with a value holding angle brackets
+    add(&[
+        "mit",
+        "--param",
+        "year=2025",
+        "--param",
+        "copyright-holders=Jane Doe <jane@example.com>",
+    ])
+    .stdout(predicate::str::contains(
+        "Filled 2 out of 2 placeholder(s).",
+    ))
+    .stdout(predicate::str::contains("unused parameter").not());
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Copyright (c) 2025 Jane Doe <jane@example.com>\n"));
+    assert!(content.starts_with("MIT License"));
+
+    // Variables without placeholders are kept unless named
+    add(&["isc"]).stdout(predicate::str::contains(
+        "Kept the standard text of: copyright, copyrightHolder0, copyrightHolder1",
+    ));
+    add(&[
+        "isc",
+        "--param",
+        "copyright=Copyright (c) 2025 Acme",
+        "--param",
+        "copyrightHolder0=ACME",
+        "--param",
+        "copyrightholder1=ACME",
+    ])
+    .stdout(predicate::str::contains("unused parameter").not());
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Copyright (c) 2025 Acme\n"));
+    assert!(content.contains("\"AS IS\" AND ACME DISCLAIMS"));
+    assert!(content.contains("SHALL ACME BE LIABLE"));
+    assert!(!content.contains("Internet Systems Consortium"));
+}
+
+#[test]
+fn test_license_add_project_defaults() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path.join("Cargo.toml"),
+        "[package]\nname = \"engine\"\nauthors = [\"Charles Babbage <cb@example.com>\"]\n",
+    )
+    .unwrap();
+    commit_all(&repo.path, "2020-03-01T12:00:00");
+    let add = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "add", "--force"]).args(args);
+        cmd.assert().success()
+    };
+
+    // The holder comes from the manifest, the years from the first commit
+    add(&["mit"])
+        .stdout(predicate::str::contains("Filled from project defaults:"))
+        .stdout(predicate::str::contains("(Cargo.toml authors)"))
+        .stdout(predicate::str::contains("(first commit to current year)"));
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Copyright (c) 2020-"));
+    assert!(content.contains(" Charles Babbage\n"));
+
+    // git user.name comes before the manifest, and --param before both
+    std::process::Command::new("git")
+        .args(["config", "user.name", "Ada Lovelace"])
+        .current_dir(&repo.path)
+        .output()
+        .unwrap();
+    add(&["mit", "--param", "year=1843"])
+        .stdout(predicate::str::contains("(git config user.name)"));
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Copyright (c) 1843 Ada Lovelace\n"));
+
+    // Interactive mode offers the defaults, taken on an empty answer
+    let mut cmd = repo.command();
+    cmd.args(["license", "add", "mit", "--force", "-i"])
+        .write_stdin("\nGrace Hopper\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'year' [2020-"))
+        .stdout(predicate::str::contains(
+            "'copyright holders' [Ada Lovelace]",
+        ));
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains(" Grace Hopper\n"));
+    assert!(!content.contains("Ada Lovelace"));
+
+    add(&["mit", "--no-defaults"])
+        .stdout(predicate::str::contains("Filled from project defaults").not());
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Copyright (c) <year> <copyright holders>\n"));
+}
+
+#[test]
+fn test_license_add_omit_optional() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args([
+            "license",
+            "add",
+            "mit",
+            "--omit-optional",
+            "--param",
+            "year=2025",
+            "--param",
+            "copyright-holders=Jane Doe",
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.starts_with("Copyright (c) 2025 Jane Doe\n\nPermission is hereby granted"));
+}
+
+#[test]
+fn test_license_add_expression_suffix_layout() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args([
+            "license",
+            "add",
+            "mit or isc",
+            "--param",
+            "year=2025",
+            "--param",
+            "copyright-holders=Jane Doe",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LICENSE-MIT - has been added."))
+        .stdout(predicate::str::contains("LICENSE-ISC - has been added."))
+        .stdout(predicate::str::contains("Licensed under MIT OR ISC"));
+
+    let mit = fs::read_to_string(repo.path.join("LICENSE-MIT")).unwrap();
+    assert!(mit.contains("Copyright (c) 2025 Jane Doe"));
+    let isc = fs::read_to_string(repo.path.join("LICENSE-ISC")).unwrap();
+    assert!(isc.starts_with("ISC License"));
+
+    let summary = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(summary.contains("either of"));
+    assert!(summary.contains("- MIT License (LICENSE-MIT)"));
+    assert!(summary.contains("- ISC License (LICENSE-ISC)"));
+    assert!(summary.contains("at your option."));
+    assert!(summary.contains("SPDX-License-Identifier: MIT OR ISC"));
+}
+
+#[test]
+fn test_license_add_expression_reuse_layout() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["license", "add", "MIT", "AND", "ISC", "--layout", "reuse"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Licensed under MIT AND ISC"));
+
+    assert_file_exists(&repo.path.join("LICENSES").join("MIT.txt"));
+    assert_file_exists(&repo.path.join("LICENSES").join("ISC.txt"));
+    assert!(!repo.path.join("LICENSE-MIT").exists());
+
+    let summary = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(summary.contains("all of the following licenses"));
+    assert!(summary.contains("- MIT License (LICENSES/MIT.txt)"));
+    assert!(summary.contains("SPDX-License-Identifier: MIT AND ISC"));
+}
+
+#[test]
+fn test_license_add_expression_invalid() {
+    let repo = TestRepo::new();
+    let add = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "add"]).args(args);
+        cmd.assert().failure()
+    };
+
+    add(&["MIT OR"]).stderr(predicate::str::contains("expected a license id"));
+    add(&["(MIT AND ISC"]).stderr(predicate::str::contains("missing ')'"));
+    add(&["MIT OR Foo-1.0"]).stderr(predicate::str::contains(
+        "License 'Foo-1.0' not found in SPDX cache",
+    ));
+    add(&["MIT OR ISC", "-o", "A", "B"]).stderr(predicate::str::contains(
+        "--output cannot be used with a license expression",
+    ));
+
+    assert!(!repo.path.join("LICENSE").exists());
+}
+
+#[test]
+fn test_license_add_expression_with_exception() {
+    let repo = TestRepo::new();
+    seed_exception_caches(&repo.home);
+    let add = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "add"]).args(args);
+        cmd.assert()
+    };
+
+    add(&["mit with llvm-exception"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Licensed under MIT WITH LLVM-exception",
+        ));
+    let license = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(license.starts_with("MIT License"));
+    let exception_at = license
+        .find("LLVM Exceptions to the Apache 2.0 License")
+        .unwrap();
+    assert!(exception_at > license.find("Permission is hereby granted").unwrap());
+
+    add(&[
+        "MIT WITH LLVM-exception OR ISC",
+        "--layout",
+        "reuse",
+        "--force",
+    ])
+    .success();
+    assert_file_exists(&repo.path.join("LICENSES").join("LLVM-exception.txt"));
+    let mit = fs::read_to_string(repo.path.join("LICENSES").join("MIT.txt")).unwrap();
+    assert!(!mit.contains("LLVM Exceptions"));
+    let summary = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(summary.contains(
+        "- MIT License with LLVM Exception (LICENSES/MIT.txt, LICENSES/LLVM-exception.txt)"
+    ));
+
+    add(&["MIT WITH Foo-exception", "--force"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "License exception 'Foo-exception' not found",
+        ));
+}
+
+#[test]
+fn test_license_add_update_manifests() {
+    let repo = TestRepo::new();
+    let cargo = "[package]\nname = \"engine\" # the engine\nlicense = \"MIT\"  # was MIT\n\n[dependencies]\nserde = \"1\"\n";
+    fs::write(repo.path.join("Cargo.toml"), cargo).unwrap();
+    fs::write(
+        repo.path.join("package.json"),
+        "{\n    \"name\": \"web\",\n    \"version\": \"1.0.0\",\n    \"scripts\": { \"license\": \"check\" }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("pyproject.toml"),
+        "[project]\nname = \"py\"\nlicense = { text = \"MIT\" }\n",
+    )
+    .unwrap();
+    let add = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "add", "--force"]).args(args);
+        cmd.assert().success()
+    };
+
+    // Without the option the manifests are left alone, with a warning
+    add(&["isc"])
+        .stdout(predicate::str::contains(
+            "Cargo.toml [package] license is \"MIT\" but the license files are ISC",
+        ))
+        .stdout(predicate::str::contains("--update-manifests"));
+    assert_eq!(
+        fs::read_to_string(repo.path.join("Cargo.toml")).unwrap(),
+        cargo
+    );
+
+    add(&["MIT OR ISC", "--update-manifests"])
+        .stdout(predicate::str::contains(
+            "Set Cargo.toml [package] license to \"MIT OR ISC\"",
+        ))
+        .stdout(predicate::str::contains("Set package.json license"))
+        .stdout(predicate::str::contains(
+            "Set pyproject.toml [project] license",
+        ));
+    assert_eq!(
+        fs::read_to_string(repo.path.join("Cargo.toml")).unwrap(),
+        cargo.replace("\"MIT\"  # was MIT", "\"MIT OR ISC\"  # was MIT")
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("package.json")).unwrap(),
+        "{\n    \"name\": \"web\",\n    \"version\": \"1.0.0\",\n    \"license\": \"MIT OR ISC\",\n    \"scripts\": { \"license\": \"check\" }\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("pyproject.toml")).unwrap(),
+        "[project]\nname = \"py\"\nlicense = \"MIT OR ISC\"\n"
+    );
+
+    add(&["isc", "mit", "--update-manifests"])
+        .stdout(predicate::str::contains("Not updating the manifests"));
+    repo.command()
+        .args(["license", "add", "--all", "--update-manifests"])
+        .assert()
+        .failure();
+}
+
 // --------     LIST COMMAND TESTS     --------
 
 #[test]
 fn test_license_list_popular() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--popular"])
+    cmd.args(["license", "list", "--popular"])
         .assert()
         .success()
         .stdout(predicate::str::contains("mit"))
@@ -236,7 +571,7 @@ fn test_license_list_popular() {
 fn test_license_list_non_software() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--non-software"])
+    cmd.args(["license", "list", "--non-software"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Non-Software Licenses"))
@@ -249,7 +584,7 @@ fn test_license_list_non_software() {
 fn test_license_list_search_wildcard() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--search", "mit"])
+    cmd.args(["license", "list", "--search", "mit"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Licenses matching"))
@@ -260,7 +595,7 @@ fn test_license_list_search_wildcard() {
 fn test_license_list_osi_approved() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--osi-approved"])
+    cmd.args(["license", "list", "--osi-approved"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Available SPDX licenses"))
@@ -271,7 +606,7 @@ fn test_license_list_osi_approved() {
 fn test_license_list_fsf_libre() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--fsf-libre"])
+    cmd.args(["license", "list", "--fsf-libre"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Available SPDX licenses"));
@@ -281,7 +616,7 @@ fn test_license_list_fsf_libre() {
 fn test_license_list_include_deprecated() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--include-deprecated"])
+    cmd.args(["license", "list", "--include-deprecated"])
         .assert()
         .success()
         .stdout(predicate::str::contains("deprecated"));
@@ -291,19 +626,73 @@ fn test_license_list_include_deprecated() {
 fn test_license_list_unknown_argument() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "list", "--unknown"])
+    cmd.args(["license", "list", "--unknown"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("Unknown argument"));
 }
 
+#[test]
+fn test_license_list_exceptions() {
+    let repo = TestRepo::new();
+    seed_exception_caches(&repo.home);
+    let list = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "list", "--exceptions"]).args(args);
+        cmd.assert().success()
+    };
+
+    list(&[])
+        .stdout(predicate::str::contains(
+            "Available SPDX license exceptions (2 found)",
+        ))
+        .stdout(predicate::str::contains("LLVM-exception"))
+        .stdout(predicate::str::contains("Classpath-exception-2.0"))
+        .stdout(predicate::str::contains("Nokia-Qt-exception-1.1").not());
+
+    list(&["--search", "llvm"])
+        .stdout(predicate::str::contains("matching 'llvm' (1 found)"))
+        .stdout(predicate::str::contains("Classpath exception 2.0").not());
+
+    list(&["--include-deprecated"]).stdout(predicate::str::contains("Nokia-Qt-exception-1.1"));
+}
+
+#[test]
+fn test_license_list_exceptions_refreshes_stale_cache() {
+    let repo = TestRepo::new();
+    let cache_dir = repo.home.join(".local").join("share").join("gh-templates");
+    seed_cache(
+        &cache_dir,
+        "spdx_exception_cache",
+        &[(
+            "Stale-exception",
+            serde_json::json!({
+                "licenseExceptionId": "Stale-exception",
+                "name": "Stale exception",
+                "isDeprecatedLicenseId": false,
+            }),
+        )],
+    );
+    let cache_file = cache_dir.join("spdx_exception_cache.json");
+    let mut cache: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&cache_file).unwrap()).unwrap();
+    cache["metadata"]["last_updated"] = serde_json::json!(0);
+    fs::write(&cache_file, cache.to_string()).unwrap();
+
+    // A stale list is fetched again rather than shown as is
+    repo.command()
+        .args(["license", "list", "--exceptions"])
+        .assert()
+        .stdout(predicate::str::contains("Stale-exception").not());
+}
+
 // --------     PREVIEW COMMAND TESTS     --------
 
 #[test]
 fn test_license_preview_with_update_cache() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "preview", "mit", "--update-cache"])
+    cmd.args(["license", "preview", "mit", "--update-cache"])
         .assert()
         .success()
         .stdout(predicate::str::contains("License:"))
@@ -314,7 +703,7 @@ fn test_license_preview_with_update_cache() {
 fn test_license_preview_with_all_flags() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&[
+    cmd.args([
         "license",
         "preview",
         "mit",
@@ -337,20 +726,1022 @@ fn test_license_preview_with_all_flags() {
 fn test_license_preview_nonexistent_license() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "preview", "not-a-license"])
+    cmd.args(["license", "preview", "not-a-license"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Could not fetch license text"))
         .stdout(predicate::str::contains("404 Not Found"));
 }
 
+#[test]
+fn test_license_preview_exception() {
+    let repo = TestRepo::new();
+    seed_exception_caches(&repo.home);
+
+    repo.command()
+        .args(["license", "preview", "llvm-exception"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exception: LLVM Exception (LLVM-exception)",
+        ))
+        .stdout(predicate::str::contains(
+            "LLVM Exceptions to the Apache 2.0 License",
+        ));
+
+    repo.command()
+        .args(["license", "preview", "LLVM-exception", "--details"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exception ID: LLVM-exception"))
+        .stdout(predicate::str::contains(
+            "gh-templates license add \"<LICENSE> WITH LLVM-exception\"",
+        ));
+}
+
+// --------     DETECT COMMAND TESTS     --------
+
+#[test]
+fn test_license_detect_standard_text() {
+    let repo = TestRepo::new();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+
+    repo.command()
+        .args(["license", "detect"])
+        .arg(&repo.path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "LICENSE: MIT (MIT License), 100.0% confidence",
+        ))
+        .stdout(predicate::str::contains("Copyright (c) 2024 Jane Doe"))
+        .stdout(predicate::str::contains("Matches the standard text."));
+}
+
+#[test]
+fn test_license_detect_manifest_disagreement() {
+    let repo = TestRepo::new();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+    fs::write(
+        repo.path.join("Cargo.toml"),
+        "[package]\nname = \"engine\"\nlicense = \"MIT/Apache-2.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("package.json"),
+        "{ \"name\": \"web\", \"license\": \"SEE LICENSE IN LICENSE\" }",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("pyproject.toml"),
+        "[project]\nname = \"py\"\nlicense = \"mit\"\n",
+    )
+    .unwrap();
+
+    repo.command()
+        .args(["license", "detect"])
+        .arg(&repo.path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Cargo.toml [package] license is \"MIT/Apache-2.0\" but the license files are MIT",
+        ))
+        .stdout(predicate::str::contains("package.json").not())
+        .stdout(predicate::str::contains("pyproject.toml").not());
+}
+
+#[test]
+fn test_license_detect_modified_text() {
+    let repo = TestRepo::new();
+    let modified = MIT_LICENSE_FILE.replace("free of charge, ", "").replace(
+        "SOFTWARE.\n",
+        "SOFTWARE.\n\nThe Software shall be used for Good, not Evil.\n",
+    );
+    fs::write(repo.path.join("COPYING.md"), modified).unwrap();
+
+    repo.command()
+        .args(["license", "detect"])
+        .arg(repo.path.join("COPYING.md"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("COPYING.md: MIT (MIT License)"))
+        .stdout(predicate::str::contains("2 non-standard modification(s):"))
+        .stdout(predicate::str::contains(
+            "permission is hereby granted [-free of charge-] to any person obtaining",
+        ))
+        .stdout(predicate::str::contains(
+            "{+the software shall be used for good not evil+}",
+        ));
+}
+
+#[test]
+fn test_license_detect_no_match() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path.join("LICENSE.txt"),
+        "All rights reserved. Do not copy this software without asking first.\n",
+    )
+    .unwrap();
+
+    repo.command()
+        .args(["license", "detect"])
+        .arg(&repo.path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "LICENSE.txt: no SPDX license matched (closest:",
+        ))
+        .stderr(predicate::str::contains(
+            "No SPDX license matched 1 of 1 file(s)",
+        ));
+}
+
+#[test]
+fn test_license_detect_missing_file() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["license", "detect"])
+        .arg(&repo.path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No LICENSE or COPYING file found"));
+}
+
+// --------     HEADER COMMAND TESTS     --------
+
+#[test]
+fn test_license_header_add() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("src")).unwrap();
+    fs::create_dir_all(repo.path.join("target")).unwrap();
+
+    fs::write(repo.path.join(".gitignore"), "target/\n").unwrap();
+    fs::write(
+        repo.path.join("src/tool.py"),
+        "#!/usr/bin/env python3\n# -*- coding: utf-8 -*-\nprint('hi')\n",
+    )
+    .unwrap();
+    fs::write(repo.path.join("src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(repo.path.join("src/style.css"), "body {}\n").unwrap();
+    fs::write(
+        repo.path.join("pom.xml"),
+        "<?xml version=\"1.0\"?>\n<project/>\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("README.md"),
+        "---\ntitle: Demo\n---\n\n# Demo\n",
+    )
+    .unwrap();
+    fs::write(repo.path.join("package.json"), "{}\n").unwrap();
+    fs::write(repo.path.join("target/generated.rs"), "fn generated() {}\n").unwrap();
+
+    let args = [
+        "license",
+        "header",
+        "add",
+        "--license",
+        "mit",
+        "--holder",
+        "Jane Doe",
+        "--year",
+        "2025",
+    ];
+    repo.command()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added headers to 3 file(s):"))
+        .stdout(predicate::str::contains(
+            "Skipped files with no known comment syntax: .json (1)",
+        ))
+        .stdout(predicate::str::contains(
+            "Skipped 3 configuration or documentation file(s)",
+        ));
+    assert_eq!(
+        fs::read_to_string(repo.path.join(".gitignore")).unwrap(),
+        "target/\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("README.md")).unwrap(),
+        "---\ntitle: Demo\n---\n\n# Demo\n"
+    );
+
+    // Configuration and documentation files are opt-in
+    repo.command()
+        .args(args)
+        .args(["--include", "*.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added headers to 1 file(s):"))
+        .stdout(predicate::str::contains("> README.md"));
+    repo.command()
+        .args(args)
+        .arg("pom.xml")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added headers to 1 file(s):"))
+        .stdout(predicate::str::contains("> pom.xml"));
+
+    let header = "SPDX-FileCopyrightText: 2025 Jane Doe\n";
+    assert_eq!(
+        fs::read_to_string(repo.path.join("src/tool.py")).unwrap(),
+        format!(
+            "#!/usr/bin/env python3\n# -*- coding: utf-8 -*-\n# {}# SPDX-License-Identifier: MIT\n\nprint('hi')\n",
+            header
+        )
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("src/main.rs")).unwrap(),
+        format!(
+            "// {}// SPDX-License-Identifier: MIT\n\nfn main() {{}}\n",
+            header
+        )
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("src/style.css")).unwrap(),
+        format!(
+            "/*\n * {} * SPDX-License-Identifier: MIT\n */\n\nbody {{}}\n",
+            header
+        )
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("pom.xml")).unwrap(),
+        format!(
+            "<?xml version=\"1.0\"?>\n<!--\n{}SPDX-License-Identifier: MIT\n-->\n\n<project/>\n",
+            header
+        )
+    );
+    assert!(
+        fs::read_to_string(repo.path.join("README.md"))
+            .unwrap()
+            .starts_with("---\ntitle: Demo\n---\n<!--\n")
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path.join("target/generated.rs")).unwrap(),
+        "fn generated() {}\n"
+    );
+
+    // A second run finds every header in place
+    let before = fs::read_to_string(repo.path.join("src/tool.py")).unwrap();
+    repo.command()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No files need a header"))
+        .stdout(predicate::str::contains("3 file(s) already have a header"));
+    assert_eq!(
+        fs::read_to_string(repo.path.join("src/tool.py")).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn test_license_header_add_comment_style() {
+    let repo = TestRepo::new();
+    fs::write(repo.path.join("deploy.tpl"), "name: {{ .Name }}\n").unwrap();
+
+    repo.command()
+        .args([
+            "license",
+            "header",
+            "add",
+            "-l",
+            "ISC",
+            "--holder",
+            "Acme",
+            "--year",
+            "2024",
+            "--comment-style",
+            "tpl=hash",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("> deploy.tpl"));
+
+    assert_eq!(
+        fs::read_to_string(repo.path.join("deploy.tpl")).unwrap(),
+        "# SPDX-FileCopyrightText: 2024 Acme\n# SPDX-License-Identifier: ISC\n\nname: {{ .Name }}\n"
+    );
+
+    repo.command()
+        .args([
+            "license",
+            "header",
+            "add",
+            "-l",
+            "ISC",
+            "--holder",
+            "Acme",
+            "--comment-style",
+            "tpl=curly",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown comment style 'curly'"));
+}
+
+#[test]
+fn test_license_header_dual_license() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("src")).unwrap();
+    fs::write(repo.path.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+    fs::write(
+        repo.path.join("src/old.rs"),
+        "// SPDX-FileCopyrightText: 2024 Acme\n// SPDX-License-Identifier: (isc or MIT)\n\npub fn g() {}\n",
+    )
+    .unwrap();
+    let header = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "header"]).args(args);
+        cmd.assert()
+    };
+
+    header(&[
+        "add",
+        "--license",
+        "isc or mit",
+        "--holder",
+        "Acme",
+        "--year",
+        "2024",
+    ])
+    .success()
+    .stdout(predicate::str::contains("1 file(s) already have a header"));
+    assert_eq!(
+        fs::read_to_string(repo.path.join("src/lib.rs")).unwrap(),
+        "// SPDX-FileCopyrightText: 2024 Acme\n// SPDX-License-Identifier: ISC OR MIT\n\npub fn f() {}\n"
+    );
+
+    header(&[
+        "check",
+        "--license",
+        "ISC OR MIT",
+        "--year-policy",
+        "ignore",
+    ])
+    .success()
+    .stdout(predicate::str::contains(
+        "All 2 file(s) have an up-to-date ISC OR MIT header",
+    ));
+    header(&["check", "--license", "MIT", "--year-policy", "ignore"])
+        .code(8)
+        .stdout(predicate::str::contains(
+            "Declares ISC OR MIT, expected MIT",
+        ));
+}
+
+#[test]
+fn test_license_header_add_other_license() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("vendor")).unwrap();
+    let vendored = "/* SPDX-License-Identifier: GPL-2.0-only */\nint x;\n";
+    fs::write(repo.path.join("vendor/lib.c"), vendored).unwrap();
+
+    repo.command()
+        .args(["license", "header", "add", "-l", "MIT", "--holder", "Acme"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1 file(s) declare another license, left unchanged:",
+        ))
+        .stdout(predicate::str::contains("vendor/lib.c (GPL-2.0-only)"));
+
+    assert_eq!(
+        fs::read_to_string(repo.path.join("vendor/lib.c")).unwrap(),
+        vendored
+    );
+}
+
+#[test]
+fn test_license_header_add_detects_license() {
+    let repo = TestRepo::new();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+    fs::write(repo.path.join("lib.go"), "package lib\n").unwrap();
+
+    repo.command()
+        .args([
+            "license", "header", "add", "--holder", "Acme", "--year", "2025",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Using MIT from the LICENSE file"));
+
+    assert!(
+        fs::read_to_string(repo.path.join("lib.go"))
+            .unwrap()
+            .starts_with("// SPDX-FileCopyrightText: 2025 Acme\n// SPDX-License-Identifier: MIT\n")
+    );
+}
+
+#[test]
+fn test_license_header_check_up_to_date() {
+    let repo = TestRepo::new();
+    commit_headed_files(&repo.path);
+
+    repo.command()
+        .args(["license", "header", "check", "--license", "mit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "All 2 file(s) have an up-to-date MIT header",
+        ));
+}
+
+#[test]
+fn test_license_header_check_exit_codes() {
+    let repo = TestRepo::new();
+    commit_headed_files(&repo.path);
+    let check = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "header", "check"]).args(args);
+        cmd.assert()
+    };
+
+    // Missing only
+    fs::write(repo.path.join("src/new.rs"), "pub fn g() {}\n").unwrap();
+    commit_all(&repo.path, "2024-07-01T12:00:00");
+    check(&["--license", "MIT"])
+        .code(4)
+        .stdout(predicate::str::contains(
+            "src/new.rs:1: No SPDX-License-Identifier header",
+        ))
+        .stdout(predicate::str::contains("Checked 3 file(s): 1 missing"));
+
+    // Mismatched only
+    check(&["--exclude", "src/new.rs", "--license", "ISC"]).code(8);
+
+    // A commit in 2025 makes the 2024 notice outdated
+    fs::write(
+        repo.path.join("src/tool.py"),
+        "#!/usr/bin/env python3\n# SPDX-FileCopyrightText: 2024 Acme\n# SPDX-License-Identifier: GPL-2.0-only\nprint()\n",
+    )
+    .unwrap();
+    commit_all(&repo.path, "2025-01-15T12:00:00");
+    check(&["--license", "MIT"])
+        .code(4 + 8 + 16)
+        .stdout(predicate::str::contains(
+            "src/tool.py:2: Copyright year 2024 is older than 2025",
+        ))
+        .stdout(predicate::str::contains(
+            "src/tool.py:3: Declares GPL-2.0-only, expected MIT",
+        ))
+        .stdout(predicate::str::contains(
+            "Checked 3 file(s): 1 missing, 1 mismatched, 1 outdated",
+        ));
+
+    check(&["-l", "MIT", "--year-policy", "ignore", "--include", "*.py"]).code(8);
+
+    // A header without a copyright notice is not reported as a missing header
+    fs::write(
+        repo.path.join("src/bare.rs"),
+        "// SPDX-License-Identifier: MIT\n\npub fn b() {}\n",
+    )
+    .unwrap();
+    check(&["-l", "MIT", "--include", "src/bare.rs"])
+        .code(32)
+        .stdout(predicate::str::contains(
+            "src/bare.rs:1: No copyright notice with a year",
+        ))
+        .stdout(predicate::str::contains(
+            "Checked 1 file(s): 1 no-copyright",
+        ));
+}
+
+#[test]
+fn test_license_header_check_globs_and_report() {
+    let repo = TestRepo::new();
+    commit_headed_files(&repo.path);
+    fs::create_dir_all(repo.path.join("vendor")).unwrap();
+    fs::write(repo.path.join("vendor/dep.rs"), "pub fn v() {}\n").unwrap();
+    fs::write(repo.path.join("src/gen.rs"), "pub fn gen() {}\n").unwrap();
+    commit_all(&repo.path, "2024-06-01T12:00:00");
+
+    let report = repo.temp_dir.path().join("report.json");
+    repo.command()
+        .args([
+            "license",
+            "header",
+            "check",
+            "-l",
+            "MIT",
+            "--include",
+            "src/**",
+            "--exclude",
+            "*.py",
+            "--report",
+        ])
+        .arg(&report)
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("src/gen.rs"))
+        .stdout(predicate::str::contains("vendor/dep.rs").not())
+        .stdout(predicate::str::contains("Checked 2 file(s): 1 missing"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(json["license"], "MIT");
+    assert_eq!(json["checked"], 2);
+    assert_eq!(json["findings"][0]["path"], "src/gen.rs");
+    assert_eq!(json["findings"][0]["kind"], "missing");
+
+    let sarif = repo.temp_dir.path().join("report.sarif");
+    repo.command()
+        .args(["license", "header", "check", "-l", "MIT", "--report"])
+        .arg(&sarif)
+        .args(["--report-format", "sarif"])
+        .assert()
+        .code(4);
+
+    let sarif: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&sarif).unwrap()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "missing-header");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/gen.rs"
+    );
+}
+
+// --------     COMPAT COMMAND TESTS     --------
+
+#[test]
+fn test_license_compat_pairs() {
+    let repo = TestRepo::new();
+    let compat = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "compat"]).args(args);
+        cmd.assert()
+    };
+
+    compat(&["mit", "gpl-3.0-only"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Compatible: code under MIT in a project under GPL-3.0-only",
+        ))
+        .stdout(predicate::str::contains(
+            "Keep the copyright and license notices of the MIT code",
+        ));
+    compat(&["apache-2.0", "GPL-2.0-only"])
+        .failure()
+        .stdout(predicate::str::contains("Incompatible"))
+        .stdout(predicate::str::contains("patent termination"))
+        .stderr(predicate::str::contains("cannot be included in a project"));
+    compat(&["apache-2.0", "GPL-2.0+"])
+        .success()
+        .stdout(predicate::str::contains("Compatible with conditions"))
+        .stdout(predicate::str::contains(
+            "the combined work would have to be distributed under GPL-3.0",
+        ));
+    compat(&["gpl-3.0-only", "mit"])
+        .failure()
+        .stdout(predicate::str::contains("Incompatible"))
+        .stdout(predicate::str::contains("strong copyleft"));
+    compat(&["agpl-3.0-or-later", "gpl-3.0-or-later"])
+        .success()
+        .stdout(predicate::str::contains("Compatible with conditions"))
+        .stdout(predicate::str::contains("section 13"));
+    compat(&["mpl-2.0", "gpl-2.0-only"])
+        .success()
+        .stdout(predicate::str::contains("Compatible: code under MPL-2.0"))
+        .stdout(predicate::str::contains("Secondary Licenses"));
+
+    // One fitting option of a choice is enough
+    compat(&["GPL-3.0-only OR Apache-2.0", "MIT"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Compatible: code under GPL-3.0-only OR Apache-2.0 in a project under MIT",
+        ));
+
+    repo.command()
+        .args(["license", "compat", "mit"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_license_compat_project() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("src")).unwrap();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+    fs::write(
+        repo.path.join("src/lib.rs"),
+        "// SPDX-License-Identifier: MIT\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("src/vendored.rs"),
+        "// SPDX-License-Identifier: Apache-2.0\n",
+    )
+    .unwrap();
+    let compat = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "compat", "--project"]).args(args);
+        cmd.assert()
+    };
+
+    compat(&[])
+        .success()
+        .stdout(predicate::str::contains("Using MIT from the LICENSE file"))
+        .stdout(predicate::str::contains("Apache-2.0 in src/vendored.rs"))
+        .stdout(predicate::str::contains(
+            "Compatible: code under Apache-2.0 in a project under MIT",
+        ))
+        .stdout(predicate::str::contains("src/lib.rs").not());
+
+    compat(&["lgpl-3.0-only"])
+        .success()
+        .stdout(predicate::str::contains("Compatible with conditions"));
+
+    fs::write(
+        repo.path.join("src/copied.rs"),
+        "// SPDX-License-Identifier: GPL-3.0-or-later\n",
+    )
+    .unwrap();
+    compat(&[])
+        .failure()
+        .stdout(predicate::str::contains(
+            "GPL-3.0-or-later in src/copied.rs",
+        ))
+        .stderr(predicate::str::contains(
+            "GPL-3.0-or-later cannot be included in a project under MIT",
+        ));
+}
+
+// --------     REUSE COMMAND TESTS     --------
+
+#[test]
+fn test_license_reuse_lint() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("src")).unwrap();
+    fs::create_dir_all(repo.path.join("LICENSES")).unwrap();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+    fs::write(
+        repo.path.join("src/lib.rs"),
+        "// SPDX-FileCopyrightText: 2024 Acme\n// SPDX-License-Identifier: MIT\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("src/old.rs"),
+        "// SPDX-FileCopyrightText: 2024 Acme\n// SPDX-License-Identifier: Foo-1.0\n",
+    )
+    .unwrap();
+    fs::write(repo.path.join("src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(repo.path.join("logo.png"), b"\x89PNG\x00\x00").unwrap();
+    fs::write(
+        repo.path.join("logo.png.license"),
+        "SPDX-FileCopyrightText: 2024 Acme\nSPDX-License-Identifier: MIT\n",
+    )
+    .unwrap();
+    fs::write(repo.path.join("data.json"), "{}\n").unwrap();
+    fs::write(repo.path.join("LICENSES/ISC.txt"), "ISC").unwrap();
+
+    repo.command()
+        .args(["license", "reuse", "--lint"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Bad license Foo-1.0 in src/old.rs",
+        ))
+        .stdout(predicate::str::contains(
+            "Missing license text LICENSES/MIT.txt, used in logo.png, src/lib.rs",
+        ))
+        .stdout(predicate::str::contains(
+            "Unused license text LICENSES/ISC.txt",
+        ))
+        .stdout(predicate::str::contains(
+            "src/main.rs has no copyright notice or license",
+        ))
+        .stdout(predicate::str::contains(
+            "data.json has no copyright notice or license",
+        ))
+        .stdout(predicate::str::contains("logo.png has").not())
+        .stdout(predicate::str::contains(
+            "Files with license information: 3 / 5",
+        ))
+        .stdout(predicate::str::contains(
+            "license header add` to add headers to 1 file(s)",
+        ))
+        .stderr(predicate::str::contains(
+            "does not follow the REUSE specification",
+        ));
+
+    assert!(!repo.path.join("REUSE.toml").exists());
+    assert!(!repo.path.join("LICENSES/MIT.txt").exists());
+}
+
+#[test]
+fn test_license_reuse_annotate_and_download() {
+    let repo = TestRepo::new();
+    fs::create_dir_all(repo.path.join("assets")).unwrap();
+    fs::write(repo.path.join("LICENSE"), MIT_LICENSE_FILE).unwrap();
+    fs::write(
+        repo.path.join("main.py"),
+        "# SPDX-FileCopyrightText: 2024 Acme\n# SPDX-License-Identifier: ISC\n",
+    )
+    .unwrap();
+    fs::write(repo.path.join("assets/a.png"), b"\x89PNG\x00\x00").unwrap();
+    fs::write(repo.path.join("assets/b.png"), b"\x89PNG\x00\x01").unwrap();
+    fs::write(repo.path.join("package.json"), "{}\n").unwrap();
+    fs::write(
+        repo.path.join("REUSE.toml"),
+        "version = 1\n\n[[annotations]]\npath = \"docs/**\"\nSPDX-FileCopyrightText = \"2024 Acme\"\nSPDX-License-Identifier = \"MIT\"\n",
+    )
+    .unwrap();
+
+    repo.command()
+        .args(["license", "reuse", "--holder", "Acme", "--year", "2025"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Using MIT from the LICENSE file"))
+        .stdout(predicate::str::contains(
+            "Annotated 3 file(s) that cannot carry a header in REUSE.toml",
+        ))
+        .stdout(predicate::str::contains(
+            "LICENSES/ISC.txt - has been added",
+        ))
+        .stdout(predicate::str::contains(
+            "LICENSES/MIT.txt - has been added",
+        ))
+        .stdout(predicate::str::contains(
+            "The repository follows the REUSE specification",
+        ));
+
+    let reuse_toml = fs::read_to_string(repo.path.join("REUSE.toml")).unwrap();
+    assert!(reuse_toml.starts_with("version = 1\n\n[[annotations]]\npath = \"docs/**\""));
+    assert!(reuse_toml.contains(
+        "[[annotations]]\npath = [\"assets/*.png\", \"package.json\"]\nSPDX-FileCopyrightText = \"2025 Acme\"\nSPDX-License-Identifier = \"MIT\"\n"
+    ));
+    assert!(
+        fs::read_to_string(repo.path.join("LICENSES/MIT.txt"))
+            .unwrap()
+            .starts_with("MIT License")
+    );
+
+    repo.command()
+        .args(["license", "reuse", "--lint"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Used licenses: ISC, MIT"));
+}
+
+// --------     NOTICE COMMAND TESTS     --------
+
+#[test]
+fn test_license_notice_init_and_add() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path.join("Cargo.toml"),
+        "[package]\nname = \"rocket\"\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path.join("foo-NOTICE"),
+        "libfoo\nCopyright 2015 The Foo Foundation\n",
+    )
+    .unwrap();
+    let notice = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "notice"]).args(args);
+        cmd.assert()
+    };
+
+    notice(&["add", "libfoo"])
+        .failure()
+        .stderr(predicate::str::contains("No NOTICE file"));
+    notice(&["init", "--holder", "Acme", "--year", "2025"]).success();
+    assert_eq!(
+        fs::read_to_string(repo.path.join("NOTICE")).unwrap(),
+        "rocket\nCopyright 2025 Acme\n"
+    );
+
+    notice(&[
+        "add",
+        "libfoo",
+        "--license",
+        "Apache-2.0",
+        "--url",
+        "https://example.com/libfoo",
+        "--notice",
+        "foo-NOTICE",
+    ])
+    .success()
+    .stdout(predicate::str::contains("Added the attribution of libfoo"));
+    notice(&[
+        "add",
+        "zlib",
+        "-l",
+        "Zlib",
+        "--copyright",
+        "1995-2024 Jean-loup Gailly",
+    ])
+    .success();
+    notice(&["add", "LIBFOO"])
+        .failure()
+        .stderr(predicate::str::contains("already attributes 'LIBFOO'"));
+    notice(&[
+        "add",
+        "zlib",
+        "--copyright",
+        "(C) 1995 Mark Adler",
+        "--force",
+    ])
+    .success()
+    .stdout(predicate::str::contains("Replaced the attribution of zlib"));
+
+    let separator = "-".repeat(80);
+    assert_eq!(
+        fs::read_to_string(repo.path.join("NOTICE")).unwrap(),
+        format!(
+            "rocket\nCopyright 2025 Acme\n\n{separator}\nThis product bundles libfoo (https://example.com/libfoo),\nwhich is available under Apache-2.0.\n\nlibfoo\nCopyright 2015 The Foo Foundation\n\n{separator}\nThis product bundles zlib.\n\nCopyright (C) 1995 Mark Adler\n"
+        )
+    );
+    notice(&["check"])
+        .success()
+        .stdout(predicate::str::contains(
+            "NOTICE names rocket and attributes 2 bundled component(s)",
+        ));
+
+    fs::write(
+        repo.path.join("NOTICE"),
+        format!("rocket\n\n{separator}\nThis product bundles zlib.\n\n{separator}\nThis product bundles zlib.\n"),
+    )
+    .unwrap();
+    notice(&["check"])
+        .failure()
+        .stdout(predicate::str::contains("NOTICE has no copyright line"))
+        .stdout(predicate::str::contains("NOTICE attributes zlib twice"));
+}
+
+#[test]
+fn test_license_notice_for_apache() {
+    let repo = TestRepo::new();
+    seed_licenses(
+        &repo.home,
+        &[
+            ("MIT", "MIT License", MIT_TEMPLATE, MIT_LICENSE_TEXT),
+            ("Apache-2.0", "Apache License 2.0", "", APACHE_LICENSE_TEXT),
+        ],
+    );
+    fs::write(
+        repo.path.join("package.json"),
+        "{\n  \"name\": \"rocket\"\n}\n",
+    )
+    .unwrap();
+    let license = |args: &[&str]| {
+        let mut cmd = repo.command();
+        cmd.arg("license").args(args);
+        cmd.assert()
+    };
+
+    license(&["add", "apache-2.0", "--no-notice"]).success();
+    assert!(!repo.path.join("NOTICE").exists());
+    license(&["detect"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Apache-2.0 projects should ship a NOTICE file",
+        ));
+    license(&["notice", "check"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "licensed under Apache-2.0 but has no NOTICE file",
+        ));
+
+    license(&[
+        "add",
+        "apache-2.0",
+        "--force",
+        "--param",
+        "name of copyright owner=Acme",
+        "--param",
+        "yyyy=2025",
+    ])
+    .success()
+    .stdout(predicate::str::contains("NOTICE - has been added"));
+    assert_eq!(
+        fs::read_to_string(repo.path.join("NOTICE")).unwrap(),
+        "rocket\nCopyright 2025 Acme\n"
+    );
+    license(&["detect"])
+        .success()
+        .stdout(predicate::str::contains("NOTICE").not());
+    license(&["notice", "check"]).success();
+
+    // An existing NOTICE is kept
+    fs::write(repo.path.join("NOTICE"), "rocket\nCopyright 2020 Acme\n").unwrap();
+    license(&["add", "apache-2.0", "--force"]).success();
+    assert_eq!(
+        fs::read_to_string(repo.path.join("NOTICE")).unwrap(),
+        "rocket\nCopyright 2020 Acme\n"
+    );
+}
+
+// --------     CHOOSE COMMAND TESTS     --------
+
+#[test]
+fn test_license_choose_shortlist() {
+    let repo = TestRepo::new();
+    seed_choose_caches(&repo.home);
+    let choose = |args: &[&str], stdin: &str| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "choose", "--no-add"])
+            .args(args)
+            .write_stdin(stdin);
+        cmd.assert().success()
+    };
+
+    // Answers given as options are not asked again; the rest are skipped
+    choose(&["--patents", "yes", "--disclose-source", "no"], "")
+        .stdout(predicate::str::contains("Should the license explicitly grant").not())
+        .stdout(predicate::str::contains(
+            "Should keeping the copyright notice",
+        ))
+        .stdout(predicate::str::contains("Recommended licenses:"))
+        .stdout(predicate::str::contains(
+            "1. Apache License 2.0 (Apache-2.0)",
+        ))
+        .stdout(predicate::str::contains("✓ grants patent rights"))
+        .stdout(predicate::str::contains(
+            "✓ does not require sharing the source",
+        ))
+        .stdout(predicate::str::contains(
+            "Requires: License and copyright notice, State changes",
+        ))
+        .stdout(predicate::str::contains("(MIT)").not())
+        .stdout(predicate::str::contains("GPL-3.0").not());
+
+    // Without answers, the featured licenses with the fewest conditions come first
+    choose(&[], "\n\n\n\n")
+        .stdout(predicate::str::contains("1. MIT License (MIT)"))
+        .stdout(predicate::str::contains(
+            "2. Apache License 2.0 (Apache-2.0)",
+        ))
+        .stdout(predicate::str::contains(
+            "3. GNU General Public License v3.0",
+        ))
+        .stdout(predicate::str::contains("AGPL-3.0").not());
+
+    choose(&[], "y\nyes\nmaybe\ny\n\n")
+        .stdout(predicate::str::contains("'maybe' is not y or n"))
+        .stdout(predicate::str::contains(
+            "1. GNU Affero General Public License v3.0 (AGPL-3.0)",
+        ))
+        .stdout(predicate::str::contains(
+            "✓ treats network use as distribution",
+        ))
+        .stdout(predicate::str::contains("Requires: License and copyright notice, State changes, Disclose source, Network use is distribution, Same license"))
+        .stdout(predicate::str::contains("(GPL-3.0)").not());
+
+    // Contradicting answers fall back to the closest licenses, marking what they miss
+    choose(
+        &["--attribution-only", "yes", "--disclose-source", "yes"],
+        "",
+    )
+    .stdout(predicate::str::contains(
+        "No popular license fits every answer",
+    ))
+    .stdout(predicate::str::contains("1. MIT License (MIT)"))
+    .stdout(predicate::str::contains(
+        "! does not require sharing the source",
+    ));
+}
+
+#[test]
+fn test_license_choose_adds_license() {
+    let repo = TestRepo::new();
+    seed_choose_caches(&repo.home);
+    let choose = |stdin: &str| {
+        let mut cmd = repo.command();
+        cmd.args(["license", "choose", "--attribution-only", "yes"])
+            .args(["--patents", "any", "--disclose-source", "any"])
+            .args(["--network-use", "any"])
+            .write_stdin(stdin);
+        cmd.assert()
+    };
+
+    choose("").success().stdout(predicate::str::contains(
+        "Run `gh-templates license add MIT` to add it later.",
+    ));
+    assert!(!repo.path.join("LICENSE").exists());
+
+    choose("9\n")
+        .failure()
+        .stderr(predicate::str::contains("There is no license number 9"));
+
+    choose("1\n").success();
+    let content = fs::read_to_string(repo.path.join("LICENSE")).unwrap();
+    assert!(content.contains("Permission is hereby granted, free of charge"));
+}
+
 // --------     HELP COMMAND TEST     --------
 
 #[test]
 fn test_license_help_command() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["license", "help"])
+    cmd.args(["license", "help"])
         .assert()
         .success()
         .stdout(predicate::str::contains(
@@ -366,3 +1757,344 @@ fn test_license_help_command() {
         .stdout(predicate::str::contains("help"))
         .stdout(predicate::str::contains("-h, --help"));
 }
+
+// --------     TEST FIXTURES     --------
+
+/// A git repository in a temporary directory, next to an isolated home whose SPDX caches
+/// hold MIT and ISC.
+struct TestRepo {
+    temp_dir: TempDir,
+    path: PathBuf,
+    home: PathBuf,
+}
+
+impl TestRepo {
+    fn new() -> Self {
+        let temp_dir = setup_test_env();
+        let path = temp_dir.path().join("repo");
+        let home = temp_dir.path().join("home");
+
+        fs::create_dir_all(&path).unwrap();
+        create_git_repo(&path);
+        seed_license_caches(&home);
+        Self {
+            temp_dir,
+            path,
+            home,
+        }
+    }
+
+    /// A `gh-templates` command run in the repository with the isolated caches.
+    fn command(&self) -> AssertCommand {
+        let mut cmd = isolated_command(&self.home, &self.temp_dir.path().join("system"));
+        cmd.current_dir(&self.path);
+        cmd
+    }
+}
+
+const MIT_TEMPLATE: &str = "<<beginOptional>> MIT License<<endOptional>>\n\n<<var;name=\"copyright\";original=\"Copyright (c) <year> <copyright holders>\";match=\".{0,5000}\">>\n\nPermission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the \"Software\"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:\n\nThe above copyright notice and this permission notice<<beginOptional>> (including the next paragraph)<<endOptional>> shall be included in all copies or substantial portions of the Software.\n\nTHE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.\n";
+
+const ISC_TEMPLATE: &str = "<<beginOptional>> ISC License<<endOptional>>\n\n<<var;name=\"copyright\";original=\"Copyright (c) 2004-2010 by Internet Systems Consortium, Inc. (\\\"ISC\\\")\";match=\".{0,5000}\">>\n\nPermission to use, copy, modify, and/or distribute this software for any purpose with or without fee is hereby granted, provided that the above copyright notice and this permission notice appear in all copies.\n\nTHE SOFTWARE IS PROVIDED \"AS IS\" AND <<var;name=\"copyrightHolder0\";original=\"ISC\";match=\".+\">> DISCLAIMS ALL WARRANTIES WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL <<var;name=\"copyrightHolder1\";original=\"ISC\";match=\".+\">> BE LIABLE FOR ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.\n";
+
+const MIT_LICENSE_FILE: &str = "MIT License
+
+Copyright (c) 2024 Jane Doe
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \u{201c}Software\u{201d}), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicence, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+";
+
+const MIT_LICENSE_TEXT: &str = "MIT License
+
+Copyright (c) <year> <copyright holders>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \"Software\"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+";
+
+const ISC_LICENSE_TEXT: &str = "ISC License
+
+Copyright (c) 2004-2010 by Internet Systems Consortium, Inc. (\"ISC\")
+
+Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted, provided that the above
+copyright notice and this permission notice appear in all copies.
+
+THE SOFTWARE IS PROVIDED \"AS IS\" AND ISC DISCLAIMS ALL WARRANTIES WITH REGARD
+TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY AND
+FITNESS. IN NO EVENT SHALL ISC BE LIABLE FOR ANY SPECIAL, DIRECT, INDIRECT, OR
+CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM LOSS OF USE, DATA
+OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS
+ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS
+SOFTWARE.
+";
+
+/// A shortened Apache-2.0 text, enough to be told apart from the other seeded licenses.
+const APACHE_LICENSE_TEXT: &str = "Apache License
+Version 2.0, January 2004
+http://www.apache.org/licenses/
+
+TERMS AND CONDITIONS FOR USE, REPRODUCTION, AND DISTRIBUTION
+
+4. Redistribution. You may reproduce and distribute copies of the Work or Derivative Works
+thereof in any medium, with or without modifications, and in Source or Object form, provided
+that You meet the following conditions: if the Work includes a \"NOTICE\" text file as part of
+its distribution, then any Derivative Works that You distribute must include a readable copy
+of the attribution notices contained within such NOTICE file.
+
+Copyright [yyyy] [name of copyright owner]
+
+Licensed under the Apache License, Version 2.0 (the \"License\"); you may not use this file
+except in compliance with the License.
+";
+
+/// Seeds the SPDX license list and text caches with MIT and ISC.
+fn seed_license_caches(home: &std::path::Path) {
+    seed_licenses(
+        home,
+        &[
+            ("MIT", "MIT License", MIT_TEMPLATE, MIT_LICENSE_TEXT),
+            ("ISC", "ISC License", ISC_TEMPLATE, ISC_LICENSE_TEXT),
+        ],
+    );
+}
+
+/// Seeds the SPDX license list and text caches with the given id, name, template and text.
+fn seed_licenses(home: &std::path::Path, licenses: &[(&str, &str, &str, &str)]) {
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+    let list: Vec<(&str, serde_json::Value)> = licenses
+        .iter()
+        .map(|(id, name, _, _)| {
+            (
+                *id,
+                serde_json::json!({
+                    "licenseId": id,
+                    "name": name,
+                    "isDeprecatedLicenseId": false,
+                    "isOsiApproved": true,
+                }),
+            )
+        })
+        .collect();
+    seed_cache(&cache_dir, "spdx_license_cache", &list);
+
+    let texts: Vec<(&str, serde_json::Value)> = licenses
+        .iter()
+        .map(|(id, name, template, text)| {
+            (
+                *id,
+                serde_json::json!({
+                    "licenseId": id,
+                    "name": name,
+                    "standardLicenseTemplate": template,
+                    "licenseText": text,
+                }),
+            )
+        })
+        .collect();
+    seed_cache(&cache_dir, "spdx_license_text_cache", &texts);
+}
+
+/// Seeds the SPDX exception list and text caches with two exceptions and a deprecated one.
+fn seed_exception_caches(home: &std::path::Path) {
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+    let exceptions = [
+        ("LLVM-exception", "LLVM Exception", false),
+        ("Classpath-exception-2.0", "Classpath exception 2.0", false),
+        (
+            "Nokia-Qt-exception-1.1",
+            "Nokia Qt LGPL exception 1.1",
+            true,
+        ),
+    ];
+
+    let list: Vec<(&str, serde_json::Value)> = exceptions
+        .iter()
+        .map(|(id, name, deprecated)| {
+            (
+                *id,
+                serde_json::json!({
+                    "licenseExceptionId": id,
+                    "name": name,
+                    "isDeprecatedLicenseId": deprecated,
+                }),
+            )
+        })
+        .collect();
+    seed_cache(&cache_dir, "spdx_exception_cache", &list);
+
+    seed_cache(
+        &cache_dir,
+        "spdx_exception_text_cache",
+        &[(
+            "LLVM-exception",
+            serde_json::json!({
+                "licenseExceptionId": "LLVM-exception",
+                "licenseExceptionText": "---- LLVM Exceptions to the Apache 2.0 License ----\n\nAs an exception, if, as a result of your compiling your source code, portions\nof this Software are embedded into an Object form of such source code, you\nmay redistribute such embedded portions in such Object form without complying\nwith the conditions of Sections 4(a), 4(b) and 4(d) of the License.\n",
+            }),
+        )],
+    );
+}
+
+/// Commits every file in `path` with the given author date.
+fn commit_all(path: &std::path::Path, date: &str) {
+    for args in [
+        vec!["add", "-A"],
+        vec![
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "commit",
+        ],
+    ] {
+        std::process::Command::new("git")
+            .args(args)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(path)
+            .output()
+            .unwrap();
+    }
+}
+
+/// Commits source files with headers for 2024, in 2024.
+fn commit_headed_files(temp_path: &std::path::Path) {
+    fs::create_dir_all(temp_path.join("src")).unwrap();
+    fs::write(
+        temp_path.join("src/lib.rs"),
+        "// SPDX-FileCopyrightText: 2022-2024 Acme\n// SPDX-License-Identifier: MIT\n\npub fn f() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join("src/tool.py"),
+        "#!/usr/bin/env python3\n# SPDX-FileCopyrightText: 2024 Acme\n# SPDX-License-Identifier: MIT\n",
+    )
+    .unwrap();
+    commit_all(temp_path, "2024-06-01T12:00:00");
+}
+
+/// Seeds the popular license list and the choosealicense.com rules of MIT, Apache-2.0,
+/// GPL-3.0 and AGPL-3.0.
+fn seed_choose_caches(home: &std::path::Path) {
+    let cache_dir = home.join(".local").join("share").join("gh-templates");
+    let permissions = [
+        "commercial-use",
+        "modifications",
+        "distribution",
+        "private-use",
+    ];
+    let licenses = [
+        (
+            "mit",
+            "MIT",
+            "MIT License",
+            true,
+            vec!["include-copyright"],
+            vec!["liability", "warranty"],
+        ),
+        (
+            "apache-2.0",
+            "Apache-2.0",
+            "Apache License 2.0",
+            true,
+            vec!["include-copyright", "document-changes"],
+            vec!["trademark-use", "liability", "warranty"],
+        ),
+        (
+            "gpl-3.0",
+            "GPL-3.0",
+            "GNU General Public License v3.0",
+            true,
+            vec![
+                "include-copyright",
+                "document-changes",
+                "disclose-source",
+                "same-license",
+            ],
+            vec!["liability", "warranty"],
+        ),
+        (
+            "agpl-3.0",
+            "AGPL-3.0",
+            "GNU Affero General Public License v3.0",
+            false,
+            vec![
+                "include-copyright",
+                "document-changes",
+                "disclose-source",
+                "network-use-disclose",
+                "same-license",
+            ],
+            vec!["liability", "warranty"],
+        ),
+    ];
+
+    let popular: Vec<(&str, serde_json::Value)> = licenses
+        .iter()
+        .map(|(key, spdx_id, name, ..)| {
+            (
+                *key,
+                serde_json::json!({ "key": key, "name": name, "spdx_id": spdx_id }),
+            )
+        })
+        .collect();
+    seed_cache(&cache_dir, "github_licenses_cache", &popular);
+
+    let rules: Vec<(&str, serde_json::Value)> = licenses
+        .iter()
+        .map(|(key, spdx_id, name, featured, conditions, limitations)| {
+            let mut granted = permissions.to_vec();
+            if *key != "mit" {
+                granted.push("patent-use");
+            }
+            (
+                *key,
+                serde_json::json!({
+                    "title": name,
+                    "spdx-id": spdx_id,
+                    "featured": featured,
+                    "hidden": false,
+                    "description": format!("The {}.", name),
+                    "permissions": granted,
+                    "conditions": conditions,
+                    "limitations": limitations,
+                }),
+            )
+        })
+        .collect();
+    seed_cache(&cache_dir, "choosealicense_cache", &rules);
+}
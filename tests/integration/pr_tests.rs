@@ -38,7 +38,7 @@ fn test_pr_add_default() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default"])
+    cmd.args(["pr", "add", "default"])
         .assert()
         .success()
         .stdout(predicate::str::contains(
@@ -57,7 +57,7 @@ fn test_pr_add_with_dir() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&[
+    cmd.args([
         "pr",
         "add",
         "default",
@@ -66,9 +66,9 @@ fn test_pr_add_with_dir() {
     ])
     .assert()
     .success()
-    .stdout(predicate::str::contains(format!(
-        "pull_request_template.md - has been added.",
-    )));
+    .stdout(predicate::str::contains(
+        "pull_request_template.md - has been added.".to_string(),
+    ));
 
     assert_file_exists(&target_dir.join("./pull_request_template.md"));
 }
@@ -86,7 +86,7 @@ fn test_pr_add_force_overwrite() {
 
     // Try to add without force (should fail)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["pr", "add", "default"])
+    cmd.args(["pr", "add", "default"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("already exists"));
@@ -95,7 +95,7 @@ fn test_pr_add_force_overwrite() {
     // Try with force flag (should succeed)
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default", "--force"])
+    cmd.args(["pr", "add", "default", "--force"])
         .assert()
         .success();
 
@@ -107,7 +107,7 @@ fn test_pr_add_force_overwrite() {
 fn test_pr_add_invalid_type() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["pr", "add", "invalid-template"])
+    cmd.args(["pr", "add", "invalid-template"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("Not Found"));
@@ -120,7 +120,7 @@ fn test_pr_add_unknown_argument() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "--unknown"])
+    cmd.args(["pr", "add", "--unknown"])
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -138,7 +138,7 @@ fn test_pr_add_valid_and_invalid_template() {
     // Add both a valid and an invalid template in a single command
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default", "invalid-template"])
+    cmd.args(["pr", "add", "default", "invalid-template"])
         .assert()
         .failure()
         .stderr(
@@ -158,7 +158,7 @@ fn test_pr_add_default_with_output_without_ext() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default", "-o", "default"])
+    cmd.args(["pr", "add", "default", "-o", "default"])
         .assert()
         .success()
         .stdout(predicate::str::contains("default.md - has been added."));
@@ -173,7 +173,7 @@ fn test_pr_add_default_with_output_with_ext() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default", "-o", "default.md"])
+    cmd.args(["pr", "add", "default", "-o", "default.md"])
         .assert()
         .success()
         .stdout(predicate::str::contains("default.md - has been added."));
@@ -189,7 +189,7 @@ fn test_pr_add_uneven_templates_and_outputs() {
     // Provide two templates but only one output file name
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "add", "default", "default", "-o", "file1.md"])
+    cmd.args(["pr", "add", "default", "default", "-o", "file1.md"])
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -202,7 +202,7 @@ fn test_pr_add_uneven_templates_and_outputs() {
 fn test_pr_list() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["pr", "list"])
+    cmd.args(["pr", "list"])
         .assert()
         .success()
         .stdout(predicate::str::contains("default.md"));
@@ -217,7 +217,7 @@ fn test_pr_preview_single() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "preview", "default"])
+    cmd.args(["pr", "preview", "default"])
         .assert()
         .success()
         .stdout(predicate::str::is_match(".+").unwrap());
@@ -230,7 +230,7 @@ fn test_pr_preview_invalid_id() {
 
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
     cmd.current_dir(&temp_path);
-    cmd.args(&["pr", "preview", "not-a-template"])
+    cmd.args(["pr", "preview", "not-a-template"])
         .assert()
         .failure()
         .stderr(
@@ -245,7 +245,7 @@ fn test_pr_preview_invalid_id() {
 fn test_pr_help_command() {
     let _temp_dir = setup_test_env();
     let mut cmd = AssertCommand::cargo_bin("gh-templates").unwrap();
-    cmd.args(&["pr", "--help"])
+    cmd.args(["pr", "--help"])
         .assert()
         .success()
         .stdout(predicate::str::contains("PR"))